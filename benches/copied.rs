@@ -0,0 +1,70 @@
+#![feature(test)]
+
+extern crate test;
+
+use const_combinations::SliceExt;
+use test::{black_box, Bencher};
+
+const ITEMS: [u32; 24] = [
+    3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4, 6, 2, 6, 4,
+];
+
+#[bench]
+fn combinations_by_ref(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS)
+            .combinations::<4>()
+            .map(|c| c.iter().fold(0u32, |sum, &&x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
+
+#[bench]
+fn combinations_copied(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS)
+            .combinations_copied::<4>()
+            .map(|c| c.iter().fold(0u32, |sum, &x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
+
+#[bench]
+fn permutations_by_ref(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS[..12])
+            .permutations::<4>()
+            .map(|p| p.iter().fold(0u32, |sum, &&x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
+
+#[bench]
+fn permutations_copied(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS[..12])
+            .permutations_copied::<4>()
+            .map(|p| p.iter().fold(0u32, |sum, &x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
+
+#[bench]
+fn permutations_heap_k6(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS[..10])
+            .permutations::<6>()
+            .map(|p| p.iter().fold(0u32, |sum, &&x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
+
+#[bench]
+fn permutations_lex_k6(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(&ITEMS[..10])
+            .lex_permutations::<6>()
+            .map(|p| p.iter().fold(0u32, |sum, &&x| sum.wrapping_add(x)))
+            .fold(0u32, u32::wrapping_add)
+    });
+}
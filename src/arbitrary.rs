@@ -0,0 +1,81 @@
+//! `Arbitrary` implementations for the iterators which don't borrow any items.
+//!
+//! Every iterator is created through its regular constructor from arbitrary
+//! parameters and is then advanced an arbitrary number of steps, so that fuzzers
+//! can reach states in the middle of an iteration while all the invariants of
+//! the underlying generators still hold.
+
+use crate::{
+    Assignments, BitCombinations, CombinationMasks, Compositions, DyckWords, GrayCodeDeltas,
+    GrayCodes, LatticePaths, LyndonWords, MixedRadix, Necklaces, NonCrossingPartitions, Partitions,
+    RoundRobin, SetPartitions, SetPartitionsInto, SurjectiveAssignments, WeakCompositions,
+};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+macro_rules! impl_arbitrary {
+    ($([$($params:tt)*] $ty:ty => |$u:ident| $make:expr;)*) => {$(
+        impl<'a, $($params)*> Arbitrary<'a> for $ty {
+            fn arbitrary($u: &mut Unstructured<'a>) -> Result<Self> {
+                let mut iter = $make;
+                let skip: u8 = $u.arbitrary()?;
+                iter.by_ref().take(skip.into()).for_each(drop);
+                Ok(iter)
+            }
+        }
+    )*};
+}
+
+impl_arbitrary! {
+    [const K: usize] BitCombinations<u64, K> => |u| Self::new(u.int_in_range(0..=u64::BITS)?);
+    [const K: usize] BitCombinations<u128, K> => |u| Self::new(u.int_in_range(0..=u128::BITS)?);
+    [const N: usize, const K: usize] CombinationMasks<N, K> => |_u| Self::new();
+    [const K: usize] Compositions<K> => |u| Self::new(u.arbitrary::<u8>()?.into());
+    [const K: usize] WeakCompositions<K> => |u| Self::new(u.arbitrary::<u8>()?.into());
+    [const N: usize] DyckWords<N> => |_u| Self::new();
+    [const N: usize] GrayCodes<N> => |_u| Self::new();
+    [const N: usize] GrayCodeDeltas<N> => |_u| Self::new();
+    [const N: usize] Necklaces<N> => |u| Self::new(u.arbitrary::<u8>()?.into());
+    [const N: usize] LyndonWords<N> => |u| Self::new(u.arbitrary::<u8>()?.into());
+    [] Partitions => |u| Self::new(u.arbitrary::<u8>()?.into());
+    [const D: usize] MixedRadix<D> => |u| Self::new(u.arbitrary::<[u8; D]>()?.map(usize::from));
+    [const N: usize] SetPartitions<N> => |_u| Self::new();
+    [const N: usize, const K: usize] SetPartitionsInto<N, K> => |_u| Self::new();
+    [const N: usize] NonCrossingPartitions<N> => |_u| Self::new();
+    [const M: usize, const N: usize, const L: usize] LatticePaths<M, N, L> => |_u| Self::new();
+    [const N: usize, const P: usize] RoundRobin<N, P> => |_u| Self::new();
+    [const N: usize, const K: usize] Assignments<N, K> => |_u| Self::new();
+    [const N: usize, const K: usize] SurjectiveAssignments<N, K> => |_u| Self::new();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{compositions, gray_codes, mixed_radix};
+
+    #[test]
+    fn skips_steps() {
+        let mut u = Unstructured::new(&[3]);
+        let codes = GrayCodes::<3>::arbitrary(&mut u).unwrap();
+        assert!(codes.eq(gray_codes::<3>().skip(3)));
+    }
+
+    #[test]
+    fn uses_parameters() {
+        let mut u = Unstructured::new(&[5, 2]);
+        let parts = Compositions::<3>::arbitrary(&mut u).unwrap();
+        assert!(parts.eq(compositions::<3>(5).skip(2)));
+
+        let mut u = Unstructured::new(&[2, 0, 3, 4]);
+        let digits = MixedRadix::<3>::arbitrary(&mut u).unwrap();
+        assert_eq!(digits.bases(), &[2, 0, 3]);
+        assert!(digits.eq(mixed_radix([2, 0, 3])));
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let bytes = [u8::MAX; 16];
+        let mut u = Unstructured::new(&bytes);
+        let masks = BitCombinations::<u64, 2>::arbitrary(&mut u).unwrap();
+        assert!(masks.into_iter().all(|mask| mask.count_ones() == 2));
+    }
+}
@@ -0,0 +1,162 @@
+//! Writing the arrays of an iterator into an arena.
+//!
+//! Arrays which have to outlive the loop that produces them would otherwise
+//! each be moved into an allocation of their own. The [`in_arena`] adaptor
+//! instead asks an [`Arena`] for room for every array, and hands out
+//! references to it.
+//!
+//! [`in_arena`]: crate::IterExt::in_arena
+
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+/// A source of uninitialized memory which lives for `'a`.
+///
+/// This is implemented for closures which take a number of elements and
+/// return that many uninitialized slots, and for [`bumpalo::Bump`] with the
+/// `bumpalo` feature.
+///
+/// [`bumpalo::Bump`]: https://docs.rs/bumpalo/3/bumpalo/struct.Bump.html
+pub trait Arena<'a, T: 'a> {
+    /// Return room for `len` elements.
+    ///
+    /// The returned slice has to be exactly `len` elements long.
+    fn alloc_slots(&mut self, len: usize) -> &'a mut [MaybeUninit<T>];
+}
+
+impl<'a, T, F> Arena<'a, T> for F
+where
+    T: 'a,
+    F: FnMut(usize) -> &'a mut [MaybeUninit<T>],
+{
+    fn alloc_slots(&mut self, len: usize) -> &'a mut [MaybeUninit<T>] {
+        self(len)
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+impl<'a, T: 'a> Arena<'a, T> for &'a ::bumpalo::Bump {
+    fn alloc_slots(&mut self, len: usize) -> &'a mut [MaybeUninit<T>] {
+        self.alloc_slice_fill_with(len, |_| MaybeUninit::uninit())
+    }
+}
+
+/// An iterator that moves the arrays of another iterator into an arena.
+///
+/// This `struct` is created by the [`in_arena`] method on [`IterExt`]. See its
+/// documentation for more.
+///
+/// [`in_arena`]: crate::IterExt::in_arena
+/// [`IterExt`]: crate::IterExt
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct InArena<'a, I, A> {
+    iter: I,
+    arena: A,
+    lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a, I, A> InArena<'a, I, A> {
+    pub(crate) fn new(iter: I, arena: A) -> Self {
+        Self {
+            iter,
+            arena,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'a, I, A, T, const K: usize> Iterator for InArena<'a, I, A>
+where
+    I: Iterator<Item = [T; K]>,
+    A: Arena<'a, T>,
+    T: 'a,
+{
+    type Item = &'a mut [T; K];
+
+    fn next(&mut self) -> Option<&'a mut [T; K]> {
+        let arr = self.iter.next()?;
+        let slots = self.arena.alloc_slots(K);
+        assert_eq!(
+            slots.len(),
+            K,
+            "the arena returned the wrong number of slots"
+        );
+        for (slot, t) in slots.iter_mut().zip(IntoIterator::into_iter(arr)) {
+            slot.write(t);
+        }
+        // SAFETY: the slice is `K` elements long, and every one of them has
+        // just been initialized
+        Some(unsafe { &mut *(slots.as_mut_ptr() as *mut [T; K]) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, A, T, const K: usize> ExactSizeIterator for InArena<'a, I, A>
+where
+    I: ExactSizeIterator<Item = [T; K]>,
+    A: Arena<'a, T>,
+    T: 'a,
+{
+}
+
+impl<'a, I, A, T, const K: usize> FusedIterator for InArena<'a, I, A>
+where
+    I: FusedIterator<Item = [T; K]>,
+    A: Arena<'a, T>,
+    T: 'a,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+    use alloc::vec::Vec;
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn closure_arena() {
+        let mut storage = [MaybeUninit::uninit(); 30];
+        let mut free = &mut storage[..];
+        let all: Vec<_> = (0..5)
+            .combinations::<3>()
+            .in_arena(|len| {
+                let (slots, rest) = core::mem::take(&mut free).split_at_mut(len);
+                free = rest;
+                slots
+            })
+            .collect();
+        assert_eq!(all.len(), 10);
+        assert!(all.iter().map(|c| **c).eq((0..5).combinations::<3>()));
+    }
+
+    #[test]
+    #[should_panic(expected = "the arena returned the wrong number of slots")]
+    fn short_slots() {
+        let mut storage = [MaybeUninit::<u8>::uninit(); 1];
+        let mut slots = Some(&mut storage[..]);
+        let _ = (0..3u8)
+            .permutations::<2>()
+            .in_arena(|_| slots.take().unwrap())
+            .next();
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn bump() {
+        use crate::SliceExt;
+
+        let bump = ::bumpalo::Bump::new();
+        let items = [1, 2, 3, 4];
+        let mut permutations = items.permutations::<2>().in_arena(&bump);
+        assert_eq!(permutations.size_hint(), (12, Some(12)));
+        let first = permutations.next().unwrap();
+        let rest: Vec<_> = permutations.collect();
+        assert_eq!(*first, [&1, &2]);
+        assert_eq!(rest.len(), 11);
+        assert!(bump.allocated_bytes() >= 12 * core::mem::size_of::<[&i32; 2]>());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::permutations::LazyPermutationGenerator;
+use crate::set_partitions::LazySetPartitionGenerator;
+use crate::MixedRadix;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over every assignment of `N` labeled items to `K`
+/// labeled bins.
+///
+/// Each assignment is yielded as an array in which item `i` goes into bin
+/// `bins[i]`. The last item varies fastest, so the assignments are produced in
+/// lexicographic order; there are `K` to the power of `N` of them. Unlike with
+/// [`set_partitions`] the bins are told apart, and they may be left empty.
+///
+/// [`set_partitions`]: crate::set_partitions
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::assignments;
+///
+/// let mut assignments = assignments::<2, 3>();
+/// assert_eq!(assignments.size_hint(), (9, Some(9)));
+/// assert_eq!(assignments.next(), Some([0, 0]));
+/// assert_eq!(assignments.next(), Some([0, 1]));
+/// assert_eq!(assignments.next(), Some([0, 2]));
+/// assert_eq!(assignments.next(), Some([1, 0]));
+/// assert_eq!(assignments.last(), Some([2, 2]));
+/// ```
+pub fn assignments<const N: usize, const K: usize>() -> Assignments<N, K> {
+    Assignments::new()
+}
+
+/// An iterator that returns the assignments of items to bins.
+///
+/// This `struct` is created by the [`assignments`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Assignments<const N: usize, const K: usize> {
+    bins: MixedRadix<N>,
+}
+
+impl<const N: usize, const K: usize> Assignments<N, K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            bins: MixedRadix::new([K; N]),
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> Iterator for Assignments<N, K> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        self.bins.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bins.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<[usize; N]> {
+        self.bins.nth(n)
+    }
+}
+
+impl<const N: usize, const K: usize> FusedIterator for Assignments<N, K> {}
+
+/// Return an iterator over the assignments of `N` labeled items to `K` labeled
+/// bins which leave none of the bins empty.
+///
+/// Assignments are yielded in the same form as [`assignments`]. Rather than
+/// filtering out the assignments with an empty bin, every partition of the
+/// items into `K` blocks from [`set_partitions_into`] is taken in turn, and its
+/// blocks are put into the bins in each of the `K!` orders of [`permutations`].
+/// There are `K!` times the Stirling number of the second kind `S(N, K)` of
+/// them. If `K` is greater than `N` the resulting iterator will yield no items.
+///
+/// [`set_partitions_into`]: crate::set_partitions_into
+/// [`permutations`]: crate::SliceExt::permutations
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::surjective_assignments;
+///
+/// let mut assignments = surjective_assignments::<3, 2>();
+/// assert_eq!(assignments.next(), Some([0, 0, 1]));
+/// assert_eq!(assignments.next(), Some([1, 1, 0]));
+/// assert_eq!(assignments.next(), Some([0, 1, 0]));
+/// assert_eq!(assignments.next(), Some([1, 0, 1]));
+/// assert_eq!(assignments.next(), Some([0, 1, 1]));
+/// assert_eq!(assignments.next(), Some([1, 0, 0]));
+/// assert_eq!(assignments.next(), None);
+/// ```
+#[doc(alias = "surjections")]
+pub fn surjective_assignments<const N: usize, const K: usize>() -> SurjectiveAssignments<N, K> {
+    SurjectiveAssignments::new()
+}
+
+/// An iterator that returns the assignments of items to bins which leave no bin
+/// empty.
+///
+/// This `struct` is created by the [`surjective_assignments`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SurjectiveAssignments<const N: usize, const K: usize> {
+    partitions: LazySetPartitionGenerator<N>,
+    // `labels[b]` is the bin which block `b` of the partition goes into
+    labels: LazyPermutationGenerator<K>,
+}
+
+impl<const N: usize, const K: usize> SurjectiveAssignments<N, K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            partitions: LazySetPartitionGenerator::with_blocks(K),
+            labels: LazyPermutationGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> Iterator for SurjectiveAssignments<N, K> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        if self.partitions.is_done() {
+            return None;
+        }
+        let labels = self.labels.indices();
+        let res = self.partitions.blocks().map(|block| labels[block]);
+        self.labels.step();
+        if self.labels.is_done() {
+            self.labels.reset();
+            self.partitions.step();
+        }
+        Some(res)
+    }
+}
+
+impl<const N: usize, const K: usize> FusedIterator for SurjectiveAssignments<N, K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::counting::{factorial, stirling2};
+    use crate::{set_partitions_into, SliceExt};
+    use alloc::collections::BTreeSet;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn count() {
+        assert_eq!(assignments::<4, 3>().count(), 81);
+        assert_eq!(assignments::<3, 1>().count(), 1);
+        assert_eq!(assignments::<3, 0>().next(), None);
+        let mut empty = assignments::<0, 0>();
+        assert_eq!(empty.next(), Some([]));
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut assignments = assignments::<3, 4>();
+        assert_eq!(assignments.size_hint(), (64, Some(64)));
+        assert_eq!(assignments.nth(20), Some([1, 1, 0]));
+        assert_eq!(assignments.size_hint(), (43, Some(43)));
+    }
+
+    #[test]
+    fn surjective_matches_filtered() {
+        let filtered: BTreeSet<_> = assignments::<6, 3>()
+            .filter(|bins| (0..3).all(|bin| bins.contains(&bin)))
+            .collect();
+        let generated: BTreeSet<_> = surjective_assignments::<6, 3>().collect();
+        assert_eq!(surjective_assignments::<6, 3>().count(), generated.len());
+        assert_eq!(generated, filtered);
+    }
+
+    #[test]
+    fn surjective_order() {
+        let labels: Vec<[usize; 3]> = [0, 1, 2].permutations_copied::<3>().collect();
+        let expected = set_partitions_into::<5, 3>()
+            .flat_map(|blocks| labels.iter().map(move |label| blocks.map(|b| label[b])));
+        assert!(surjective_assignments::<5, 3>().eq(expected));
+    }
+
+    #[test]
+    fn surjective_count() {
+        fn check<const N: usize, const K: usize>() {
+            let expected = factorial(K).unwrap() * stirling2(N, K).unwrap();
+            assert_eq!(surjective_assignments::<N, K>().count(), expected);
+        }
+        check::<1, 1>();
+        check::<5, 2>();
+        check::<6, 4>();
+        check::<7, 7>();
+        check::<3, 4>();
+    }
+
+    #[test]
+    fn surjective_edge_cases() {
+        let mut empty = surjective_assignments::<0, 0>();
+        assert_eq!(empty.next(), Some([]));
+        assert_eq!(empty.next(), None);
+        assert_eq!(surjective_assignments::<2, 0>().next(), None);
+        assert_eq!(surjective_assignments::<0, 2>().next(), None);
+        let mut bijections = surjective_assignments::<3, 3>();
+        assert_eq!(bijections.next(), Some([0, 1, 2]));
+        assert_eq!(bijections.last(), Some([2, 1, 0]));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::make_array;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyBacktrackGenerator<const K: usize> {
+    indices: [usize; K],
+    used: Vec<bool>,
+    started: bool,
+    done: bool,
+}
+
+impl<const K: usize> LazyBacktrackGenerator<K> {
+    pub fn new(item_count: usize) -> Self {
+        Self {
+            indices: [0; K],
+            used: vec![false; item_count],
+            started: false,
+            done: K > item_count,
+        }
+    }
+
+    pub fn indices(&self) -> &[usize; K] {
+        &self.indices
+    }
+
+    /// Move to the next arrangement of distinct indices accepted by `accept`,
+    /// returning `false` once there are none left.
+    ///
+    /// `accept` is called with a position, the indices chosen for the positions
+    /// before it, and a candidate index for that position.
+    pub fn step<F>(&mut self, mut accept: F) -> bool
+    where
+        F: FnMut(usize, &[usize], usize) -> bool,
+    {
+        if self.done {
+            return false;
+        }
+        let (mut pos, mut start) = if !self.started {
+            self.started = true;
+            if K == 0 {
+                return true;
+            }
+            (0, 0)
+        } else if K == 0 {
+            self.done = true;
+            return false;
+        } else {
+            // Resume the search right after the previous arrangement
+            self.used[self.indices[K - 1]] = false;
+            (K - 1, self.indices[K - 1] + 1)
+        };
+        loop {
+            let candidate = (start..self.used.len())
+                .find(|&c| !self.used[c] && accept(pos, &self.indices[..pos], c));
+            match candidate {
+                Some(c) => {
+                    self.indices[pos] = c;
+                    self.used[c] = true;
+                    if pos + 1 == K {
+                        return true;
+                    }
+                    pos += 1;
+                    start = 0;
+                }
+                None if pos == 0 => {
+                    self.done = true;
+                    return false;
+                }
+                None => {
+                    // Nothing fits here, so backtrack to the previous position
+                    pos -= 1;
+                    self.used[self.indices[pos]] = false;
+                    start = self.indices[pos] + 1;
+                }
+            }
+        }
+    }
+}
+
+/// An iterator that returns k-length permutations of values from `slice` which
+/// satisfy a constraint.
+///
+/// This `struct` is created by the [`constrained_permutations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`constrained_permutations`]: super::SliceExt::constrained_permutations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceConstrainedPermutations<'a, T, F, const K: usize> {
+    items: &'a [T],
+    gen: LazyBacktrackGenerator<K>,
+    chosen: Vec<&'a T>,
+    accept: F,
+}
+
+impl<'a, T, F, const K: usize> SliceConstrainedPermutations<'a, T, F, K>
+where
+    F: FnMut(usize, &[&'a T], &'a T) -> bool,
+{
+    pub(crate) fn new(items: &'a [T], accept: F) -> Self {
+        Self {
+            items,
+            gen: LazyBacktrackGenerator::new(items.len()),
+            chosen: Vec::with_capacity(K),
+            accept,
+        }
+    }
+}
+
+impl<'a, T, F, const K: usize> Iterator for SliceConstrainedPermutations<'a, T, F, K>
+where
+    F: FnMut(usize, &[&'a T], &'a T) -> bool,
+{
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        let Self {
+            items,
+            gen,
+            chosen,
+            accept,
+        } = self;
+        let found = gen.step(|pos, prefix, candidate| {
+            // Keep the chosen elements in sync with the indices chosen so far
+            chosen.truncate(pos);
+            let len = chosen.len();
+            chosen.extend(prefix[len..].iter().map(|&i| &items[i]));
+            accept(pos, chosen, &items[candidate])
+        });
+        if found {
+            let indices = gen.indices();
+            Some(make_array(|i| &items[indices[i]]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, F, const K: usize> FusedIterator for SliceConstrainedPermutations<'a, T, F, K> where
+    F: FnMut(usize, &[&'a T], &'a T) -> bool
+{
+}
+
+/// An iterator that returns k-length permutations of values from `slice` in
+/// which every element is in a position it is allowed in.
+///
+/// This `struct` is created by the [`permutations_where`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`permutations_where`]: super::SliceExt::permutations_where
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SlicePositionPermutations<'a, T, const K: usize> {
+    items: &'a [T],
+    gen: LazyBacktrackGenerator<K>,
+    // `allowed[pos * items.len() + i]` is whether `items[i]` may be at `pos`
+    allowed: Vec<bool>,
+}
+
+impl<'a, T, const K: usize> SlicePositionPermutations<'a, T, K> {
+    pub(crate) fn new<F>(items: &'a [T], mut allowed: F) -> Self
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let n = items.len();
+        let allowed = if K > n {
+            Vec::new()
+        } else {
+            (0..K * n).map(|i| allowed(i / n, i % n)).collect()
+        };
+        Self {
+            items,
+            gen: LazyBacktrackGenerator::new(n),
+            allowed,
+        }
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SlicePositionPermutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        let n = self.items.len();
+        let allowed = &self.allowed;
+        if self
+            .gen
+            .step(|pos, _, candidate| allowed[pos * n + candidate])
+        {
+            let indices = self.gen.indices();
+            Some(make_array(|i| &self.items[indices[i]]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SlicePositionPermutations<'_, T, K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SliceExt;
+
+    #[test]
+    fn unconstrained_order() {
+        let mut permutations = [1, 2, 3].constrained_permutations(|_, _, _| true);
+        assert_eq!(permutations.next(), Some([&1, &2]));
+        assert_eq!(permutations.next(), Some([&1, &3]));
+        assert_eq!(permutations.next(), Some([&2, &1]));
+        assert_eq!(permutations.next(), Some([&2, &3]));
+        assert_eq!(permutations.next(), Some([&3, &1]));
+        assert_eq!(permutations.next(), Some([&3, &2]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn gen_prunes() {
+        let mut calls = 0;
+        let mut gen = LazyBacktrackGenerator::<3>::new(3);
+        // Refuse everything in the first position
+        assert!(!gen.step(|pos, _, _| {
+            calls += 1;
+            pos > 0
+        }));
+        assert_eq!(calls, 3);
+        assert!(!gen.step(|_, _, _| true));
+    }
+
+    #[test]
+    fn queens() {
+        // https://oeis.org/A000170
+        fn queens<const N: usize>() -> usize {
+            let columns: [usize; N] = make_array(|i| i);
+            columns
+                .constrained_permutations::<N, _>(|row, placed, &col| {
+                    placed
+                        .iter()
+                        .enumerate()
+                        .all(|(r, &&c)| c.abs_diff(col) != row - r)
+                })
+                .count()
+        }
+        assert_eq!(queens::<4>(), 2);
+        assert_eq!(queens::<6>(), 4);
+        assert_eq!(queens::<8>(), 92);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut permutations = [1].constrained_permutations::<2, _>(|_, _, _| true);
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = [1, 2].constrained_permutations::<0, _>(|_, _, _| false);
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod position_test {
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        // Nobody works the shift matching their own number
+        let mut rota = [0, 1, 2].permutations_where::<3, _>(|shift, worker| shift != worker);
+        assert_eq!(rota.next(), Some([&1, &2, &0]));
+        assert_eq!(rota.next(), Some([&2, &0, &1]));
+        assert_eq!(rota.next(), None);
+        assert_eq!(rota.next(), None);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let items = [1, 2, 3, 4, 5];
+        let allowed = |pos: usize, i: usize| pos != i && pos + 1 != i;
+        let mut filtered: Vec<_> = items
+            .permutations::<3>()
+            .filter(|p| {
+                p.iter()
+                    .enumerate()
+                    .all(|(pos, &&x)| allowed(pos, x as usize - 1))
+            })
+            .collect();
+        filtered.sort();
+        let found: Vec<_> = items.permutations_where::<3, _>(allowed).collect();
+        assert_eq!(found, filtered);
+    }
+
+    #[test]
+    fn calls_once_per_pair() {
+        let mut calls = 0;
+        let count = [1, 2, 3, 4]
+            .permutations_where::<2, _>(|_, _| {
+                calls += 1;
+                true
+            })
+            .count();
+        assert_eq!((count, calls), (12, 8));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut permutations = [1].permutations_where::<2, _>(|_, _| true);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = [1, 2].permutations_where::<0, _>(|_, _| false);
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+    }
+}
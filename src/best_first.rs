@@ -0,0 +1,335 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use core::iter::{empty, FusedIterator, Iterator, Sum};
+use core::ops::Add;
+
+/// A combination which has been chosen up to position `len`, and which may
+/// still choose any of the elements from `next` onwards.
+#[derive(Clone)]
+struct Node<S, const K: usize> {
+    // The score of the chosen elements plus the bound on what the rest can add
+    priority: S,
+    score: S,
+    indices: [usize; K],
+    len: usize,
+    next: usize,
+}
+
+impl<S, const K: usize> Node<S, K>
+where
+    S: Ord,
+{
+    fn key(&self) -> (&S, usize, Reverse<&[usize]>) {
+        // Among equal priorities finish deeper prefixes first, so complete
+        // combinations come out as soon as possible, and in lexicographic order
+        (&self.priority, self.len, Reverse(&self.indices[..self.len]))
+    }
+}
+
+impl<S, const K: usize> PartialEq for Node<S, K>
+where
+    S: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<S, const K: usize> Eq for Node<S, K> where S: Ord {}
+
+impl<S, const K: usize> PartialOrd for Node<S, K>
+where
+    S: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, const K: usize> Ord for Node<S, K>
+where
+    S: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// An iterator that returns k-length combinations of values from `slice` from
+/// the highest to the lowest score.
+///
+/// This `struct` is created by the [`best_first_combinations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`best_first_combinations`]: super::SliceExt::best_first_combinations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceBestFirstCombinations<'a, T, S, B, const K: usize> {
+    items: &'a [T],
+    scores: Vec<S>,
+    bound: B,
+    heap: BinaryHeap<Node<S, K>>,
+}
+
+impl<'a, T, S, B, const K: usize> SliceBestFirstCombinations<'a, T, S, B, K>
+where
+    S: Copy + Ord + Add<Output = S> + Sum,
+    B: FnMut(usize, usize) -> S,
+{
+    pub(crate) fn new<F>(items: &'a [T], score: F, bound: B) -> Self
+    where
+        F: FnMut(&'a T) -> S,
+    {
+        let mut this = Self {
+            items,
+            scores: items.iter().map(score).collect(),
+            bound,
+            heap: BinaryHeap::new(),
+        };
+        if K <= items.len() {
+            let zero = empty().sum();
+            this.push(zero, [0; K], 0, 0);
+        }
+        this
+    }
+
+    fn push(&mut self, score: S, indices: [usize; K], len: usize, next: usize) {
+        let priority = if len == K {
+            score
+        } else {
+            score + (self.bound)(next, K - len)
+        };
+        self.heap.push(Node {
+            priority,
+            score,
+            indices,
+            len,
+            next,
+        });
+    }
+}
+
+impl<'a, T, S, B, const K: usize> Iterator for SliceBestFirstCombinations<'a, T, S, B, K>
+where
+    S: Copy + Ord + Add<Output = S> + Sum,
+    B: FnMut(usize, usize) -> S,
+{
+    type Item = ([&'a T; K], S);
+
+    fn next(&mut self) -> Option<([&'a T; K], S)> {
+        while let Some(node) = self.heap.pop() {
+            let Node {
+                score,
+                mut indices,
+                len,
+                next,
+                ..
+            } = node;
+            if len == K {
+                return Some((indices.map(|i| &self.items[i]), score));
+            }
+            // Either skip the next element, if enough are left after it...
+            if self.items.len() - next > K - len {
+                self.push(score, indices, len, next + 1);
+            }
+            // ...or choose it
+            indices[len] = next;
+            self.push(score + self.scores[next], indices, len + 1, next + 1);
+        }
+        None
+    }
+}
+
+impl<T, S, B, const K: usize> FusedIterator for SliceBestFirstCombinations<'_, T, S, B, K>
+where
+    S: Copy + Ord + Add<Output = S> + Sum,
+    B: FnMut(usize, usize) -> S,
+{
+}
+
+/// Return the `k` combinations of `items` with the smallest total key, from
+/// the smallest up.
+pub(crate) fn k_smallest_by_key<'a, T, S, F, const K: usize>(
+    items: &'a [T],
+    k: usize,
+    mut f: F,
+) -> Vec<[&'a T; K]>
+where
+    S: Copy + Ord + Add<Output = S> + Sum,
+    F: FnMut(&'a T) -> S,
+{
+    if k == 0 || K > items.len() {
+        return Vec::new();
+    }
+    // Search the elements from the smallest key up, so that the cheapest way
+    // to complete a prefix is always to take the elements right after it
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let keys: Vec<S> = items.iter().map(&mut f).collect();
+    order.sort_by_key(|&i| keys[i]);
+    let mut search = Search {
+        keys: order.iter().map(|&i| keys[i]).collect(),
+        k,
+        heap: BinaryHeap::with_capacity(k),
+        chosen: [0; K],
+    };
+    search.visit(0, 0, empty().sum());
+    search
+        .heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(_, positions)| {
+            let mut indices = positions.map(|p| order[p]);
+            indices.sort_unstable();
+            indices.map(|i| &items[i])
+        })
+        .collect()
+}
+
+/// A depth-first search for the `k` smallest combinations of sorted keys.
+struct Search<S, const K: usize> {
+    keys: Vec<S>,
+    k: usize,
+    // The best combinations found so far, with the worst of them on top
+    heap: BinaryHeap<(S, [usize; K])>,
+    chosen: [usize; K],
+}
+
+impl<S, const K: usize> Search<S, K>
+where
+    S: Copy + Ord + Add<Output = S> + Sum,
+{
+    fn visit(&mut self, len: usize, start: usize, sum: S) {
+        if len == K {
+            // Anything that got this far beats the worst combination found so
+            // far, since ties are found in order and were pruned below
+            if self.heap.len() == self.k {
+                self.heap.pop();
+            }
+            self.heap.push((sum, self.chosen));
+            return;
+        }
+        let count = K - len;
+        for next in start..=self.keys.len() - count {
+            let bound = sum + self.keys[next..next + count].iter().copied().sum();
+            if self.heap.len() == self.k && bound >= self.heap.peek().unwrap().0 {
+                // Later elements have larger keys, so they can't do better
+                break;
+            }
+            self.chosen[len] = next;
+            self.visit(len + 1, next + 1, sum + self.keys[next]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let items = [3, 1, 4, 1, 5];
+        let mut best = items.best_first_combinations::<2, _, _, _>(|&x| x, |_, count| 5 * count);
+        assert_eq!(best.next(), Some(([&4, &5], 9)));
+        assert_eq!(best.next(), Some(([&3, &5], 8)));
+        assert_eq!(best.next(), Some(([&3, &4], 7)));
+        assert_eq!(best.next(), Some(([&1, &5], 6)));
+        assert_eq!(best.next(), Some(([&1, &5], 6)));
+        assert_eq!(best.count(), 5);
+    }
+
+    #[test]
+    fn matches_sorted() {
+        let items: Vec<i64> = (0..11).map(|i| (i * 7 % 11) - 4).collect();
+        // Bound what `count` more elements from `start` onwards can add by the
+        // largest ones in that range
+        let bound = |start: usize, count: usize| {
+            let mut rest = items[start..].to_vec();
+            rest.sort_by(|a, b| b.cmp(a));
+            rest.iter().take(count).sum()
+        };
+        let best: Vec<i64> = items
+            .best_first_combinations::<4, _, _, _>(|&x| x, bound)
+            .map(|(_, score)| score)
+            .collect();
+        let mut sorted: Vec<i64> = items
+            .combinations::<4>()
+            .map(|c| c.iter().copied().sum())
+            .collect();
+        sorted.sort_by(|a, b| b.cmp(a));
+        assert_eq!(best, sorted);
+    }
+
+    #[test]
+    fn top_k_expands_little() {
+        let items: Vec<u32> = (0..40).collect();
+        let mut calls = 0;
+        let top: Vec<_> = items
+            .best_first_combinations::<3, _, _, _>(
+                |&x| x,
+                |_, count| {
+                    calls += 1;
+                    // The largest elements are at the end of the slice
+                    (0..count as u32).map(|i| 39 - i).sum()
+                },
+            )
+            .take(2)
+            .collect();
+        assert_eq!(top, [([&37, &38, &39], 114), ([&36, &38, &39], 113)]);
+        assert!(calls < 400);
+    }
+
+    #[test]
+    fn k_smallest_matches_sorted() {
+        let items: Vec<i64> = (0..13).map(|i| (i * 5 % 13) - 6).collect();
+        let sum = |c: &[&i64; 3]| c.iter().copied().sum::<i64>();
+        let mut sorted: Vec<_> = items.combinations::<3>().collect();
+        sorted.sort_by_key(sum);
+        for k in [0, 1, 7, 286, 1000] {
+            let smallest = items.k_smallest_by_key::<3, _, _>(k, |&x| x);
+            // Ties may come out in another order than the sort's
+            assert!(smallest.iter().map(sum).eq(sorted.iter().take(k).map(sum)));
+        }
+        let mut all = items.k_smallest_by_key::<3, _, _>(1000, |&x| x);
+        all.sort();
+        sorted.sort();
+        assert_eq!(all, sorted);
+    }
+
+    #[test]
+    fn k_smallest_prunes() {
+        // There are over 8 * 10^12 combinations, so this only finishes if
+        // nearly all of them are pruned
+        let items: Vec<u64> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        let smallest = items.k_smallest_by_key::<5, _, _>(3, |&x| x);
+        let sums: Vec<u64> = smallest.iter().map(|c| c.iter().copied().sum()).collect();
+        assert_eq!(sums, [10, 11, 12]);
+    }
+
+    #[test]
+    fn k_smallest_ties() {
+        let items = [2, 1, 1, 2, 1];
+        let smallest = items.k_smallest_by_key::<2, _, _>(4, |&x| x);
+        assert_eq!(smallest, [[&1, &1], [&1, &1], [&1, &1], [&2, &1]]);
+        assert_eq!(
+            [1, 2].k_smallest_by_key::<0, _, _>(3, |&x| x),
+            [[] as [&i32; 0]]
+        );
+        assert!([1, 2].k_smallest_by_key::<3, _, _>(3, |&x| x).is_empty());
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut best = [1, 2].best_first_combinations::<3, _, _, _>(|&x| x, |_, _| 0);
+        assert_eq!(best.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut best = [1, 2].best_first_combinations::<0, _, _, _>(|&x| x, |_, _| 0);
+        assert_eq!(best.next(), Some(([], 0)));
+        assert_eq!(best.next(), None);
+    }
+}
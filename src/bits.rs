@@ -0,0 +1,269 @@
+use crate::combinations::LazyCombinationGenerator;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over the `K`-element subsets of `0..n` as `u64` bitmasks.
+///
+/// Each subset is yielded as a mask with exactly `K` bits set, in increasing
+/// numeric order, which matches the order of [`SliceExt::combinations`]. The masks
+/// are computed with Gosper's hack, which avoids building index arrays entirely.
+/// If `K` is greater than `n` the resulting iterator will yield no items.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 64.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::bit_combinations;
+///
+/// let mut masks = bit_combinations::<2>(3);
+/// assert_eq!(masks.next(), Some(0b011));
+/// assert_eq!(masks.next(), Some(0b101));
+/// assert_eq!(masks.next(), Some(0b110));
+/// assert_eq!(masks.next(), None);
+/// ```
+///
+/// [`SliceExt::combinations`]: super::SliceExt::combinations
+pub fn bit_combinations<const K: usize>(n: u32) -> BitCombinations<u64, K> {
+    BitCombinations::<u64, K>::new(n)
+}
+
+/// Return an iterator over the `K`-element subsets of `0..n` as `u128` bitmasks.
+///
+/// This is the same as [`bit_combinations`], but supports sets of up to 128
+/// elements.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 128.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::bit_combinations_u128;
+///
+/// let mut masks = bit_combinations_u128::<1>(100);
+/// assert_eq!(masks.next(), Some(1));
+/// assert_eq!(masks.last(), Some(1 << 99));
+/// ```
+pub fn bit_combinations_u128<const K: usize>(n: u32) -> BitCombinations<u128, K> {
+    BitCombinations::<u128, K>::new(n)
+}
+
+/// An iterator that returns the k-element subsets of a set as bitmasks.
+///
+/// This `struct` is created by the [`bit_combinations`] and
+/// [`bit_combinations_u128`] functions. See their documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct BitCombinations<M, const K: usize> {
+    mask: Option<M>,
+    n: u32,
+}
+
+macro_rules! impl_bit_combinations {
+    ($($mask:ty),*) => {$(
+        impl<const K: usize> BitCombinations<$mask, K> {
+            pub(crate) fn new(n: u32) -> Self {
+                assert!(
+                    n <= <$mask>::BITS,
+                    "cannot take subsets of {} elements with {}-bit masks",
+                    n,
+                    <$mask>::BITS,
+                );
+                let mask = match K {
+                    0 => Some(0),
+                    k if k <= n as usize => Some(<$mask>::MAX >> (<$mask>::BITS - k as u32)),
+                    _ => None,
+                };
+                Self { mask, n }
+            }
+
+            fn successor(&self, mask: $mask) -> Option<$mask> {
+                if mask == 0 {
+                    return None;
+                }
+                // Gosper's hack: move the lowest block of ones up by one, and
+                // shift the rest of that block back down to the bottom
+                let lowest = mask & mask.wrapping_neg();
+                let ripple = mask.checked_add(lowest)?;
+                let next = (((ripple ^ mask) >> 2) / lowest) | ripple;
+                match next.checked_shr(self.n) {
+                    Some(0) | None => Some(next),
+                    Some(_) => None,
+                }
+            }
+        }
+
+        impl<const K: usize> Iterator for BitCombinations<$mask, K> {
+            type Item = $mask;
+
+            fn next(&mut self) -> Option<$mask> {
+                let mask = self.mask?;
+                self.mask = self.successor(mask);
+                Some(mask)
+            }
+        }
+
+        impl<const K: usize> FusedIterator for BitCombinations<$mask, K> {}
+    )*};
+}
+
+impl_bit_combinations!(u64, u128);
+
+/// Return an iterator over the `K`-element subsets of an `N`-element set as
+/// membership masks.
+///
+/// The iterator produces a new `[bool; N]` array per iteration in which exactly
+/// `K` positions are `true`, in the same order as [`SliceExt::combinations`]. If
+/// `K` is greater than `N` the resulting iterator will yield no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::combination_masks;
+///
+/// let mut masks = combination_masks::<3, 2>();
+/// assert_eq!(masks.next(), Some([true, true, false]));
+/// assert_eq!(masks.next(), Some([true, false, true]));
+/// assert_eq!(masks.next(), Some([false, true, true]));
+/// assert_eq!(masks.next(), None);
+/// ```
+///
+/// [`SliceExt::combinations`]: super::SliceExt::combinations
+pub fn combination_masks<const N: usize, const K: usize>() -> CombinationMasks<N, K> {
+    CombinationMasks::new()
+}
+
+/// An iterator that returns the k-element subsets of a set as `[bool; N]` masks.
+///
+/// This `struct` is created by the [`combination_masks`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CombinationMasks<const N: usize, const K: usize> {
+    gen: LazyCombinationGenerator<K>,
+}
+
+impl<const N: usize, const K: usize> CombinationMasks<N, K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyCombinationGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> Iterator for CombinationMasks<N, K> {
+    type Item = [bool; N];
+
+    fn next(&mut self) -> Option<[bool; N]> {
+        if self.gen.is_done(N) {
+            None
+        } else {
+            let mut mask = [false; N];
+            for &i in self.gen.indices() {
+                mask[i] = true;
+            }
+            self.gen.step();
+            Some(mask)
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> FusedIterator for CombinationMasks<N, K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut masks = bit_combinations::<3>(5);
+        assert_eq!(masks.next(), Some(0b00111));
+        assert_eq!(masks.next(), Some(0b01011));
+        assert_eq!(masks.next(), Some(0b01101));
+        assert_eq!(masks.next(), Some(0b01110));
+        assert_eq!(masks.next(), Some(0b10011));
+        assert_eq!(masks.next(), Some(0b10101));
+        assert_eq!(masks.next(), Some(0b10110));
+        assert_eq!(masks.next(), Some(0b11001));
+        assert_eq!(masks.next(), Some(0b11010));
+        assert_eq!(masks.next(), Some(0b11100));
+        assert_eq!(masks.next(), None);
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    fn matches_slice_combinations() {
+        let items = [0, 1, 2, 3, 4, 5, 6];
+        let masks = items
+            .combinations::<4>()
+            .map(|c| c.iter().fold(0, |mask, &&i| mask | 1 << i));
+        assert!(masks.eq(bit_combinations::<4>(7)));
+    }
+
+    #[test]
+    fn full_width() {
+        let mut masks = bit_combinations::<64>(64);
+        assert_eq!(masks.next(), Some(u64::MAX));
+        assert_eq!(masks.next(), None);
+
+        let mut masks = bit_combinations_u128::<127>(128);
+        assert_eq!(masks.next(), Some(u128::MAX >> 1));
+        assert_eq!(masks.last(), Some(u128::MAX - 1));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut masks = bit_combinations::<4>(3);
+        assert_eq!(masks.next(), None);
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    fn empty_mask_on_k_zero() {
+        let mut masks = bit_combinations::<0>(3);
+        assert_eq!(masks.next(), Some(0));
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_n_too_big() {
+        let _ = bit_combinations::<1>(65);
+    }
+}
+
+#[cfg(test)]
+mod mask_test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut masks = combination_masks::<4, 2>();
+        assert_eq!(masks.next(), Some([true, true, false, false]));
+        assert_eq!(masks.next(), Some([true, false, true, false]));
+        assert_eq!(masks.next(), Some([false, true, true, false]));
+        assert_eq!(masks.next(), Some([true, false, false, true]));
+        assert_eq!(masks.next(), Some([false, true, false, true]));
+        assert_eq!(masks.next(), Some([false, false, true, true]));
+        assert_eq!(masks.next(), None);
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut masks = combination_masks::<2, 3>();
+        assert_eq!(masks.next(), None);
+        assert_eq!(masks.next(), None);
+    }
+
+    #[test]
+    fn empty_mask_on_k_zero() {
+        let mut masks = combination_masks::<2, 0>();
+        assert_eq!(masks.next(), Some([false, false]));
+        assert_eq!(masks.next(), None);
+    }
+}
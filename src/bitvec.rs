@@ -0,0 +1,265 @@
+//! Subsets as `bitvec` membership masks.
+//!
+//! Sets which are already modelled as bitmaps can be enumerated without going
+//! through index arrays: [`combination_bitvecs`] and [`combination_bitarrays`]
+//! produce the subsets of `0..n` as [`BitVec`]s and [`BitArray`]s, and
+//! [`BitSliceExt::combination_masks`] produces the subsets of the bits which
+//! are set in a [`BitSlice`].
+
+use crate::combinations::LazyCombinationGenerator;
+use ::bitvec::array::BitArray;
+use ::bitvec::order::{BitOrder, Lsb0};
+use ::bitvec::slice::BitSlice;
+use ::bitvec::store::BitStore;
+use ::bitvec::vec::BitVec;
+use ::bitvec::view::BitViewSized;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+use core::marker::PhantomData;
+
+/// Return an iterator over the `K`-element subsets of `0..n` as [`BitVec`]
+/// masks.
+///
+/// The iterator produces a new mask of `n` bits per iteration in which exactly
+/// `K` bits are set, in the same order as [`combination_masks`]. If `K` is
+/// greater than `n` the resulting iterator will yield no items.
+///
+/// [`combination_masks`]: crate::combination_masks
+///
+/// # Examples
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use const_combinations::combination_bitvecs;
+///
+/// let mut masks = combination_bitvecs::<2>(3);
+/// assert_eq!(masks.next(), Some(bitvec![1, 1, 0]));
+/// assert_eq!(masks.next(), Some(bitvec![1, 0, 1]));
+/// assert_eq!(masks.next(), Some(bitvec![0, 1, 1]));
+/// assert_eq!(masks.next(), None);
+/// ```
+pub fn combination_bitvecs<const K: usize>(n: usize) -> CombinationBitVecs<K> {
+    CombinationBitVecs::new((0..n).collect(), n)
+}
+
+/// Return an iterator over the `K`-element subsets of `0..n` as [`BitArray`]
+/// masks.
+///
+/// This is the same as [`combination_bitvecs`], but the masks are stored inline
+/// in an `A`, like `[u64; 2]` for sets of up to 128 elements. The bits past `n`
+/// are never set.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than the number of bits in an `A`.
+///
+/// # Examples
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use const_combinations::combination_bitarrays;
+///
+/// let mut masks = combination_bitarrays::<u8, 2>(3);
+/// assert_eq!(masks.next(), Some(BitArray::new(0b011)));
+/// assert_eq!(masks.next(), Some(BitArray::new(0b101)));
+/// assert_eq!(masks.next(), Some(BitArray::new(0b110)));
+/// assert_eq!(masks.next(), None);
+/// ```
+pub fn combination_bitarrays<A, const K: usize>(n: usize) -> CombinationBitArrays<A, K>
+where
+    A: BitViewSized,
+{
+    CombinationBitArrays::new(n)
+}
+
+/// An extension trait adding subset enumeration to bit-slices.
+pub trait BitSliceExt {
+    /// Return an iterator over the `K`-element subsets of the bits set in the
+    /// slice, as [`BitVec`] masks.
+    ///
+    /// The masks are as long as the slice, and each has `K` of the set bits of
+    /// the slice set. They are produced in the same order as
+    /// [`SliceExt::combinations`] produces the combinations of the positions of
+    /// the set bits. If fewer than `K` bits are set the resulting iterator will
+    /// yield no items.
+    ///
+    /// [`SliceExt::combinations`]: crate::SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use const_combinations::BitSliceExt;
+    ///
+    /// let domain = bits![0, 1, 0, 1, 1];
+    /// let mut masks = domain.combination_masks::<2>();
+    /// assert_eq!(masks.next(), Some(bitvec![0, 1, 0, 1, 0]));
+    /// assert_eq!(masks.next(), Some(bitvec![0, 1, 0, 0, 1]));
+    /// assert_eq!(masks.next(), Some(bitvec![0, 0, 0, 1, 1]));
+    /// assert_eq!(masks.next(), None);
+    /// ```
+    fn combination_masks<const K: usize>(&self) -> CombinationBitVecs<K>;
+}
+
+impl<T, O> BitSliceExt for BitSlice<T, O>
+where
+    T: BitStore,
+    O: BitOrder,
+{
+    fn combination_masks<const K: usize>(&self) -> CombinationBitVecs<K> {
+        CombinationBitVecs::new(self.iter_ones().collect(), self.len())
+    }
+}
+
+/// An iterator that returns the k-element subsets of a set as [`BitVec`] masks.
+///
+/// This `struct` is created by the [`combination_bitvecs`] function and the
+/// [`combination_masks`] method on [`BitSliceExt`]. See their documentation
+/// for more.
+///
+/// [`combination_masks`]: BitSliceExt::combination_masks
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CombinationBitVecs<const K: usize> {
+    // Positions of the elements of the set within the masks
+    positions: Vec<usize>,
+    len: usize,
+    gen: LazyCombinationGenerator<K>,
+}
+
+impl<const K: usize> CombinationBitVecs<K> {
+    pub(crate) fn new(positions: Vec<usize>, len: usize) -> Self {
+        Self {
+            positions,
+            len,
+            gen: LazyCombinationGenerator::new(),
+        }
+    }
+}
+
+impl<const K: usize> Iterator for CombinationBitVecs<K> {
+    type Item = BitVec;
+
+    fn next(&mut self) -> Option<BitVec> {
+        if self.gen.is_done(self.positions.len()) {
+            None
+        } else {
+            let mut mask = BitVec::repeat(false, self.len);
+            for &i in self.gen.indices() {
+                mask.set(self.positions[i], true);
+            }
+            self.gen.step();
+            Some(mask)
+        }
+    }
+}
+
+impl<const K: usize> FusedIterator for CombinationBitVecs<K> {}
+
+/// An iterator that returns the k-element subsets of a set as [`BitArray`]
+/// masks.
+///
+/// This `struct` is created by the [`combination_bitarrays`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CombinationBitArrays<A, const K: usize> {
+    n: usize,
+    gen: LazyCombinationGenerator<K>,
+    mask: PhantomData<A>,
+}
+
+impl<A, const K: usize> CombinationBitArrays<A, K>
+where
+    A: BitViewSized,
+{
+    pub(crate) fn new(n: usize) -> Self {
+        let bits = BitArray::<A, Lsb0>::ZERO.len();
+        assert!(
+            n <= bits,
+            "cannot take subsets of {} elements with {}-bit masks",
+            n,
+            bits,
+        );
+        Self {
+            n,
+            gen: LazyCombinationGenerator::new(),
+            mask: PhantomData,
+        }
+    }
+}
+
+impl<A, const K: usize> Iterator for CombinationBitArrays<A, K>
+where
+    A: BitViewSized,
+{
+    type Item = BitArray<A>;
+
+    fn next(&mut self) -> Option<BitArray<A>> {
+        if self.gen.is_done(self.n) {
+            None
+        } else {
+            let mut mask = BitArray::ZERO;
+            for &i in self.gen.indices() {
+                mask.set(i, true);
+            }
+            self.gen.step();
+            Some(mask)
+        }
+    }
+}
+
+impl<A, const K: usize> FusedIterator for CombinationBitArrays<A, K> where A: BitViewSized {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{bit_combinations, combination_masks, SliceExt};
+    use ::bitvec::prelude::*;
+
+    #[test]
+    fn matches_bool_masks() {
+        let masks = combination_bitvecs::<3>(6).map(|mask| {
+            let mut arr = [false; 6];
+            for i in mask.iter_ones() {
+                arr[i] = true;
+            }
+            arr
+        });
+        assert!(masks.eq(combination_masks::<6, 3>()));
+        assert_eq!(combination_bitvecs::<4>(3).next(), None);
+        let mut empty = combination_bitvecs::<0>(2);
+        assert_eq!(empty.next(), Some(bitvec![0, 0]));
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn matches_bit_combinations() {
+        let masks = combination_bitarrays::<u64, 4>(9).map(|mask| mask.into_inner());
+        assert!(masks.eq(bit_combinations::<4>(9)));
+        let mut wide = combination_bitarrays::<[u64; 2], 1>(100);
+        assert_eq!(
+            wide.nth(99).map(|mask| mask.into_inner()),
+            Some([0, 1 << 35])
+        );
+    }
+
+    #[test]
+    fn domain() {
+        let domain = bitvec![1, 0, 1, 1, 0, 0, 1];
+        let ones = [0, 2, 3, 6];
+        let masks = domain
+            .combination_masks::<2>()
+            .map(|mask| mask.iter_ones().collect::<Vec<_>>());
+        assert!(masks.eq(ones.combinations::<2>().map(|c| c.map(|&i| i).to_vec())));
+        assert_eq!(domain.combination_masks::<5>().next(), None);
+        let domain = bits![u8, Msb0; 1, 1];
+        assert_eq!(domain.combination_masks::<2>().next(), Some(bitvec![1, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot take subsets of 9 elements with 8-bit masks")]
+    fn panics_on_n_too_big() {
+        let _ = combination_bitarrays::<u8, 1>(9);
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::boxed::Box;
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+
+mod sealed {
+    use alloc::boxed::Box;
+
+    /// Iterators which can write their next item straight into a heap allocation.
+    pub trait BoxedSource: Iterator {
+        fn next_boxed(&mut self) -> Option<Box<Self::Item>>;
+    }
+}
+
+pub(crate) use sealed::BoxedSource;
+
+/// An iterator that returns the arrays of another iterator in boxes.
+///
+/// This `struct` is created by the `boxed` method on the slice iterators, like
+/// [`SliceCombinations::boxed`]. See its documentation for more.
+///
+/// [`SliceCombinations::boxed`]: crate::SliceCombinations::boxed
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Boxed<I> {
+    iter: I,
+}
+
+impl<I> Boxed<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<I> Iterator for Boxed<I>
+where
+    I: BoxedSource,
+{
+    type Item = Box<I::Item>;
+
+    fn next(&mut self) -> Option<Box<I::Item>> {
+        self.iter.next_boxed()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Boxed<I> where I: BoxedSource + ExactSizeIterator {}
+
+impl<I> FusedIterator for Boxed<I> where I: BoxedSource + FusedIterator {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+
+    #[test]
+    fn matches_unboxed() {
+        let items = [3, 1, 4, 1, 5];
+        assert!(items
+            .combinations::<3>()
+            .boxed()
+            .map(|b| *b)
+            .eq(items.combinations::<3>()));
+        assert!(items
+            .combinations_copied::<2>()
+            .boxed()
+            .map(|b| *b)
+            .eq(items.combinations_copied::<2>()));
+        assert!(items
+            .permutations::<3>()
+            .boxed()
+            .map(|b| *b)
+            .eq(items.permutations::<3>()));
+        assert!(items
+            .permutations_copied::<2>()
+            .boxed()
+            .map(|b| *b)
+            .eq(items.permutations_copied::<2>()));
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut permutations = [1, 2, 3].permutations::<2>().boxed();
+        assert_eq!(permutations.size_hint(), (6, Some(6)));
+        permutations.next();
+        assert_eq!(permutations.size_hint(), (5, Some(5)));
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].combinations::<0>().boxed();
+        assert_eq!(combinations.next().as_deref(), Some(&[]));
+        assert_eq!(combinations.next(), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! The buffer the iterator adaptors keep the elements they have read in.
+//!
+//! With the `allocator_api` feature this is a `Vec` in any [`Allocator`].
+//! Without it the adaptors still take an allocator parameter, but the only
+//! allocator is the global one, so that the same code works in both cases.
+
+#[cfg(feature = "allocator_api")]
+pub use alloc::alloc::{Allocator, Global};
+
+#[cfg(feature = "allocator_api")]
+pub(crate) type Buffer<T, A> = alloc::vec::Vec<T, A>;
+
+#[cfg(feature = "allocator_api")]
+pub(crate) fn buffer_in<T, A: Allocator>(alloc: A) -> Buffer<T, A> {
+    Buffer::new_in(alloc)
+}
+
+#[cfg(feature = "allocator_api")]
+pub(crate) fn into_vec<T>(buffer: Buffer<T, Global>) -> alloc::vec::Vec<T> {
+    buffer
+}
+
+#[cfg(feature = "allocator_api")]
+pub(crate) fn into_arc<T, A: Allocator>(buffer: Buffer<T, A>) -> alloc::sync::Arc<[T]> {
+    buffer.into_iter().collect()
+}
+
+#[cfg(not(feature = "allocator_api"))]
+pub use fallback::{Allocator, Buffer, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) fn buffer_in<T, A: Allocator>(_: A) -> Buffer<T, A> {
+    Buffer(alloc::vec::Vec::new(), core::marker::PhantomData)
+}
+
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) fn into_vec<T>(buffer: Buffer<T, Global>) -> alloc::vec::Vec<T> {
+    buffer.0
+}
+
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) fn into_arc<T, A: Allocator>(buffer: Buffer<T, A>) -> alloc::sync::Arc<[T]> {
+    buffer.0.into()
+}
+
+/// Read up to `count` more elements from `iter` into `items`.
+///
+/// Room is only made for the elements the current pass needs, capped by how
+/// many the iterator can still have, since the caller may stop before reading
+/// the rest. The buffer still grows by doubling, and `reserve_items` is there
+/// for callers who know they'll read more.
+pub(crate) fn fill<I, A>(items: &mut Buffer<I::Item, A>, iter: &mut I, count: usize)
+where
+    I: Iterator,
+    A: Allocator,
+{
+    let (_, upper) = iter.size_hint();
+    items.reserve(upper.map_or(count, |upper| upper.min(count)));
+    items.extend(iter.by_ref().take(count));
+}
+
+#[cfg(not(feature = "allocator_api"))]
+mod fallback {
+    use alloc::vec::Vec;
+    use core::marker::PhantomData;
+    use core::ops::{Deref, DerefMut};
+
+    /// Stand-in for the unstable allocator trait.
+    pub trait Allocator {}
+
+    /// Stand-in for the unstable global allocator handle.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Global;
+
+    impl Allocator for Global {}
+
+    #[derive(Clone)]
+    pub struct Buffer<T, A>(pub(crate) Vec<T>, pub(crate) PhantomData<A>);
+
+    impl<T, A> Deref for Buffer<T, A> {
+        type Target = Vec<T>;
+
+        fn deref(&self) -> &Vec<T> {
+            &self.0
+        }
+    }
+
+    impl<T, A> DerefMut for Buffer<T, A> {
+        fn deref_mut(&mut self) -> &mut Vec<T> {
+            &mut self.0
+        }
+    }
+}
+
+#[cfg(all(test, feature = "allocator_api"))]
+mod test {
+    use super::{Allocator, Global};
+    use crate::IterExt;
+    use alloc::vec::Vec;
+    use core::alloc::{AllocError, Layout};
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    #[derive(Clone, Copy)]
+    struct Counting<'a>(&'a Cell<usize>);
+
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn custom_allocator() {
+        let count = Cell::new(0);
+        let all: Vec<_> = (0..6).combinations_in::<3, _>(Counting(&count)).collect();
+        assert!(all.into_iter().eq((0..6).combinations::<3>()));
+        assert!(count.get() > 0);
+        count.set(0);
+        let all: Vec<_> = (0..4).permutations_in::<2, _>(Counting(&count)).collect();
+        assert!(all.into_iter().eq((0..4).permutations::<2>()));
+        assert!(count.get() > 0);
+    }
+}
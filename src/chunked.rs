@@ -0,0 +1,106 @@
+use crate::try_make_array;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// An iterator that yields `M`-sized batches of items from `iter`.
+///
+/// This `struct` is created by the [`chunked`] method on [`IterExt`]. See its
+/// documentation for more.
+///
+/// [`chunked`]: super::IterExt::chunked
+/// [`IterExt`]: super::IterExt
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Chunked<I, const M: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    remainder: Option<Vec<I::Item>>,
+}
+
+impl<I, const M: usize> Chunked<I, M>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            remainder: None,
+        }
+    }
+
+    /// Returns the items that were left over once the inner iterator ran out
+    /// before a full batch could be produced.
+    ///
+    /// Returns `None` if the inner iterator has not run out yet, or if it ran out
+    /// exactly on a batch boundary.
+    pub fn into_remainder(self) -> Option<Vec<I::Item>> {
+        self.remainder
+    }
+}
+
+impl<I, const M: usize> Iterator for Chunked<I, M>
+where
+    I: Iterator,
+{
+    type Item = [I::Item; M];
+
+    fn next(&mut self) -> Option<[I::Item; M]> {
+        let iter = &mut self.iter;
+        match try_make_array(|_| iter.next()) {
+            Ok(chunk) => Some(chunk),
+            Err(partial) => {
+                if !partial.is_empty() {
+                    self.remainder = Some(partial);
+                }
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        match M {
+            0 => (usize::MAX, None),
+            _ => (lower / M, upper.map(|upper| upper / M)),
+        }
+    }
+}
+
+impl<I, const M: usize> FusedIterator for Chunked<I, M> where I: FusedIterator {}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+    use alloc::vec;
+
+    #[test]
+    fn batches() {
+        let mut chunks = (0..6).chunked::<2>();
+        assert_eq!(chunks.next(), Some([0, 1]));
+        assert_eq!(chunks.next(), Some([2, 3]));
+        assert_eq!(chunks.next(), Some([4, 5]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.into_remainder(), None);
+    }
+
+    #[test]
+    fn remainder() {
+        let mut chunks = (0..5).chunked::<2>();
+        assert_eq!(chunks.next(), Some([0, 1]));
+        assert_eq!(chunks.next(), Some([2, 3]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.into_remainder(), Some(vec![4]));
+    }
+
+    #[test]
+    fn combination_batches() {
+        let mut chunks = [1, 2, 3, 4].iter().combinations::<2>().chunked::<2>();
+        assert_eq!(chunks.next(), Some([[&1, &2], [&1, &3]]));
+        assert_eq!(chunks.next(), Some([[&2, &3], [&1, &4]]));
+        assert_eq!(chunks.next(), Some([[&2, &4], [&3, &4]]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.into_remainder(), None);
+    }
+}
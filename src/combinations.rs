@@ -1,6 +1,28 @@
-use crate::make_array;
+#[cfg(feature = "alloc")]
+use crate::boxed::{Boxed, BoxedSource};
+#[cfg(feature = "alloc")]
+use crate::buffer::{buffer_in, fill, into_arc, into_vec, Allocator, Buffer, Global};
+use crate::indexed::{IndexedSource, WithIndices};
+#[cfg(feature = "alloc")]
+use crate::make_boxed_array;
+#[cfg(feature = "alloc")]
+use crate::streaming::StreamingCombinations;
+use crate::{
+    counting::{binomial, binomial_u128, size_hint},
+    get_item, make_array,
+};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use core::iter::{FusedIterator, Iterator};
+use core::convert::Infallible;
+#[cfg(feature = "alloc")]
+use core::iter::Map;
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator, Step, StepBy, Take};
+use core::num::NonZero;
+use core::ops::{Range, Try};
 
 #[derive(Clone)]
 pub struct LazyCombinationGenerator<const K: usize> {
@@ -28,39 +50,324 @@ impl<const K: usize> LazyCombinationGenerator<K> {
         &self.indices
     }
 
+    /// Return the position of the current combination in the iteration order,
+    /// which is the colexicographic order of the indices.
+    pub fn rank(&self) -> Option<usize> {
+        self.indices
+            .iter()
+            .enumerate()
+            .try_fold(0usize, |rank, (i, &index)| {
+                rank.checked_add(binomial(index, i + 1)?)
+            })
+    }
+
+    /// Return the number of combinations left, including the current one.
+    pub fn remaining(&self, item_count: usize) -> Option<usize> {
+        if self.is_done(item_count) {
+            Some(0)
+        } else {
+            Some(binomial(item_count, K)? - self.rank()?)
+        }
+    }
+
+    /// Return the number of combinations left as a `u128`, including the
+    /// current one.
+    pub fn remaining_u128(&self, item_count: usize) -> Option<u128> {
+        if self.is_done(item_count) {
+            return Some(0);
+        }
+        // The rank is below the total, so it fits whenever the total does
+        let total = binomial_u128(item_count, K)?;
+        let rank = self
+            .indices
+            .iter()
+            .enumerate()
+            .try_fold(0u128, |rank, (i, &index)| {
+                rank.checked_add(binomial_u128(index, i + 1)?)
+            })?;
+        Some(total - rank)
+    }
+
+    /// Jump to the combination at position `rank`, which has to be less than the
+    /// number of combinations of `item_count` items.
+    pub fn seek(&mut self, mut rank: usize, item_count: usize) {
+        for i in (0..K).rev() {
+            // Find the largest index whose binomial doesn't exceed the rank
+            let (mut lo, mut hi) = (i, item_count);
+            while lo + 1 < hi {
+                let mid = lo + (hi - lo) / 2;
+                match binomial(mid, i + 1) {
+                    Some(b) if b <= rank => lo = mid,
+                    _ => hi = mid,
+                }
+            }
+            self.indices[i] = lo;
+            rank -= binomial(lo, i + 1).unwrap();
+        }
+    }
+
+    pub fn advance_by(&mut self, item_count: usize, n: usize) -> Result<(), NonZero<usize>> {
+        match self.remaining(item_count) {
+            Some(remaining) if n < remaining => {
+                if n > 0 {
+                    let rank = self.rank().unwrap();
+                    self.seek(rank + n, item_count);
+                }
+                Ok(())
+            }
+            Some(remaining) => {
+                self.finish();
+                NonZero::new(n - remaining).map_or(Ok(()), Err)
+            }
+            None => {
+                // There are more combinations than a `usize` can count, so any
+                // rank which does fit can still be jumped to directly
+                if let Some(rank) = self.rank().and_then(|rank| rank.checked_add(n)) {
+                    self.seek(rank, item_count);
+                    return Ok(());
+                }
+                // Otherwise take the slow path
+                for i in 0..n {
+                    if self.is_done(item_count) {
+                        return Err(NonZero::new(n - i).unwrap());
+                    }
+                    self.step();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.done = true;
+    }
+
+    pub fn raise_first(&mut self) {
+        // Move the leading run of consecutive indices up by one, which gives the
+        // next combination (in order) that doesn't include the first item
+        let run = (0..K).find(|&i| self.indices[i] != i).unwrap_or(K);
+        for i in 0..run {
+            self.indices[i] = i + 1;
+        }
+    }
+
     pub fn step(&mut self) {
         if K == 0 {
             self.done = true;
         } else {
-            let mut i = 0;
-            // Reset consecutive indices
-            while i + 1 < K && self.indices[i] + 1 == self.indices[i + 1] {
-                self.indices[i] = i;
-                i += 1;
+            step_indices(&mut self.indices);
+        }
+    }
+}
+
+const fn step_indices<const K: usize>(indices: &mut [usize; K]) {
+    let mut i = 0;
+    // Reset consecutive indices
+    while i + 1 < K && indices[i] + 1 == indices[i + 1] {
+        indices[i] = i;
+        i += 1;
+    }
+    // Increment the last consecutive index
+    indices[i] += 1;
+}
+
+/// Return the combination of `n` items which comes after `indices`, or `None`
+/// if `indices` is the last one.
+///
+/// This is the step the `combinations` iterators take between items, so
+/// starting from `[0, 1, ..., K - 1]` it walks through the same index arrays in
+/// the same order. As a `const fn` it can also be used to walk the combinations
+/// at compile time. If `indices` isn't a strictly increasing array of indices
+/// below `n` the result is meaningless.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::next_combination;
+///
+/// const fn last_pair(n: usize) -> [usize; 2] {
+///     let mut indices = [0, 1];
+///     while let Some(next) = next_combination(indices, n) {
+///         indices = next;
+///     }
+///     indices
+/// }
+///
+/// const LAST: [usize; 2] = last_pair(4);
+/// assert_eq!(LAST, [2, 3]);
+/// assert_eq!(next_combination([0, 2], 4), Some([1, 2]));
+/// assert_eq!(next_combination([1, 2], 3), None);
+/// ```
+#[doc(alias = "successor")]
+pub const fn next_combination<const K: usize>(
+    mut indices: [usize; K],
+    n: usize,
+) -> Option<[usize; K]> {
+    if K == 0 {
+        return None;
+    }
+    step_indices(&mut indices);
+    if indices[K - 1] < n {
+        Some(indices)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Generates combinations of a runtime size in lexicographic order.
+#[derive(Clone)]
+pub struct LazyLexCombinationGenerator {
+    indices: Vec<usize>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl LazyLexCombinationGenerator {
+    pub fn new(k: usize) -> Self {
+        Self {
+            indices: (0..k).collect(),
+            done: false,
+        }
+    }
+
+    pub fn is_done(&self, item_count: usize) -> bool {
+        self.done || self.indices.len() > item_count
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn step(&mut self, item_count: usize) {
+        let k = self.indices.len();
+        // Find the last index which hasn't reached its final position yet
+        let mut i = k;
+        while i > 0 {
+            i -= 1;
+            if self.indices[i] < item_count - k + i {
+                // Increment it and pack the indices after it right behind it
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+                return;
             }
-            // Increment the last consecutive index
-            self.indices[i] += 1;
         }
+        self.done = true;
     }
 }
 
+/// Write the `K`-combinations of the indices `0..N` into `out`, returning how
+/// many were written.
+///
+/// The combinations are written in the same order as the `combinations`
+/// iterators produce them, until either all of them are written or `out` is
+/// full, so the return value is the smaller of `out.len()` and the number of
+/// combinations. As a `const fn` this lets const code fill storage of its own,
+/// where [`combinations_table!`] would need the whole table to be its own
+/// constant.
+///
+/// [`combinations_table!`]: crate::combinations_table
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::write_combinations;
+///
+/// const TRIPLES: ([[usize; 3]; 8], usize) = {
+///     let mut out = [[0; 3]; 8];
+///     let written = write_combinations::<5, 3>(&mut out);
+///     (out, written)
+/// };
+/// assert_eq!(TRIPLES.1, 8);
+/// assert_eq!(TRIPLES.0[..3], [[0, 1, 2], [0, 1, 3], [0, 2, 3]]);
+///
+/// let mut out = [[0; 2]; 10];
+/// assert_eq!(write_combinations::<4, 2>(&mut out), 6);
+/// ```
+pub const fn write_combinations<const N: usize, const K: usize>(out: &mut [[usize; K]]) -> usize {
+    let mut written = 0;
+    crate::const_for_combinations!(N, K, |indices| {
+        if written == out.len() {
+            break;
+        }
+        out[written] = indices;
+        written += 1;
+    });
+    written
+}
+
+/// A saved position of an iterator over combinations.
+///
+/// This `struct` is created by the `save` method on the combinations iterators,
+/// like [`Combinations::save`], and is handed back to their `restore` method to
+/// return to that position. It only holds the indices of the next
+/// combination, so it's cheap to keep many of them around.
+#[derive(Clone)]
+pub struct CombinationsSnapshot<const K: usize> {
+    state: State<K>,
+}
+
 #[derive(Clone)]
-struct State<const K: usize> {
+pub(crate) struct State<const K: usize> {
     gen: LazyCombinationGenerator<K>,
 }
 
 impl<const K: usize> State<K> {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             gen: LazyCombinationGenerator::new(),
         }
     }
 
-    fn max_index(&self) -> Option<usize> {
+    #[cfg(any(feature = "alloc", feature = "heapless"))]
+    pub(crate) fn max_index(&self) -> Option<usize> {
         self.gen.max_index()
     }
 
-    fn get_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<[O; K]>
+    pub(crate) fn remaining(&self, item_count: usize) -> Option<usize> {
+        self.gen.remaining(item_count)
+    }
+
+    pub(crate) fn is_done(&self, item_count: usize) -> bool {
+        self.gen.is_done(item_count)
+    }
+
+    pub(crate) fn remaining_u128(&self, item_count: usize) -> Option<u128> {
+        self.gen.remaining_u128(item_count)
+    }
+
+    pub(crate) fn advance_by(&mut self, item_count: usize, n: usize) -> Result<(), NonZero<usize>> {
+        self.gen.advance_by(item_count, n)
+    }
+
+    fn peek<'a, T, O, F>(&self, items: &'a [T], f: F) -> Option<[O; K]>
+    where
+        F: Fn(&'a T) -> O,
+        O: 'a,
+    {
+        if self.gen.is_done(items.len()) {
+            None
+        } else {
+            let indices = self.gen.indices();
+            // SAFETY: the generator isn't done, so every index is in bounds
+            Some(make_array(|i| f(unsafe { get_item(items, indices[i]) })))
+        }
+    }
+
+    pub(crate) fn get_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<[O; K]>
+    where
+        F: Fn(&'a T) -> O,
+        O: 'a,
+    {
+        let res = self.peek(items, f)?;
+        self.gen.step();
+        Some(res)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn get_boxed_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<Box<[O; K]>>
     where
         F: Fn(&'a T) -> O,
         O: 'a,
@@ -69,13 +376,68 @@ impl<const K: usize> State<K> {
             None
         } else {
             let indices = self.gen.indices();
-            let res = make_array(|i| f(&items[indices[i]]));
+            // SAFETY: the generator isn't done, so every index is in bounds
+            let res = make_boxed_array(|i| f(unsafe { get_item(items, indices[i]) }));
+            self.gen.step();
+            Some(res)
+        }
+    }
+
+    /// Feed every remaining combination to `g`, stepping the generator in a
+    /// plain loop instead of going through an `Option` per combination.
+    fn try_fold<'a, T, B, G, R>(&mut self, items: &'a [T], init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [&'a T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        while !self.gen.is_done(items.len()) {
+            let indices = self.gen.indices();
+            // SAFETY: the generator isn't done, so every index is in bounds
+            let res = make_array(|i| unsafe { get_item(items, indices[i]) });
+            self.gen.step();
+            acc = g(acc, res)?;
+        }
+        R::from_output(acc)
+    }
+
+    fn fold<'a, T, B, G>(&mut self, items: &'a [T], init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [&'a T; K]) -> B,
+    {
+        match self.try_fold(items, init, |acc, res| Ok::<_, Infallible>(g(acc, res))) {
+            Ok(acc) => acc,
+            Err(never) => match never {},
+        }
+    }
+
+    pub(crate) fn get_indices_and_step(&mut self, item_count: usize) -> Option<[usize; K]> {
+        if self.gen.is_done(item_count) {
+            None
+        } else {
+            let res = *self.gen.indices();
+            self.gen.step();
+            Some(res)
+        }
+    }
+
+    fn get_copied_and_step<T>(&mut self, items: &[T]) -> Option<[T; K]>
+    where
+        T: Copy,
+    {
+        if self.gen.is_done(items.len()) {
+            None
+        } else {
+            // Index straight into the slice instead of going through a closure
+            // SAFETY: the generator isn't done, so every index is in bounds
+            let res = self.gen.indices().map(|i| unsafe { *get_item(items, i) });
             self.gen.step();
             Some(res)
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 /// An iterator that returns k-length combinations of values from `iter`.
 ///
 /// This `struct` is created by the [`combinations`] method on [`IterExt`]. See its
@@ -85,30 +447,257 @@ impl<const K: usize> State<K> {
 /// [`IterExt`]: super::IterExt
 #[derive(Clone)]
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct Combinations<I, const K: usize>
+pub struct Combinations<I, const K: usize, A = Global>
 where
     I: Iterator,
+    A: Allocator,
 {
     iter: I,
-    items: Vec<I::Item>,
+    items: Buffer<I::Item, A>,
     state: State<K>,
 }
 
+#[cfg(feature = "alloc")]
 impl<I, const K: usize> Combinations<I, K>
 where
     I: Iterator,
 {
     pub(crate) fn new(iter: I) -> Self {
+        Self::new_in(iter, Global)
+    }
+
+    pub(crate) fn with_capacity(iter: I, capacity: usize) -> Self {
+        Self::with_capacity_in(iter, capacity, Global)
+    }
+
+    /// Convert this into a lending iterator which reuses a single array for all
+    /// the combinations.
+    ///
+    /// Only the elements which differ from the previous combination are cloned
+    /// into the array, which saves most of the clones for consumers that just
+    /// read each combination. The conversion picks up wherever this iterator
+    /// left off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{IterExt, LendingIterator};
+    ///
+    /// let words = vec!["apple", "banana", "cherry"].into_iter().map(String::from);
+    /// let mut combinations = words.combinations::<2>().streaming();
+    /// assert_eq!(combinations.next(), Some(&["apple", "banana"].map(String::from)));
+    /// assert_eq!(combinations.next(), Some(&["apple", "cherry"].map(String::from)));
+    /// assert_eq!(combinations.next(), Some(&["banana", "cherry"].map(String::from)));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    pub fn streaming(self) -> StreamingCombinations<I, K>
+    where
+        I::Item: Clone,
+    {
+        StreamingCombinations::new(self.iter, into_vec(self.items), self.state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> Combinations<I, K, A>
+where
+    I: Iterator,
+    A: Allocator,
+{
+    pub(crate) fn new_in(iter: I, alloc: A) -> Self {
+        let mut this = Self::with_capacity_in(iter, 0, alloc);
+        if let (lower, Some(_)) = this.iter.size_hint() {
+            if K > 0 {
+                // The hint may be far larger than what fits in memory, in
+                // which case the buffer just grows as usual
+                let _ = this.items.try_reserve(lower);
+            }
+        }
+        this
+    }
+
+    pub(crate) fn with_capacity_in(iter: I, capacity: usize, alloc: A) -> Self {
+        let mut items = buffer_in(alloc);
+        items.reserve(capacity);
         Self {
             iter,
-            items: Vec::new(),
+            items,
             state: State::new(),
         }
     }
+
+    /// Return the elements read from the input iterator so far.
+    ///
+    /// Elements are only read once they are needed, so this is a prefix of the
+    /// input which contains every element of the combinations returned so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (1..10).combinations::<2>();
+    /// assert_eq!(combinations.items(), &[]);
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// assert_eq!(combinations.items(), &[1, 2]);
+    /// combinations.next();
+    /// assert_eq!(combinations.items(), &[1, 2, 3]);
+    /// ```
+    pub fn items(&self) -> &[I::Item] {
+        &self.items
+    }
+
+    /// Reserve room in the buffer for at least `additional` more elements of
+    /// the input.
+    ///
+    /// The buffer only grows as far as the combinations so far need, since
+    /// enumerating may stop early. Callers who know they'll read more of the
+    /// input can make room up front instead of having the buffer grow while
+    /// enumerating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..1000).filter(|n| n % 7 == 0).combinations::<2>();
+    /// combinations.reserve_items(143);
+    /// assert_eq!(combinations.count(), 10153);
+    /// ```
+    pub fn reserve_items(&mut self, additional: usize) {
+        self.items.reserve(additional);
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// This needs the input iterator to know its exact length. Unlike
+    /// [`len`](ExactSizeIterator::len), it doesn't panic when the count is too
+    /// large for a `usize`. See [`SliceCombinations::remaining_len`] for
+    /// more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..100).combinations::<10>();
+    /// assert_eq!(combinations.remaining_len(), Some(17_310_309_456_440));
+    /// combinations.next();
+    /// assert_eq!(combinations.remaining_len(), Some(17_310_309_456_439));
+    /// ```
+    pub fn remaining_len(&self) -> Option<u128>
+    where
+        I: ExactSizeIterator,
+    {
+        let item_count = self.items.len().checked_add(self.iter.len())?;
+        self.state.remaining_u128(item_count)
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// This reads only as many elements from the input as the next combination
+    /// needs, and keeps them for it, so nothing is lost by asking. Since it may
+    /// have to read from the input it takes `&mut self`, which is why it isn't
+    /// called `is_empty` like on the slice iterators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..3).combinations::<2>();
+    /// assert!(!combinations.is_exhausted());
+    /// assert_eq!(combinations.items(), &[0, 1]);
+    /// assert_eq!(combinations.by_ref().count(), 3);
+    /// assert!(combinations.is_exhausted());
+    /// assert!((0..1).combinations::<2>().is_exhausted());
+    /// ```
+    pub fn is_exhausted(&mut self) -> bool {
+        if let Some(max_index) = self.state.max_index() {
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            fill(&mut self.items, &mut self.iter, missing_count);
+        }
+        self.state.is_done(self.items.len())
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// [`restore`].
+    ///
+    /// Only the position is saved, not the elements read so far, which makes
+    /// this much cheaper than cloning the iterator. Search algorithms can use
+    /// this to explore the combinations ahead and then roll back.
+    ///
+    /// [`restore`]: Combinations::restore
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..5).combinations::<2>();
+    /// combinations.next();
+    /// let snapshot = combinations.save();
+    /// assert_eq!(combinations.nth(3), Some([1, 3]));
+    /// combinations.restore(snapshot);
+    /// assert_eq!(combinations.next(), Some([0, 2]));
+    /// ```
+    pub fn save(&self) -> CombinationsSnapshot<K> {
+        CombinationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with [`save`].
+    ///
+    /// Restoring a snapshot of another iterator continues from the same
+    /// position in the order of the combinations, reading more of the input if
+    /// it's needed.
+    ///
+    /// [`save`]: Combinations::save
+    pub fn restore(&mut self, snapshot: CombinationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Buffer the rest of the input and return an iterator over the remaining
+    /// combinations which shares the buffer between its clones.
+    ///
+    /// Cloning this iterator clones the whole buffer. The returned iterator
+    /// keeps the buffer behind an [`Arc`] instead, so it can be cloned into any
+    /// number of cursors over the same elements without copying them. Note that
+    /// this reads the input iterator to the end, even if it is infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..4).combinations::<2>();
+    /// assert_eq!(combinations.next(), Some([0, 1]));
+    /// let forked = combinations.fork();
+    /// let cursors = vec![forked.clone(), forked.clone(), forked];
+    /// for cursor in cursors {
+    ///     assert_eq!(cursor.count(), 5);
+    /// }
+    /// ```
+    pub fn fork(mut self) -> ForkedCombinations<I::Item, K> {
+        if K > 0 {
+            self.items.extend(self.iter);
+        }
+        ForkedCombinations {
+            items: into_arc(self.items),
+            state: self.state,
+        }
+    }
 }
 
-impl<I, const K: usize> Iterator for Combinations<I, K>
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> Iterator for Combinations<I, K, A>
 where
+    A: Allocator,
     I: Iterator,
     I::Item: Clone,
 {
@@ -120,52 +709,1241 @@ where
             let missing_count = (max_index + 1).saturating_sub(self.items.len());
             if missing_count > 0 {
                 // Try to fill the buffer
-                self.items.extend(self.iter.by_ref().take(missing_count));
+                fill(&mut self.items, &mut self.iter, missing_count);
             }
         }
         self.state.get_and_step(&self.items, |t| t.clone())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = self.items.len();
+        let remaining = |len: usize| self.state.remaining(buffered.checked_add(len)?);
+        let (lower, _) = size_hint(remaining(lower));
+        (lower, upper.and_then(remaining))
+    }
+
+    fn fold<B, G>(mut self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [I::Item; K]) -> B,
+    {
+        match self.try_fold(init, |acc, res| Ok::<_, Infallible>(g(acc, res))) {
+            Ok(acc) => acc,
+            Err(never) => match never {},
+        }
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [I::Item; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        loop {
+            if K > 0 {
+                // Read only the element the next combination needs, like `next`
+                // does, so an infinite input is never buffered to the end
+                let max_index = self.state.max_index().unwrap();
+                let missing_count = (max_index + 1).saturating_sub(self.items.len());
+                fill(&mut self.items, &mut self.iter, missing_count);
+            }
+            if self.state.is_done(self.items.len()) {
+                return R::from_output(acc);
+            }
+            // Every combination of the buffered elements comes before any
+            // which needs another one
+            acc = self
+                .state
+                .try_fold(&self.items, acc, |acc, res| g(acc, res.map(Clone::clone)))?;
+        }
+    }
+}
+
+// The count is exact whenever it fits in a `usize`, and `remaining_len` covers
+// the inputs where it doesn't
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> ExactSizeIterator for Combinations<I, K, A>
+where
+    A: Allocator,
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
 }
 
-impl<I, const K: usize> FusedIterator for Combinations<I, K>
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> FusedIterator for Combinations<I, K, A>
 where
+    A: Allocator,
     I: FusedIterator,
     I::Item: Clone,
 {
 }
 
-/// An iterator that returns k-length combinations of values from `slice`.
+#[cfg(feature = "alloc")]
+/// An iterator that returns k-length combinations of values from a shared buffer.
+///
+/// This `struct` is created by the [`fork`] method on [`Combinations`]. See its
+/// documentation for more.
+///
+/// [`fork`]: Combinations::fork
 #[derive(Clone)]
-#[must_use = "iterator does nothing unless consumed"]
-pub struct SliceCombinations<'a, T, const K: usize> {
-    items: &'a [T],
+#[must_use = "iterators do nothing unless consumed"]
+pub struct ForkedCombinations<T, const K: usize> {
+    items: Arc<[T]>,
     state: State<K>,
 }
 
-impl<'a, T, const K: usize> SliceCombinations<'a, T, K> {
-    pub(crate) fn new(items: &'a [T]) -> Self {
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> ForkedCombinations<T, K> {
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Combinations::save`] for more.
+    pub fn save(&self) -> CombinationsSnapshot<K> {
+        CombinationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Combinations::restore`] for more.
+    pub fn restore(&mut self, snapshot: CombinationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> Iterator for ForkedCombinations<T, K>
+where
+    T: Clone,
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> FusedIterator for ForkedCombinations<T, K> where T: Clone {}
+
+#[cfg(feature = "alloc")]
+/// An iterator that returns k-length combinations of values from `iter`, wrapped
+/// in shared pointers.
+///
+/// This `struct` is created by the [`combinations_shared`] method on [`IterExt`].
+/// See its documentation for more.
+///
+/// [`combinations_shared`]: super::IterExt::combinations_shared
+/// [`IterExt`]: super::IterExt
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct SharedCombinations<I, P, const K: usize>
+where
+    I: Iterator,
+{
+    inner: Combinations<IntoShared<I, P>, K>,
+}
+
+#[cfg(feature = "alloc")]
+type IntoShared<I, P> = Map<I, fn(<I as Iterator>::Item) -> P>;
+
+#[cfg(feature = "alloc")]
+impl<I, P, const K: usize> SharedCombinations<I, P, K>
+where
+    I: Iterator,
+    P: From<I::Item>,
+{
+    pub(crate) fn new(iter: I) -> Self {
         Self {
-            items,
-            state: State::new(),
+            inner: Combinations::new(iter.map(P::from as fn(I::Item) -> P)),
         }
     }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`Combinations::is_exhausted`] for more.
+    pub fn is_exhausted(&mut self) -> bool {
+        self.inner.is_exhausted()
+    }
 }
 
-impl<'a, T, const K: usize> Iterator for SliceCombinations<'a, T, K> {
-    type Item = [&'a T; K];
+#[cfg(feature = "alloc")]
+impl<I, P, const K: usize> Iterator for SharedCombinations<I, P, K>
+where
+    I: Iterator,
+    P: Clone,
+{
+    type Item = [P; K];
 
-    fn next(&mut self) -> Option<[&'a T; K]> {
-        self.state.get_and_step(self.items, |t| t)
+    fn next(&mut self) -> Option<[P; K]> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
-impl<T, const K: usize> FusedIterator for SliceCombinations<'_, T, K> {}
+#[cfg(feature = "alloc")]
+impl<I, P, const K: usize> ExactSizeIterator for SharedCombinations<I, P, K>
+where
+    I: ExactSizeIterator,
+    P: Clone,
+{
+}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::IterExt;
-    use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "alloc")]
+impl<I, P, const K: usize> FusedIterator for SharedCombinations<I, P, K>
+where
+    I: FusedIterator,
+    P: Clone,
+{
+}
+
+/// An iterator that returns k-length combinations of values from `slice`.
+#[derive(Clone)]
+#[must_use = "iterator does nothing unless consumed"]
+pub struct SliceCombinations<'a, T, const K: usize> {
+    pub(crate) items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SliceCombinations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Combinations::save`] for more.
+    pub fn save(&self) -> CombinationsSnapshot<K> {
+        CombinationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Combinations::restore`] for more.
+    pub fn restore(&mut self, snapshot: CombinationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the next combination without advancing the iterator.
+    ///
+    /// This is computed from the current state, so unlike wrapping the
+    /// iterator in a [`Peekable`] no combination is stored.
+    ///
+    /// [`Peekable`]: core::iter::Peekable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = [1, 2, 3];
+    /// let mut combinations = items.combinations::<2>();
+    /// assert_eq!(combinations.peek(), Some([&1, &2]));
+    /// assert_eq!(combinations.next(), Some([&1, &2]));
+    /// assert_eq!(combinations.peek(), Some([&1, &3]));
+    /// ```
+    pub fn peek(&self) -> Option<[&'a T; K]> {
+        self.state.peek(self.items, |t| t)
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// The number of combinations grows quickly enough that a `usize` can't
+    /// hold it for even moderately sized inputs, especially where it is 32
+    /// bits, which is why this iterator doesn't implement
+    /// [`ExactSizeIterator`]. This counts up to `u128::MAX`, and returns `None`
+    /// beyond that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items: Vec<u32> = (0..100).collect();
+    /// let mut combinations = items.combinations::<10>();
+    /// assert_eq!(combinations.remaining_len(), Some(17_310_309_456_440));
+    /// combinations.next();
+    /// assert_eq!(combinations.remaining_len(), Some(17_310_309_456_439));
+    ///
+    /// let items: Vec<u32> = (0..200).collect();
+    /// assert_eq!(items.combinations::<100>().remaining_len(), None);
+    /// ```
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// This is computed from the current state, without advancing the
+    /// iterator, so there's no need to clone it and call `next` to find out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut combinations = [1, 2].combinations::<2>();
+    /// assert!(!combinations.is_empty());
+    /// combinations.by_ref().for_each(drop);
+    /// assert!(combinations.is_empty());
+    /// assert!([1].combinations::<2>().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+
+    /// Create an iterator over only the combinations of `items` whose position
+    /// in the iteration order falls in `ranks`.
+    ///
+    /// The start of the range is jumped to directly, so a large space of
+    /// combinations can be split into chunks which are each enumerated on their
+    /// own. Ranks past the last combination are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{SliceCombinations, SliceExt};
+    ///
+    /// let items = [1, 2, 3, 4, 5];
+    /// let chunk = SliceCombinations::<_, 3>::from_rank_range(&items, 4..7);
+    /// assert_eq!(chunk.size_hint(), (3, Some(3)));
+    /// assert!(chunk.eq(items.combinations::<3>().skip(4).take(3)));
+    /// ```
+    pub fn from_rank_range(items: &'a [T], ranks: Range<usize>) -> Take<Self> {
+        let mut iter = Self::new(items);
+        let _ = iter.advance_by(ranks.start);
+        iter.take(ranks.len())
+    }
+
+    /// Return the combination at position `rank` in the iteration order, or
+    /// `None` if there are no more than `rank` combinations.
+    ///
+    /// The rank is counted from the first combination, regardless of how far the
+    /// iterator has advanced, and the iterator itself is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let combinations = [1, 2, 3, 4].combinations();
+    /// assert_eq!(combinations.get(0), Some([&1, &2, &3]));
+    /// assert_eq!(combinations.get(2), Some([&1, &3, &4]));
+    /// assert_eq!(combinations.get(4), None);
+    /// ```
+    pub fn get(&self, rank: usize) -> Option<[&'a T; K]> {
+        let item_count = self.items.len();
+        // A count which doesn't fit in a `usize` is larger than any rank
+        if binomial(item_count, K).is_some_and(|count| rank >= count) {
+            return None;
+        }
+        let mut gen = LazyCombinationGenerator::<K>::new();
+        gen.seek(rank, item_count);
+        let indices = gen.indices();
+        Some(make_array(|i| &self.items[indices[i]]))
+    }
+
+    /// Return an iterator adaptor which yields every `step`-th combination,
+    /// starting with the first one.
+    ///
+    /// Unlike stepping through all the combinations in between, every step
+    /// jumps straight to the combination `step` ranks further along. This makes
+    /// systematic sampling of huge spaces of combinations cheap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items: Vec<u32> = (0..60).collect();
+    /// // There are over 10^17 combinations, so stepping through them would take a while
+    /// let samples = items.combinations::<30>().step_by_rank(1_000_000_000_000_000);
+    /// assert_eq!(samples.count(), 119);
+    /// ```
+    pub fn step_by_rank(self, step: usize) -> StepBy<Self> {
+        self.step_by(step)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return an iterator adaptor which yields every combination in a `Box`.
+    ///
+    /// Each array is written straight into its heap allocation, rather than
+    /// being built on the stack and moved from frame to frame. This keeps the
+    /// stack usage down when `K` is large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items: Vec<u32> = (0..300).collect();
+    /// let mut combinations = items.combinations::<299>().boxed();
+    /// let first = combinations.next().unwrap();
+    /// assert_eq!(*first[298], 298);
+    /// assert_eq!(combinations.count(), 299);
+    /// ```
+    pub fn boxed(self) -> Boxed<Self> {
+        Boxed::new(self)
+    }
+
+    /// Return an iterator adaptor which also yields the positions in the slice
+    /// of the elements of each combination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = ["a", "b", "c"];
+    /// let mut combinations = items.combinations::<2>().with_indices();
+    /// assert_eq!(combinations.next(), Some(([0, 1], [&"a", &"b"])));
+    /// assert_eq!(combinations.next(), Some(([0, 2], [&"a", &"c"])));
+    /// assert_eq!(combinations.next(), Some(([1, 2], [&"b", &"c"])));
+    /// ```
+    pub fn with_indices(self) -> WithIndices<Self, K> {
+        WithIndices::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, const K: usize> BoxedSource for SliceCombinations<'a, T, K> {
+    fn next_boxed(&mut self) -> Option<Box<[&'a T; K]>> {
+        self.state.get_boxed_and_step(self.items, |t| t)
+    }
+}
+
+impl<'a, T, const K: usize> IndexedSource<K> for SliceCombinations<'a, T, K> {
+    fn next_with_indices(&mut self) -> Option<([usize; K], [&'a T; K])> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the generator wasn't done, so every index is in bounds
+        Some((indices, indices.map(|i| unsafe { get_item(self.items, i) })))
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceCombinations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        self.state.get_and_step(self.items, |t| t)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+
+    fn fold<B, G>(mut self, init: B, g: G) -> B
+    where
+        G: FnMut(B, [&'a T; K]) -> B,
+    {
+        self.state.fold(self.items, init, g)
+    }
+
+    fn for_each<G>(mut self, mut g: G)
+    where
+        G: FnMut([&'a T; K]),
+    {
+        self.state.fold(self.items, (), |(), res| g(res));
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, g: G) -> R
+    where
+        G: FnMut(B, [&'a T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        self.state.try_fold(self.items, init, g)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceCombinations<'_, T, K> {}
+
+/// An iterator that returns k-length combinations of values from `slice`, along
+/// with the values which weren't chosen.
+///
+/// This `struct` is created by the [`combinations_with_complement`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`combinations_with_complement`]: super::SliceExt::combinations_with_complement
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceComplementCombinations<'a, T, const K: usize> {
+    items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SliceComplementCombinations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceComplementCombinations<'a, T, K> {
+    type Item = ([&'a T; K], Complement<'a, T, K>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chosen = self.state.get_indices_and_step(self.items.len())?;
+        let items = self.items;
+        Some((
+            make_array(|i| &items[chosen[i]]),
+            Complement {
+                items,
+                chosen,
+                index: 0,
+                skipped: 0,
+            },
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceComplementCombinations<'_, T, K> {}
+
+/// An iterator over the values of a slice which weren't chosen for a combination.
+///
+/// This `struct` is yielded by [`SliceComplementCombinations`] next to the
+/// chosen values, and returns the remaining values in the order of the slice.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Complement<'a, T, const K: usize> {
+    items: &'a [T],
+    // Chosen indices in increasing order, `skipped` of which have been passed
+    chosen: [usize; K],
+    index: usize,
+    skipped: usize,
+}
+
+impl<'a, T, const K: usize> Iterator for Complement<'a, T, K> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        while self.index < self.items.len() {
+            let index = self.index;
+            self.index += 1;
+            if self.chosen.get(self.skipped) == Some(&index) {
+                self.skipped += 1;
+            } else {
+                return Some(&self.items[index]);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.items.len() - self.index - (K - self.skipped);
+        (len, Some(len))
+    }
+}
+
+impl<T, const K: usize> ExactSizeIterator for Complement<'_, T, K> {}
+
+impl<T, const K: usize> FusedIterator for Complement<'_, T, K> {}
+
+/// An iterator that returns pairs of disjoint combinations of values from
+/// `slice`.
+///
+/// This `struct` is created by the [`disjoint_combinations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`disjoint_combinations`]: super::SliceExt::disjoint_combinations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceDisjointCombinations<'a, T, const K1: usize, const K2: usize> {
+    items: &'a [T],
+    first: LazyCombinationGenerator<K1>,
+    // Indexes into the items which aren't part of the first combination
+    second: LazyCombinationGenerator<K2>,
+}
+
+impl<'a, T, const K1: usize, const K2: usize> SliceDisjointCombinations<'a, T, K1, K2> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        let mut first = LazyCombinationGenerator::new();
+        if K1.checked_add(K2).is_none_or(|k| k > items.len()) {
+            first.finish();
+        }
+        Self {
+            items,
+            first,
+            second: LazyCombinationGenerator::new(),
+        }
+    }
+
+    /// Return `true` if there are no pairs of combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        // A count which doesn't fit in a `usize` isn't zero
+        self.remaining() == Some(0)
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        let item_count = self.items.len();
+        match self.first.remaining(item_count)? {
+            0 => Some(0),
+            first => {
+                // Every later first combination comes with all second ones
+                let rest = item_count - K1;
+                (first - 1)
+                    .checked_mul(binomial(rest, K2)?)?
+                    .checked_add(self.second.remaining(rest)?)
+            }
+        }
+    }
+}
+
+impl<'a, T, const K1: usize, const K2: usize> Iterator
+    for SliceDisjointCombinations<'a, T, K1, K2>
+{
+    type Item = ([&'a T; K1], [&'a T; K2]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item_count = self.items.len();
+        if self.first.is_done(item_count) {
+            return None;
+        }
+        if self.second.is_done(item_count - K1) {
+            self.first.step();
+            self.second = LazyCombinationGenerator::new();
+            if self.first.is_done(item_count) {
+                return None;
+            }
+        }
+        let first = self.first.indices();
+        let res = (
+            make_array(|i| &self.items[first[i]]),
+            make_array(|i| {
+                // Skip over the indices taken by the first combination, which
+                // are in increasing order
+                let mut index = self.second.indices()[i];
+                for &taken in first {
+                    if taken <= index {
+                        index += 1;
+                    }
+                }
+                &self.items[index]
+            }),
+        );
+        self.second.step();
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.remaining())
+    }
+}
+
+impl<T, const K1: usize, const K2: usize> FusedIterator
+    for SliceDisjointCombinations<'_, T, K1, K2>
+{
+}
+
+/// A lending iterator that returns k-length combinations of mutable references
+/// into `slice`.
+///
+/// The same element shows up in many combinations, so the references returned
+/// by [`next`] only live until the next call. That's why this doesn't implement
+/// [`Iterator`], and is driven with `while let` instead.
+///
+/// This `struct` is created by the [`combinations_mut`] method on [`SliceExt`].
+/// See its documentation for more.
+///
+/// [`next`]: SliceCombinationsMut::next
+/// [`combinations_mut`]: super::SliceExt::combinations_mut
+/// [`SliceExt`]: super::SliceExt
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceCombinationsMut<'a, T, const K: usize> {
+    items: &'a mut [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SliceCombinationsMut<'a, T, K> {
+    pub(crate) fn new(items: &'a mut [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Advance to the next combination and return mutable references to its
+    /// elements, or `None` once every combination has been returned.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<[&mut T; K]> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the indices of a combination are in bounds and strictly
+        // increasing, so no element is borrowed twice
+        Some(unsafe { self.items.get_disjoint_unchecked_mut(indices) })
+    }
+
+    /// Return the number of combinations left.
+    ///
+    /// Returns `None` if the number doesn't fit in a `usize`.
+    pub fn remaining(&self) -> Option<usize> {
+        self.state.remaining(self.items.len())
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+/// An iterator that returns k-length combinations of copies of values from
+/// `slice`.
+///
+/// This `struct` is created by the [`combinations_copied`] method on [`SliceExt`].
+/// See its documentation for more.
+///
+/// [`combinations_copied`]: super::SliceExt::combinations_copied
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceCopiedCombinations<'a, T, const K: usize> {
+    items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SliceCopiedCombinations<'a, T, K>
+where
+    T: Copy,
+{
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Combinations::save`] for more.
+    pub fn save(&self) -> CombinationsSnapshot<K> {
+        CombinationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Combinations::restore`] for more.
+    pub fn restore(&mut self, snapshot: CombinationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the next combination without advancing the iterator.
+    ///
+    /// This is computed from the current state, so unlike wrapping the
+    /// iterator in a [`Peekable`] no combination is stored.
+    ///
+    /// [`Peekable`]: core::iter::Peekable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = [1, 2, 3];
+    /// let mut combinations = items.combinations_copied::<2>();
+    /// assert_eq!(combinations.peek(), Some([1, 2]));
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// ```
+    pub fn peek(&self) -> Option<[T; K]> {
+        self.state.peek(self.items, |t| *t)
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return an iterator adaptor which copies every combination into a `Box`.
+    ///
+    /// See [`SliceCombinations::boxed`] for more.
+    pub fn boxed(self) -> Boxed<Self> {
+        Boxed::new(self)
+    }
+
+    /// Return an iterator adaptor which also yields the positions in the slice
+    /// of the elements of each combination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = ["a", "b", "c"];
+    /// let mut combinations = items.combinations_copied::<2>().with_indices();
+    /// assert_eq!(combinations.next(), Some(([0, 1], ["a", "b"])));
+    /// assert_eq!(combinations.next(), Some(([0, 2], ["a", "c"])));
+    /// assert_eq!(combinations.next(), Some(([1, 2], ["b", "c"])));
+    /// ```
+    pub fn with_indices(self) -> WithIndices<Self, K> {
+        WithIndices::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> BoxedSource for SliceCopiedCombinations<'_, T, K>
+where
+    T: Copy,
+{
+    fn next_boxed(&mut self) -> Option<Box<[T; K]>> {
+        self.state.get_boxed_and_step(self.items, |&t| t)
+    }
+}
+
+impl<T, const K: usize> IndexedSource<K> for SliceCopiedCombinations<'_, T, K>
+where
+    T: Copy,
+{
+    fn next_with_indices(&mut self) -> Option<([usize; K], [T; K])> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the generator wasn't done, so every index is in bounds
+        Some((
+            indices,
+            indices.map(|i| unsafe { *get_item(self.items, i) }),
+        ))
+    }
+}
+
+impl<T, const K: usize> Iterator for SliceCopiedCombinations<'_, T, K>
+where
+    T: Copy,
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        self.state.get_copied_and_step(self.items)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [T; K]) -> B,
+    {
+        let mut acc = init;
+        while let Some(res) = self.state.get_copied_and_step(self.items) {
+            acc = g(acc, res);
+        }
+        acc
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        while let Some(res) = self.state.get_copied_and_step(self.items) {
+            acc = g(acc, res)?;
+        }
+        R::from_output(acc)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceCopiedCombinations<'_, T, K> where T: Copy {}
+
+/// Return an iterator over the k-length combinations of the values in `range`.
+///
+/// This produces the same arrays in the same order as calling
+/// [`combinations`] on the range, but computes each value from its index
+/// instead of buffering the values it has seen. That keeps the memory use
+/// constant no matter how large the range is, and lets [`nth`] jump straight
+/// to any combination. If `K` is greater than the length of the range the
+/// resulting iterator will yield no items.
+///
+/// [`combinations`]: crate::IterExt::combinations
+/// [`nth`]: Iterator::nth
+///
+/// # Panics
+///
+/// Panics if the range holds more than `usize::MAX` values, since every
+/// combination is computed from the positions of its values in the range.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::range_combinations;
+///
+/// let mut combinations = range_combinations::<3, _>(1..5);
+/// assert_eq!(combinations.next(), Some([1, 2, 3]));
+/// assert_eq!(combinations.next(), Some([1, 2, 4]));
+/// assert_eq!(combinations.next(), Some([1, 3, 4]));
+/// assert_eq!(combinations.next(), Some([2, 3, 4]));
+/// assert_eq!(combinations.next(), None);
+///
+/// let mut pairs = range_combinations::<2, u64>(0..1 << 20);
+/// assert_eq!(pairs.nth(1 << 30), Some([20854, 46341]));
+/// ```
+pub fn range_combinations<const K: usize, T>(range: Range<T>) -> RangeCombinations<T, K>
+where
+    T: Step,
+{
+    RangeCombinations::new(range)
+}
+
+/// An iterator that returns k-length combinations of values from a range.
+///
+/// This `struct` is created by the [`range_combinations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct RangeCombinations<T, const K: usize> {
+    start: T,
+    len: usize,
+    state: State<K>,
+}
+
+impl<T, const K: usize> RangeCombinations<T, K>
+where
+    T: Step,
+{
+    pub(crate) fn new(range: Range<T>) -> Self {
+        let len = match T::steps_between(&range.start, &range.end) {
+            (_, Some(len)) => len,
+            (_, None) => panic!("range holds more than `usize::MAX` values"),
+        };
+        Self {
+            len,
+            start: range.start,
+            state: State::new(),
+        }
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.len)
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.len)
+    }
+}
+
+impl<T, const K: usize> Iterator for RangeCombinations<T, K>
+where
+    T: Step,
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        let indices = self.state.get_indices_and_step(self.len)?;
+        Some(make_array(|i| T::forward(self.start.clone(), indices[i])))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.len))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.len, n)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for RangeCombinations<T, K> where T: Step {}
+
+/// An iterator that returns k-length combinations of values from `slice` whose
+/// positions are at least a given distance apart.
+///
+/// This `struct` is created by the [`spaced_combinations`],
+/// [`non_adjacent_combinations`] and [`circular_non_adjacent_combinations`]
+/// methods on [`SliceExt`]. See their documentation for more.
+///
+/// [`spaced_combinations`]: super::SliceExt::spaced_combinations
+/// [`non_adjacent_combinations`]: super::SliceExt::non_adjacent_combinations
+/// [`circular_non_adjacent_combinations`]: super::SliceExt::circular_non_adjacent_combinations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceSpacedCombinations<'a, T, const K: usize> {
+    items: &'a [T],
+    gen: LazyCombinationGenerator<K>,
+    // Extra distance between consecutive picks on top of the usual one
+    padding: usize,
+    // Whether the first and last items are considered adjacent
+    circular: bool,
+}
+
+impl<'a, T, const K: usize> SliceSpacedCombinations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T], gap: usize) -> Self {
+        Self {
+            items,
+            gen: LazyCombinationGenerator::new(),
+            padding: gap.saturating_sub(1),
+            circular: false,
+        }
+    }
+
+    pub(crate) fn circular(items: &'a [T]) -> Self {
+        Self {
+            circular: true,
+            ..Self::new(items, 2)
+        }
+    }
+
+    fn reduced_len(&self) -> Option<usize> {
+        // Spaced combinations of `n` items correspond one-to-one with plain
+        // combinations of the `n - (K - 1) * padding` items left after removing
+        // the padding between picks
+        let padding = K.saturating_sub(1).checked_mul(self.padding)?;
+        self.items.len().checked_sub(padding)
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceSpacedCombinations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        let reduced_len = self.reduced_len()?;
+        if self.circular && K > 1 && self.gen.max_index().map(|i| i + 1) == Some(reduced_len) {
+            // Picking both the first and the last item would wrap around
+            if self.gen.indices()[0] == 0 {
+                self.gen.raise_first();
+            }
+        }
+        if self.gen.is_done(reduced_len) {
+            None
+        } else {
+            let indices = self.gen.indices();
+            let res = make_array(|i| &self.items[indices[i] + i * self.padding]);
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceSpacedCombinations<'_, T, K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IterExt;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn remaining_len() {
+        use crate::SliceExt;
+
+        let items = [0u8; 9];
+        let mut combinations = items.combinations::<4>();
+        let mut copied = items.combinations_copied::<4>();
+        let mut mutable = [0u8; 9];
+        let mut mutable = mutable.combinations_mut::<4>();
+        loop {
+            let len = combinations.clone().count() as u128;
+            assert_eq!(combinations.remaining_len(), Some(len));
+            assert_eq!(copied.remaining_len(), Some(len));
+            assert_eq!(mutable.remaining_len(), Some(len));
+            mutable.next();
+            copied.next();
+            if combinations.next().is_none() {
+                break;
+            }
+        }
+        assert_eq!(combinations.remaining_len(), Some(0));
+        // Past what a `usize` holds, C(128, 64) ~ 2.4e37 still fits a `u128`
+        let items = [0u8; 128];
+        let mut combinations = items.combinations::<64>();
+        let total = crate::counting::binomial_u128(128, 64).unwrap();
+        assert_eq!(combinations.remaining_len(), Some(total));
+        combinations.nth(1000);
+        assert_eq!(combinations.remaining_len(), Some(total - 1001));
+    }
+
+    #[test]
+    fn snapshots() {
+        use crate::SliceExt;
+        use alloc::vec::Vec;
+
+        let all: Vec<_> = (0..6).combinations::<3>().collect();
+        let mut combinations = (0..6).combinations::<3>();
+        combinations.nth(4);
+        let snapshot = combinations.save();
+        assert!(combinations.by_ref().eq(all[5..].iter().copied()));
+        combinations.restore(snapshot.clone());
+        assert_eq!(combinations.size_hint().1, Some(all.len() - 5));
+        assert_eq!(combinations.next(), Some(all[5]));
+
+        // Snapshots carry over to any iterator over the same elements
+        let items = [0, 1, 2, 3, 4, 5];
+        let mut slice = items.combinations::<3>();
+        slice.restore(snapshot.clone());
+        assert_eq!(slice.next(), Some(all[5].map(|i| &items[i])));
+        let mut copied = items.combinations_copied::<3>();
+        copied.restore(snapshot.clone());
+        assert_eq!(copied.save().state.remaining(6), Some(all.len() - 5));
+        let mut fresh = (0..6).combinations::<3>();
+        fresh.restore(snapshot);
+        assert!(fresh.items().is_empty());
+        assert!(fresh.eq(all[5..].iter().copied()));
+
+        let mut forked = (0..6).combinations::<3>().fork();
+        let start = forked.save();
+        assert_eq!(forked.by_ref().count(), all.len());
+        forked.restore(start);
+        assert_eq!(forked.next(), Some(all[0]));
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut combinations = (0..6).combinations::<3>();
+        for len in (0..=20).rev() {
+            assert_eq!(combinations.len(), len);
+            combinations.next();
+        }
+        assert_eq!((0..2).combinations::<3>().len(), 0);
+        assert_eq!((0..2).combinations::<0>().len(), 1);
+        let shared = (0..5).combinations_shared::<alloc::rc::Rc<i32>, 2>();
+        assert_eq!(shared.len(), 10);
+        assert_eq!((0..).combinations::<2>().size_hint(), (usize::MAX, None));
+        assert_eq!(
+            (0..usize::MAX).combinations::<2>().size_hint(),
+            (usize::MAX, None)
+        );
+        let n = usize::MAX as u128;
+        assert_eq!(
+            (0..usize::MAX).combinations::<2>().remaining_len(),
+            Some(n * (n - 1) / 2)
+        );
+    }
+
+    #[test]
+    fn next_combination_matches() {
+        let mut indices = Some([0, 1, 2]);
+        for expected in (0..7).combinations::<3>() {
+            assert_eq!(indices, Some(expected));
+            indices = next_combination(indices.unwrap(), 7);
+        }
+        assert_eq!(indices, None);
+        assert_eq!(next_combination([], 3), None);
+        assert_eq!(next_combination([2], 3), None);
+    }
+
+    #[test]
+    fn write_combinations_into() {
+        let mut out = [[0; 3]; 40];
+        assert_eq!(write_combinations::<7, 3>(&mut out), 35);
+        assert!(out[..35].iter().copied().eq((0..7).combinations::<3>()));
+        assert_eq!(out[35..], [[0; 3]; 5]);
+        let mut out = [[0; 2]; 2];
+        assert_eq!(write_combinations::<3, 2>(&mut out), 2);
+        assert_eq!(out, [[0, 1], [0, 2]]);
+        assert_eq!(write_combinations::<3, 2>(&mut []), 0);
+        assert_eq!(write_combinations::<0, 0>(&mut [[]]), 1);
+    }
+
+    #[test]
+    fn fold() {
+        let sum = (1..6)
+            .combinations::<3>()
+            .fold(0, |acc, [a, b, c]| acc + a * b * c);
+        assert_eq!(sum, 225);
+        // Nothing is buffered when no items are needed
+        assert_eq!((0..).combinations::<0>().fold(0, |acc, _| acc + 1), 1);
+        // Only the elements the combinations so far needed are read
+        let mut combinations = (0..).combinations::<2>();
+        assert_eq!(combinations.find(|&[a, b]| a + b == 7), Some([3, 4]));
+        assert_eq!(combinations.items(), &[0, 1, 2, 3, 4]);
+        assert_eq!(combinations.next(), Some([0, 5]));
+        let mut combinations = (0..5).combinations::<3>();
+        combinations.nth(3);
+        let rest: Vec<_> = combinations.collect();
+        assert!(rest.into_iter().eq((0..5).combinations::<3>().skip(4)));
+    }
 
     #[test]
     fn order() {
@@ -199,6 +1977,90 @@ mod test {
         assert_eq!(combinations.next(), None);
     }
 
+    #[test]
+    fn presized() {
+        assert!((0..10).combinations::<3>().items.capacity() >= 10);
+        assert_eq!((0..10).combinations::<0>().items.capacity(), 0);
+        assert_eq!((0..).combinations::<2>().items.capacity(), 0);
+        assert_eq!((0..usize::MAX).combinations::<2>().items.capacity(), 0);
+        let filtered = (0..10).filter(|n| n % 3 > 0);
+        assert!(filtered.combinations_with_capacity::<2>(7).items.capacity() >= 7);
+    }
+
+    #[test]
+    fn is_empty() {
+        use crate::SliceExt;
+
+        let mut combinations = (0..4).combinations::<3>();
+        assert!(!combinations.is_exhausted());
+        assert!(!combinations.is_exhausted());
+        assert!(combinations.clone().eq((0..4).combinations::<3>()));
+        let mut forked = combinations.fork();
+        assert!(!forked.is_empty());
+        forked.by_ref().for_each(drop);
+        assert!(forked.is_empty());
+        assert!(!(0..0).combinations::<0>().is_exhausted());
+
+        let mut items = [1, 2, 3];
+        assert!(!items.combinations_copied::<3>().is_empty());
+        assert!(items.combinations_copied::<4>().is_empty());
+        let mut mutable = items.combinations_mut::<3>();
+        assert!(!mutable.is_empty());
+        mutable.next();
+        assert!(mutable.is_empty());
+
+        let mut complement = items.combinations_with_complement::<2>();
+        assert!(!complement.is_empty());
+        complement.by_ref().for_each(drop);
+        assert!(complement.is_empty());
+        let mut disjoint = items.disjoint_combinations::<1, 2>();
+        assert!(!disjoint.is_empty());
+        disjoint.by_ref().for_each(drop);
+        assert!(disjoint.is_empty());
+        assert!(items.disjoint_combinations::<2, 2>().is_empty());
+        let mut range = range_combinations::<2, u8>(0..3);
+        assert!(!range.is_empty());
+        range.by_ref().for_each(drop);
+        assert!(range.is_empty());
+        let mut shared = (0..3).combinations_shared::<alloc::rc::Rc<i32>, 2>();
+        assert!(!shared.is_exhausted());
+        shared.by_ref().for_each(drop);
+        assert!(shared.is_exhausted());
+    }
+
+    #[test]
+    fn grows_as_needed() {
+        // Neither bound is exact, so nothing is reserved up front, and reading
+        // the first combination only makes room for its elements
+        let hinted = (0..1_000_000).chain(core::iter::from_fn(|| None));
+        let mut combinations = hinted.combinations::<2>();
+        assert_eq!(combinations.items.capacity(), 0);
+        combinations.next();
+        assert!(combinations.items.capacity() < 100);
+        assert_eq!(combinations.by_ref().take(1000).count(), 1000);
+        assert!(combinations.items.capacity() < 1000);
+        let mut combinations = (0..).combinations::<2>();
+        combinations.next();
+        assert!(combinations.items.capacity() < 1000);
+        let mut combinations = (0..10).filter(|n| n % 3 > 0).combinations::<2>();
+        combinations.reserve_items(7);
+        assert!(combinations.items.capacity() >= 7);
+    }
+
+    #[test]
+    fn fork() {
+        let mut combinations = (0..6).combinations::<3>();
+        combinations.nth(4);
+        let forked = combinations.clone().fork();
+        assert_eq!(forked.size_hint(), combinations.size_hint());
+        assert_eq!(forked.remaining_len(), Some(15));
+        let other = forked.clone();
+        assert!(Arc::ptr_eq(&forked.items, &other.items));
+        assert!(forked.eq(combinations.clone()));
+        assert!(other.eq(combinations));
+        assert_eq!((0..).combinations::<0>().fork().count(), 1);
+    }
+
     #[test]
     fn fused_propagation() {
         let fused = [1, 2, 3].iter().fuse();
@@ -263,10 +2125,399 @@ mod test {
     }
 }
 
+#[cfg(test)]
+mod copied_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut combinations = [1, 2, 3, 4].combinations_copied();
+        assert_eq!(combinations.next(), Some([1, 2, 3]));
+        assert_eq!(combinations.next(), Some([1, 2, 4]));
+        assert_eq!(combinations.next(), Some([1, 3, 4]));
+        assert_eq!(combinations.next(), Some([2, 3, 4]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn matches_references() {
+        let items = [3u8, 1, 4, 1, 5, 9, 2, 6];
+        assert!(items
+            .combinations_copied::<3>()
+            .eq(items.combinations::<3>().map(|c| c.map(|&t| t))));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1].combinations_copied::<2>();
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].combinations_copied();
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod complement_test {
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let mut combinations = [1, 2, 3].combinations_with_complement::<2>();
+        let (chosen, rest) = combinations.next().unwrap();
+        assert_eq!((chosen, rest.collect::<Vec<_>>()), ([&1, &2], [&3].into()));
+        let (chosen, rest) = combinations.next().unwrap();
+        assert_eq!((chosen, rest.collect::<Vec<_>>()), ([&1, &3], [&2].into()));
+        let (chosen, rest) = combinations.next().unwrap();
+        assert_eq!((chosen, rest.collect::<Vec<_>>()), ([&2, &3], [&1].into()));
+        assert!(combinations.next().is_none());
+        assert!(combinations.next().is_none());
+    }
+
+    #[test]
+    fn partitions_slice() {
+        let items = [3, 1, 4, 1, 5, 9, 2];
+        let combinations = items.combinations_with_complement::<3>();
+        assert_eq!(combinations.size_hint(), (35, Some(35)));
+        for ((chosen, rest), expected) in combinations.zip(items.combinations::<3>()) {
+            assert_eq!(chosen, expected);
+            assert_eq!(rest.len(), 4);
+            let mut all: Vec<_> = chosen.iter().copied().chain(rest).copied().collect();
+            all.sort_unstable();
+            assert_eq!(all, [1, 1, 2, 3, 4, 5, 9]);
+        }
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1].combinations_with_complement::<2>();
+        assert!(combinations.next().is_none());
+        assert!(combinations.next().is_none());
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].combinations_with_complement::<0>();
+        let (chosen, rest) = combinations.next().unwrap();
+        assert_eq!((chosen, rest.collect::<Vec<_>>()), ([], [&1, &2].into()));
+        assert!(combinations.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod disjoint_test {
+    use crate::counting::factorial;
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut groups = [1, 2, 3].disjoint_combinations::<1, 1>();
+        assert_eq!(groups.next(), Some(([&1], [&2])));
+        assert_eq!(groups.next(), Some(([&1], [&3])));
+        assert_eq!(groups.next(), Some(([&2], [&1])));
+        assert_eq!(groups.next(), Some(([&2], [&3])));
+        assert_eq!(groups.next(), Some(([&3], [&1])));
+        assert_eq!(groups.next(), Some(([&3], [&2])));
+        assert_eq!(groups.next(), None);
+        assert_eq!(groups.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        let items = [0, 1, 2, 3, 4, 5, 6];
+        let mut groups = items.disjoint_combinations::<2, 3>();
+        // The multinomial coefficient 7! / (2! 3! 2!)
+        let expected = factorial(7).unwrap() / 24;
+        assert_eq!(groups.size_hint(), (expected, Some(expected)));
+        groups.next();
+        assert_eq!(groups.size_hint(), (expected - 1, Some(expected - 1)));
+        let mut seen = 1;
+        for (first, second) in groups.by_ref() {
+            assert!(first.iter().all(|a| !second.contains(a)));
+            seen += 1;
+        }
+        assert_eq!(seen, expected);
+        assert_eq!(groups.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut groups = [1, 2].disjoint_combinations::<2, 1>();
+        assert_eq!(groups.next(), None);
+        assert_eq!(groups.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut groups = [1, 2].disjoint_combinations::<0, 0>();
+        assert_eq!(groups.next(), Some(([], [])));
+        assert_eq!(groups.next(), None);
+        let mut groups = [1, 2].disjoint_combinations::<0, 2>();
+        assert_eq!(groups.next(), Some(([], [&1, &2])));
+        assert_eq!(groups.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod mut_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut items = [1, 2, 3];
+        let mut combinations = items.combinations_mut();
+        assert_eq!(combinations.remaining(), Some(3));
+        assert_eq!(combinations.next(), Some([&mut 1, &mut 2]));
+        assert_eq!(combinations.next(), Some([&mut 1, &mut 3]));
+        assert_eq!(combinations.next(), Some([&mut 2, &mut 3]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.remaining(), Some(0));
+    }
+
+    #[test]
+    fn mutates_in_place() {
+        let mut items = [0; 5];
+        let mut combinations = items.combinations_mut::<3>();
+        while let Some([a, b, c]) = combinations.next() {
+            *a += 1;
+            *b += 10;
+            *c += 100;
+        }
+        // Element i is first in C(4 - i, 2) combinations, and so on
+        assert_eq!(items, [6, 33, 141, 330, 600]);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut items = [1];
+        let mut combinations = items.combinations_mut::<2>();
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut items = [1, 2];
+        let mut combinations = items.combinations_mut::<0>();
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod shared_test {
+    use crate::IterExt;
+    use alloc::rc::Rc;
+    use alloc::string::{String, ToString};
+    use alloc::sync::Arc;
+
+    #[test]
+    fn shares_items() {
+        let words = ["foo", "bar", "baz"].iter().map(|s| s.to_string());
+        let combinations: alloc::vec::Vec<[Rc<String>; 2]> = words.combinations_shared().collect();
+        assert_eq!(combinations.len(), 3);
+        assert_eq!(*combinations[0][0], "foo");
+        assert_eq!(*combinations[0][1], "bar");
+        // The first element is shared between both combinations it appears in
+        assert!(Rc::ptr_eq(&combinations[0][0], &combinations[1][0]));
+        assert_eq!(Rc::strong_count(&combinations[0][0]), 2);
+    }
+
+    #[test]
+    fn order() {
+        let mut combinations = (1..5).combinations_shared::<Arc<i32>, 3>();
+        assert_eq!(combinations.next(), Some([1, 2, 3].map(Arc::new)));
+        assert_eq!(combinations.next(), Some([1, 2, 4].map(Arc::new)));
+        assert_eq!(combinations.next(), Some([1, 3, 4].map(Arc::new)));
+        assert_eq!(combinations.next(), Some([2, 3, 4].map(Arc::new)));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = (1..2).combinations_shared::<Rc<i32>, 2>();
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod array_test {
+    use crate::{ArrayExt, SliceExt};
+
+    #[test]
+    fn matches_iterator() {
+        let items = [1, 2, 3, 4, 5];
+        let table: [[i32; 3]; 10] = items.combinations_array();
+        assert!(table
+            .iter()
+            .copied()
+            .eq(items.combinations().map(|c: [&i32; 3]| c.map(|&t| t))));
+    }
+
+    #[test]
+    fn edge_sizes() {
+        let empty: [[u8; 3]; 0] = [1, 2].combinations_array();
+        assert!(empty.is_empty());
+        let single: [[u8; 0]; 1] = [1, 2].combinations_array();
+        assert_eq!(single, [[]]);
+    }
+}
+
 #[cfg(test)]
 mod slice_test {
     use crate::SliceExt;
 
+    #[test]
+    fn peek() {
+        let items = [1, 2, 3, 4, 5];
+        let mut combinations = items.combinations::<3>();
+        let mut copied = items.combinations_copied::<3>();
+        loop {
+            let peeked = combinations.peek();
+            assert_eq!(peeked, combinations.next());
+            assert_eq!(copied.peek(), copied.next());
+            if peeked.is_none() {
+                break;
+            }
+        }
+        assert_eq!(items.combinations::<6>().peek(), None);
+        assert_eq!(items.combinations::<0>().peek(), Some([]));
+    }
+
+    #[test]
+    fn step_by_rank() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        for step in 1..40 {
+            assert!(items
+                .combinations::<4>()
+                .step_by_rank(step)
+                .eq(items.combinations::<4>().step_by(step)));
+        }
+        let items: alloc::vec::Vec<_> = (0..60).collect();
+        let mut samples = items.combinations::<30>().step_by_rank(1 << 56);
+        assert_eq!(samples.size_hint(), (2, Some(2)));
+        samples.next();
+        assert_eq!(samples.next(), items.combinations::<30>().get(1 << 56));
+    }
+
+    #[test]
+    fn from_rank_range() {
+        use crate::SliceCombinations;
+
+        let items = [1, 2, 3, 4, 5, 6];
+        let all: alloc::vec::Vec<_> = items.combinations::<3>().collect();
+        for start in 0..=22 {
+            for end in start..=22 {
+                let chunk = SliceCombinations::<_, 3>::from_rank_range(&items, start..end);
+                let expected = &all[start.min(20)..end.min(20)];
+                assert_eq!(chunk.size_hint(), (expected.len(), Some(expected.len())));
+                assert!(chunk.eq(expected.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn get() {
+        let items = [1, 2, 3, 4, 5, 6];
+        let mut combinations = items.combinations::<3>();
+        let all: alloc::vec::Vec<_> = combinations.clone().collect();
+        combinations.nth(7);
+        for (rank, combination) in all.iter().enumerate() {
+            assert_eq!(combinations.get(rank).as_ref(), Some(combination));
+        }
+        assert_eq!(combinations.get(20), None);
+        assert_eq!(combinations.next(), Some(all[8]));
+        assert_eq!(items.combinations::<7>().get(0), None);
+        assert_eq!(items.combinations::<0>().get(0), Some([]));
+        assert_eq!(items.combinations::<0>().get(1), None);
+    }
+
+    #[test]
+    fn colex_order() {
+        // Reading the indices back to front gives increasing lexicographic order
+        let items = [0, 1, 2, 3, 4, 5, 6];
+        let reversed: alloc::vec::Vec<[usize; 4]> = items
+            .combinations::<4>()
+            .map(|[&a, &b, &c, &d]| [d, c, b, a])
+            .collect();
+        assert!(reversed.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(reversed.len(), 35);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut combinations = [1, 2, 3, 4, 5].combinations::<2>();
+        for len in (0..=10).rev() {
+            assert_eq!(combinations.size_hint(), (len, Some(len)));
+            combinations.next();
+        }
+    }
+
+    #[test]
+    fn advance_by() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        for start in 0..36 {
+            for n in 0..40 {
+                let mut jumped = items.combinations::<4>();
+                let mut stepped = jumped.clone();
+                jumped.advance_by(start).ok();
+                stepped.by_ref().take(start).for_each(drop);
+                let res = jumped.advance_by(n);
+                let taken = stepped.by_ref().take(n).count();
+                assert_eq!(
+                    res.map_err(|k| k.get()),
+                    if taken == n { Ok(()) } else { Err(n - taken) }
+                );
+                assert!(jumped.eq(stepped));
+            }
+        }
+    }
+
+    #[test]
+    fn nth_is_fast() {
+        let items: alloc::vec::Vec<usize> = (0..1000).collect();
+        let mut combinations = items.combinations::<3>();
+        // The last combination is number `C(1000, 3) - 1`
+        assert_eq!(combinations.nth(166_166_999), Some([&997, &998, &999]));
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn for_each() {
+        let items = [1, 2, 3, 4, 5, 6];
+        let mut all = alloc::vec::Vec::new();
+        items.combinations::<3>().for_each(|c| all.push(c));
+        assert!(all.into_iter().eq(items.combinations::<3>()));
+    }
+
+    #[test]
+    fn try_fold() {
+        let mut combinations = [1, 2, 3, 4, 5].combinations();
+        let found = combinations.try_fold((), |(), [&a, &b]| match a + b {
+            6 => Err([a, b]),
+            _ => Ok(()),
+        });
+        assert_eq!(found, Err([2, 4]));
+        assert_eq!(combinations.next(), Some([&3, &4]));
+        assert_eq!(combinations.try_fold(0, |acc, _| Some(acc + 1)), Some(4));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(
+            [1, 2, 3, 4, 5]
+                .combinations::<3>()
+                .fold(0, |acc, _| acc + 1),
+            10
+        );
+    }
+
     #[test]
     fn order() {
         let mut combinations = [1, 2, 3, 4, 5].combinations();
@@ -299,3 +2550,138 @@ mod slice_test {
         assert_eq!(combinations.next(), None);
     }
 }
+
+#[cfg(test)]
+mod spaced_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut combinations = [1, 2, 3, 4, 5, 6].spaced_combinations(2);
+        assert_eq!(combinations.next(), Some([&1, &3, &5]));
+        assert_eq!(combinations.next(), Some([&1, &3, &6]));
+        assert_eq!(combinations.next(), Some([&1, &4, &6]));
+        assert_eq!(combinations.next(), Some([&2, &4, &6]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let items = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        for gap in 0..5 {
+            let filtered = items
+                .combinations::<3>()
+                .filter(|c| c.windows(2).all(|w| *w[1] - *w[0] >= gap));
+            assert!(filtered.eq(items.spaced_combinations::<3>(gap)));
+        }
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1, 2, 3, 4].spaced_combinations::<3>(2);
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].spaced_combinations::<0>(5);
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod non_adjacent_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn linear() {
+        let mut combinations = [1, 2, 3, 4].non_adjacent_combinations();
+        assert_eq!(combinations.next(), Some([&1, &3]));
+        assert_eq!(combinations.next(), Some([&1, &4]));
+        assert_eq!(combinations.next(), Some([&2, &4]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn circular() {
+        let mut combinations = [1, 2, 3, 4].circular_non_adjacent_combinations();
+        assert_eq!(combinations.next(), Some([&1, &3]));
+        assert_eq!(combinations.next(), Some([&2, &4]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn circular_matches_filtered() {
+        let items = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        for n in 0..items.len() {
+            let items = &items[..n];
+            let filtered = items
+                .combinations::<3>()
+                .filter(|c| c.windows(2).all(|w| *w[1] - *w[0] >= 2) && *c[0] + n - *c[2] >= 2);
+            assert!(filtered.eq(items.circular_non_adjacent_combinations::<3>()));
+        }
+    }
+
+    #[test]
+    fn circular_single() {
+        let mut combinations = [1].circular_non_adjacent_combinations();
+        assert_eq!(combinations.next(), Some([&1]));
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1, 2, 3, 4, 5, 6].circular_non_adjacent_combinations::<4>();
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod range_test {
+    use super::*;
+    use crate::IterExt;
+
+    #[test]
+    fn matches_combinations() {
+        assert!(range_combinations::<3, _>(-4..5).eq((-4..5).combinations::<3>()));
+        assert!(range_combinations::<2, _>('a'..'g').eq(('a'..'g').combinations::<2>()));
+        assert!(range_combinations::<4, u8>(0..3).eq((0..3).combinations::<4>()));
+        let mut empty = range_combinations::<0, _>(5..5);
+        assert_eq!(empty.next(), Some([]));
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut combinations = range_combinations::<3, _>(10..16);
+        for len in (0..=20).rev() {
+            assert_eq!(combinations.size_hint(), (len, Some(len)));
+            combinations.next();
+        }
+        let combinations = range_combinations::<3, usize>(0..usize::MAX);
+        assert_eq!(combinations.size_hint(), (usize::MAX, None));
+        let n = usize::MAX as u128;
+        let combinations = range_combinations::<2, usize>(0..usize::MAX);
+        assert_eq!(combinations.remaining_len(), Some(n * (n - 1) / 2));
+    }
+
+    #[test]
+    fn huge_range() {
+        let start = usize::MAX / 2;
+        let mut combinations = range_combinations::<2, _>(start..usize::MAX);
+        assert_eq!(combinations.next(), Some([start, start + 1]));
+        assert_eq!(combinations.nth(2), Some([start, start + 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range holds more than `usize::MAX` values")]
+    fn too_long_range() {
+        let _ = range_combinations::<2, u128>(0..u128::MAX);
+    }
+}
@@ -0,0 +1,238 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyCompositionGenerator<const K: usize> {
+    parts: [usize; K],
+    min: usize,
+    done: bool,
+}
+
+impl<const K: usize> LazyCompositionGenerator<K> {
+    pub fn new(n: usize, min: usize) -> Self {
+        let mut parts = [min; K];
+        let done = match K.checked_mul(min) {
+            Some(floor) if K > 0 && floor <= n => {
+                parts[K - 1] += n - floor;
+                false
+            }
+            _ => !(K == 0 && n == 0),
+        };
+        Self { parts, min, done }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn parts(&self) -> &[usize; K] {
+        &self.parts
+    }
+
+    pub fn step(&mut self) {
+        // Find the last part past the first one which can still give something up
+        let min = self.min;
+        let j = match self.parts.iter().skip(1).rposition(|&part| part > min) {
+            Some(j) => j + 1,
+            None => {
+                self.done = true;
+                return;
+            }
+        };
+        // Move a single unit to the part before it, and gather the surplus of
+        // everything after that part in the last part
+        let surplus = self.parts[j] - min - 1;
+        self.parts[j - 1] += 1;
+        self.parts[j] = min;
+        self.parts[K - 1] += surplus;
+    }
+}
+
+/// Return an iterator over the compositions of `n` into `K` positive parts.
+///
+/// The iterator produces a new array per iteration whose elements sum to `n`, in
+/// lexicographic order. If `n` is less than `K` the resulting iterator will yield
+/// no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::compositions;
+///
+/// let mut compositions = compositions::<3>(5);
+/// assert_eq!(compositions.next(), Some([1, 1, 3]));
+/// assert_eq!(compositions.next(), Some([1, 2, 2]));
+/// assert_eq!(compositions.next(), Some([1, 3, 1]));
+/// assert_eq!(compositions.next(), Some([2, 1, 2]));
+/// assert_eq!(compositions.next(), Some([2, 2, 1]));
+/// assert_eq!(compositions.next(), Some([3, 1, 1]));
+/// assert_eq!(compositions.next(), None);
+/// ```
+pub fn compositions<const K: usize>(n: usize) -> Compositions<K> {
+    Compositions::new(n)
+}
+
+/// An iterator that returns the compositions of an integer into k positive parts.
+///
+/// This `struct` is created by the [`compositions`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Compositions<const K: usize> {
+    gen: LazyCompositionGenerator<K>,
+}
+
+impl<const K: usize> Compositions<K> {
+    pub(crate) fn new(n: usize) -> Self {
+        Self {
+            gen: LazyCompositionGenerator::new(n, 1),
+        }
+    }
+}
+
+impl<const K: usize> Iterator for Compositions<K> {
+    type Item = [usize; K];
+
+    fn next(&mut self) -> Option<[usize; K]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.parts();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const K: usize> FusedIterator for Compositions<K> {}
+
+/// Return an iterator over the weak compositions of `n` into `K` parts.
+///
+/// Unlike [`compositions`], parts are allowed to be zero, so this enumerates every
+/// way of distributing `n` identical items over `K` labeled bins. The iterator
+/// produces a new array per iteration whose elements sum to `n`, in lexicographic
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::weak_compositions;
+///
+/// let mut compositions = weak_compositions::<3>(2);
+/// assert_eq!(compositions.next(), Some([0, 0, 2]));
+/// assert_eq!(compositions.next(), Some([0, 1, 1]));
+/// assert_eq!(compositions.next(), Some([0, 2, 0]));
+/// assert_eq!(compositions.next(), Some([1, 0, 1]));
+/// assert_eq!(compositions.next(), Some([1, 1, 0]));
+/// assert_eq!(compositions.next(), Some([2, 0, 0]));
+/// assert_eq!(compositions.next(), None);
+/// ```
+pub fn weak_compositions<const K: usize>(n: usize) -> WeakCompositions<K> {
+    WeakCompositions::new(n)
+}
+
+/// An iterator that returns the weak compositions of an integer into k parts.
+///
+/// This `struct` is created by the [`weak_compositions`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct WeakCompositions<const K: usize> {
+    gen: LazyCompositionGenerator<K>,
+}
+
+impl<const K: usize> WeakCompositions<K> {
+    pub(crate) fn new(n: usize) -> Self {
+        Self {
+            gen: LazyCompositionGenerator::new(n, 0),
+        }
+    }
+}
+
+impl<const K: usize> Iterator for WeakCompositions<K> {
+    type Item = [usize; K];
+
+    fn next(&mut self) -> Option<[usize; K]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.parts();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const K: usize> FusedIterator for WeakCompositions<K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut compositions = compositions::<2>(4);
+        assert_eq!(compositions.next(), Some([1, 3]));
+        assert_eq!(compositions.next(), Some([2, 2]));
+        assert_eq!(compositions.next(), Some([3, 1]));
+        assert_eq!(compositions.next(), None);
+        assert_eq!(compositions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // There are C(n - 1, k - 1) compositions of n into k parts
+        assert_eq!(compositions::<3>(7).count(), 15);
+        assert_eq!(compositions::<4>(4).count(), 1);
+        assert_eq!(compositions::<1>(9).count(), 1);
+    }
+
+    #[test]
+    fn none_on_n_too_small() {
+        let mut compositions = compositions::<3>(2);
+        assert_eq!(compositions.next(), None);
+        assert_eq!(compositions.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_k_zero() {
+        let mut compositions = compositions::<0>(0);
+        assert_eq!(compositions.next(), Some([]));
+        assert_eq!(compositions.next(), None);
+    }
+
+    #[test]
+    fn none_on_k_zero_and_n_positive() {
+        let mut compositions = compositions::<0>(1);
+        assert_eq!(compositions.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod weak_test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut compositions = weak_compositions::<2>(2);
+        assert_eq!(compositions.next(), Some([0, 2]));
+        assert_eq!(compositions.next(), Some([1, 1]));
+        assert_eq!(compositions.next(), Some([2, 0]));
+        assert_eq!(compositions.next(), None);
+        assert_eq!(compositions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // There are C(n + k - 1, k - 1) weak compositions of n into k parts
+        assert_eq!(weak_compositions::<3>(4).count(), 15);
+        assert_eq!(weak_compositions::<4>(0).count(), 1);
+        assert_eq!(weak_compositions::<1>(9).count(), 1);
+    }
+
+    #[test]
+    fn empty_arr_on_k_zero() {
+        let mut compositions = weak_compositions::<0>(0);
+        assert_eq!(compositions.next(), Some([]));
+        assert_eq!(compositions.next(), None);
+    }
+}
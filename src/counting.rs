@@ -0,0 +1,374 @@
+//! Functions for counting combinatorial objects.
+//!
+//! These are `const fn`s, so they can be used to size arrays at compile time.
+//!
+//! # Examples
+//!
+//! ```
+//! use const_combinations::counting::binomial;
+//!
+//! const PAIRS: usize = match binomial(5, 2) {
+//!     Some(n) => n,
+//!     None => panic!(),
+//! };
+//! let table = [[0u8; 2]; PAIRS];
+//! assert_eq!(table.len(), 10);
+//! ```
+
+/// Return the number of ways to choose `k` out of `n` items, or `None` if it
+/// doesn't fit in a `usize`.
+///
+/// This is the number of items produced by the `combinations` iterators.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::binomial;
+///
+/// assert_eq!(binomial(5, 2), Some(10));
+/// assert_eq!(binomial(2, 5), Some(0));
+/// ```
+#[doc(alias = "checked_binomial")]
+pub const fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = if k < n - k { k } else { n - k };
+    let mut res: usize = 1;
+    let mut i = 0;
+    while i < k {
+        // The intermediate product is always divisible by `i + 1`
+        let wide = res as u128 * (n - i) as u128 / (i + 1) as u128;
+        if wide > usize::MAX as u128 {
+            return None;
+        }
+        res = wide as usize;
+        i += 1;
+    }
+    Some(res)
+}
+
+/// Return the number of orderings of `n` items, or `None` if it doesn't fit in
+/// a `usize`.
+///
+/// This is the number of items produced by the `permutations` iterators when
+/// `K` is the number of items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::factorial;
+///
+/// assert_eq!(factorial(5), Some(120));
+/// assert_eq!(factorial(100), None);
+/// ```
+#[doc(alias = "checked_factorial")]
+pub const fn factorial(n: usize) -> Option<usize> {
+    let mut res: usize = 1;
+    let mut i = 2;
+    while i <= n {
+        res = match res.checked_mul(i) {
+            Some(res) => res,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(res)
+}
+
+/// Return the number of ways to choose `k` out of `n` items as a `u128`, or
+/// `None` if it doesn't fit.
+///
+/// This is [`binomial`] with a wider result, for counts which overflow a `usize`,
+/// which happens quickly on 32-bit targets.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::{binomial, binomial_u128};
+///
+/// assert_eq!(binomial(100, 50), None);
+/// assert_eq!(binomial_u128(100, 50), Some(100891344545564193334812497256));
+/// assert_eq!(binomial_u128(200, 100), None);
+/// ```
+pub const fn binomial_u128(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = if k < n - k { k } else { n - k };
+    let mut res: u128 = 1;
+    let mut i = 0;
+    while i < k {
+        // `res * (n - i)` is divisible by `i + 1`, so divide before multiplying
+        // to keep the intermediate value from overflowing
+        let d = (i + 1) as u128;
+        let g = gcd(res, d);
+        let factor = (n - i) as u128 / (d / g);
+        res = match (res / g).checked_mul(factor) {
+            Some(res) => res,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(res)
+}
+
+/// Return the number of orderings of `n` items as a `u128`, or `None` if it
+/// doesn't fit.
+///
+/// This is [`factorial`] with a wider result.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::factorial_u128;
+///
+/// assert_eq!(factorial_u128(30), Some(265252859812191058636308480000000));
+/// assert_eq!(factorial_u128(35), None);
+/// ```
+pub const fn factorial_u128(n: usize) -> Option<u128> {
+    let mut res: u128 = 1;
+    let mut i = 2;
+    while i <= n {
+        res = match res.checked_mul(i as u128) {
+            Some(res) => res,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(res)
+}
+
+/// Return the number of ways to partition `n` items into `k` non-empty blocks,
+/// or `None` if it doesn't fit in a `usize`.
+///
+/// This is the Stirling number of the second kind, and the number of items
+/// produced by [`set_partitions_into`].
+///
+/// [`set_partitions_into`]: crate::set_partitions_into
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::stirling2;
+///
+/// assert_eq!(stirling2(4, 2), Some(7));
+/// assert_eq!(stirling2(2, 4), Some(0));
+/// assert_eq!(stirling2(100, 2), None);
+/// ```
+#[doc(alias = "stirling")]
+pub const fn stirling2(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    if k == 0 {
+        return Some((n == 0) as usize);
+    }
+    let d = n - k;
+    match (k, d) {
+        (1, _) | (_, 0) => return Some(1),
+        (_, 1) => return binomial(n, 2),
+        // There are at least `2^d` ways to spread `d` of the items over the
+        // first two blocks
+        _ if d >= 128 => return None,
+        _ => {}
+    }
+    // `row[e]` is `stirling2(j + e, j)`, for `j` going from `1` up to `k`. The
+    // entries only grow with `j`, so overflowing on the way means the result
+    // overflows too.
+    let mut row = [1usize; 128];
+    let mut j = 2;
+    while j <= k {
+        let mut e = 1;
+        while e <= d {
+            row[e] = match row[e - 1].checked_mul(j) {
+                Some(prod) => match prod.checked_add(row[e]) {
+                    Some(sum) => sum,
+                    None => return None,
+                },
+                None => return None,
+            };
+            e += 1;
+        }
+        j += 1;
+    }
+    Some(row[d])
+}
+
+/// Return the number of ways to partition `n` items into non-empty blocks, or
+/// `None` if it doesn't fit in a `usize`.
+///
+/// This is the Bell number, and the number of items produced by
+/// [`set_partitions`].
+///
+/// [`set_partitions`]: crate::set_partitions
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::bell;
+///
+/// assert_eq!(bell(0), Some(1));
+/// assert_eq!(bell(5), Some(52));
+/// assert_eq!(bell(30), None);
+/// ```
+pub const fn bell(n: usize) -> Option<usize> {
+    let mut res: usize = 0;
+    let mut k = 0;
+    while k <= n {
+        res = match stirling2(n, k) {
+            Some(s) => match res.checked_add(s) {
+                Some(res) => res,
+                None => return None,
+            },
+            None => return None,
+        };
+        k += 1;
+    }
+    Some(res)
+}
+
+/// Return the `n`th Catalan number, or `None` if it doesn't fit in a `usize`.
+///
+/// This is the number of items produced by [`dyck_words`] of length `2 * n`,
+/// and by [`noncrossing_partitions`] of `n` points.
+///
+/// [`dyck_words`]: crate::dyck_words
+/// [`noncrossing_partitions`]: crate::noncrossing_partitions
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::counting::catalan;
+///
+/// assert_eq!(catalan(3), Some(5));
+/// assert_eq!(catalan(35), Some(3116285494907301262));
+/// assert_eq!(catalan(40), None);
+/// ```
+pub const fn catalan(n: usize) -> Option<usize> {
+    let twice = match n.checked_mul(2) {
+        Some(twice) => twice,
+        None => return None,
+    };
+    match binomial_u128(twice, n) {
+        // `binomial(2n, n)` is always divisible by `n + 1`
+        Some(b) if b / (n as u128 + 1) <= usize::MAX as u128 => {
+            Some((b / (n as u128 + 1)) as usize)
+        }
+        _ => None,
+    }
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Turn a possibly uncountable number of remaining items into a size hint.
+pub(crate) fn size_hint(remaining: Option<usize>) -> (usize, Option<usize>) {
+    match remaining {
+        Some(n) => (n, Some(n)),
+        None => (usize::MAX, None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn binomials() {
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(2, 3), Some(0));
+        assert_eq!(binomial(64, 32), Some(1832624140942590534));
+        assert_eq!(binomial(usize::MAX, 1), Some(usize::MAX));
+        assert_eq!(binomial(usize::MAX, usize::MAX - 1), Some(usize::MAX));
+        assert_eq!(binomial(usize::MAX, 2), None);
+    }
+
+    #[test]
+    fn factorials() {
+        assert_eq!(factorial(0), Some(1));
+        assert_eq!(factorial(5), Some(120));
+        assert_eq!(factorial(20), Some(2432902008176640000));
+        assert_eq!(factorial(21), None);
+    }
+
+    #[test]
+    fn wide() {
+        for n in 0..70 {
+            for k in 0..=n + 1 {
+                match binomial(n, k) {
+                    Some(b) => assert_eq!(binomial_u128(n, k), Some(b as u128)),
+                    None => assert!(binomial_u128(n, k).unwrap() > usize::MAX as u128),
+                }
+            }
+        }
+        // Pascal's rule holds all the way to the limit of a u128
+        for k in 1..64 {
+            assert_eq!(
+                binomial_u128(128, k),
+                Some(binomial_u128(127, k - 1).unwrap() + binomial_u128(127, k).unwrap())
+            );
+        }
+        assert_eq!(binomial_u128(usize::MAX, 1), Some(usize::MAX as u128));
+        assert!(binomial_u128(131, 65).is_some());
+        assert_eq!(binomial_u128(132, 66), None);
+        assert_eq!(
+            factorial_u128(34),
+            Some(295232799039604140847618609643520000000)
+        );
+        assert_eq!(factorial_u128(20), factorial(20).map(|f| f as u128));
+    }
+
+    #[test]
+    fn stirling_numbers() {
+        assert_eq!(stirling2(0, 0), Some(1));
+        assert_eq!(stirling2(5, 0), Some(0));
+        assert_eq!(stirling2(7, 3), Some(301));
+        assert_eq!(stirling2(10, 5), Some(42525));
+        assert_eq!(stirling2(65, 2), Some(usize::MAX));
+        assert_eq!(stirling2(66, 2), None);
+        assert_eq!(stirling2(usize::MAX, usize::MAX), Some(1));
+        assert_eq!(stirling2(usize::MAX, 1), Some(1));
+        assert_eq!(stirling2(usize::MAX, usize::MAX - 2), None);
+        // The recurrence holds wherever the numbers fit
+        for n in 1..30 {
+            for k in 1..=n {
+                if let (Some(a), Some(b), Some(c)) = (
+                    stirling2(n, k),
+                    stirling2(n - 1, k),
+                    stirling2(n - 1, k - 1),
+                ) {
+                    assert_eq!(a, k * b + c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bell_numbers() {
+        // https://oeis.org/A000110
+        assert_eq!(bell(1), Some(1));
+        assert_eq!(bell(7), Some(877));
+        assert_eq!(bell(25), Some(4638590332229999353));
+        assert_eq!(bell(26), None);
+        assert_eq!(bell(usize::MAX), None);
+    }
+
+    #[test]
+    fn catalan_numbers() {
+        // https://oeis.org/A000108
+        assert_eq!(catalan(0), Some(1));
+        assert_eq!(catalan(4), Some(14));
+        assert_eq!(catalan(8), Some(1430));
+        assert_eq!(catalan(37), None);
+        assert_eq!(catalan(usize::MAX), None);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::SliceCombinations;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over every way of choosing `K` elements from `a` together
+/// with `M` elements from `b`.
+///
+/// The iterator produces a pair of arrays per iteration, and returns references to
+/// the elements of the slices. The combinations of `b` vary fastest. If `K` is
+/// greater than the length of `a`, or `M` is greater than the length of `b`, the
+/// resulting iterator will yield no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::cross_combinations;
+///
+/// let mut teams = cross_combinations::<_, _, 2, 1>(&[1, 2, 3], &['a', 'b']);
+/// assert_eq!(teams.next(), Some(([&1, &2], [&'a'])));
+/// assert_eq!(teams.next(), Some(([&1, &2], [&'b'])));
+/// assert_eq!(teams.next(), Some(([&1, &3], [&'a'])));
+/// assert_eq!(teams.next(), Some(([&1, &3], [&'b'])));
+/// assert_eq!(teams.next(), Some(([&2, &3], [&'a'])));
+/// assert_eq!(teams.next(), Some(([&2, &3], [&'b'])));
+/// assert_eq!(teams.next(), None);
+/// ```
+pub fn cross_combinations<'a, 'b, A, B, const K: usize, const M: usize>(
+    a: &'a [A],
+    b: &'b [B],
+) -> CrossCombinations<'a, 'b, A, B, K, M> {
+    CrossCombinations::new(a, b)
+}
+
+/// An iterator that returns k-length combinations of values from one slice paired
+/// with m-length combinations of values from another.
+///
+/// This `struct` is created by the [`cross_combinations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CrossCombinations<'a, 'b, A, B, const K: usize, const M: usize> {
+    left: SliceCombinations<'a, A, K>,
+    current: Option<[&'a A; K]>,
+    right: SliceCombinations<'b, B, M>,
+    b: &'b [B],
+}
+
+impl<'a, 'b, A, B, const K: usize, const M: usize> CrossCombinations<'a, 'b, A, B, K, M> {
+    pub(crate) fn new(a: &'a [A], b: &'b [B]) -> Self {
+        let mut left = SliceCombinations::new(a);
+        // Don't walk the left side at all if the right side is empty
+        let current = match SliceCombinations::<B, M>::new(b).next() {
+            Some(_) => left.next(),
+            None => None,
+        };
+        Self {
+            left,
+            current,
+            right: SliceCombinations::new(b),
+            b,
+        }
+    }
+}
+
+impl<'a, 'b, A, B, const K: usize, const M: usize> Iterator
+    for CrossCombinations<'a, 'b, A, B, K, M>
+{
+    type Item = ([&'a A; K], [&'b B; M]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = self.current?;
+            match self.right.next() {
+                Some(right) => return Some((current, right)),
+                None => {
+                    // Move to the next left combination and restart the right side
+                    self.current = self.left.next();
+                    self.right = SliceCombinations::new(self.b);
+                }
+            }
+        }
+    }
+}
+
+impl<A, B, const K: usize, const M: usize> FusedIterator for CrossCombinations<'_, '_, A, B, K, M> {}
+
+#[cfg(test)]
+mod test {
+    use super::cross_combinations;
+
+    #[test]
+    fn order() {
+        let mut teams = cross_combinations::<_, _, 1, 2>(&[1, 2], &[3, 4, 5]);
+        assert_eq!(teams.next(), Some(([&1], [&3, &4])));
+        assert_eq!(teams.next(), Some(([&1], [&3, &5])));
+        assert_eq!(teams.next(), Some(([&1], [&4, &5])));
+        assert_eq!(teams.next(), Some(([&2], [&3, &4])));
+        assert_eq!(teams.next(), Some(([&2], [&3, &5])));
+        assert_eq!(teams.next(), Some(([&2], [&4, &5])));
+        assert_eq!(teams.next(), None);
+        assert_eq!(teams.next(), None);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut teams = cross_combinations::<_, _, 3, 1>(&[1, 2], &[3, 4, 5]);
+        assert_eq!(teams.next(), None);
+        let mut teams = cross_combinations::<_, _, 1, 4>(&[1, 2], &[3, 4, 5]);
+        assert_eq!(teams.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_zero() {
+        let mut teams = cross_combinations::<_, _, 0, 0>(&[1, 2], &[3, 4, 5]);
+        assert_eq!(teams.next(), Some(([], [])));
+        assert_eq!(teams.next(), None);
+    }
+}
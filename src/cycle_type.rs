@@ -0,0 +1,271 @@
+use crate::combinations::LazyLexCombinationGenerator;
+use crate::perm::Perm;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+struct Cycle {
+    // Index into the distinct lengths of the length of this cycle
+    length: usize,
+    // Which of the free positions after the first one are in this cycle
+    support: LazyLexCombinationGenerator,
+    // The order those positions are visited in after the first one
+    order: Vec<usize>,
+}
+
+impl Cycle {
+    fn new(length: usize, len: usize) -> Self {
+        Self {
+            length,
+            support: LazyLexCombinationGenerator::new(len - 1),
+            order: (0..len - 1).collect(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LazyCycleTypeGenerator<const N: usize> {
+    // The distinct cycle lengths from longest to shortest
+    lengths: Vec<usize>,
+    // How many cycles of each length are still to be placed
+    counts: Vec<usize>,
+    cycles: Vec<Cycle>,
+    indices: [usize; N],
+    done: bool,
+}
+
+impl<const N: usize> LazyCycleTypeGenerator<N> {
+    pub fn new(mut cycle_type: Vec<usize>) -> Self {
+        cycle_type.sort_unstable_by(|a, b| b.cmp(a));
+        let mut lengths = cycle_type.clone();
+        lengths.dedup();
+        let counts = lengths
+            .iter()
+            .map(|&len| cycle_type.iter().filter(|&&l| l == len).count())
+            .collect();
+        let mut gen = Self {
+            lengths,
+            counts,
+            cycles: Vec::new(),
+            indices: [0; N],
+            done: false,
+        };
+        gen.fill();
+        gen
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn indices(&self) -> &[usize; N] {
+        &self.indices
+    }
+
+    /// Place all the remaining cycles with their first choices, and write out
+    /// the resulting permutation.
+    fn fill(&mut self) {
+        while let Some(length) = self.counts.iter().position(|&count| count > 0) {
+            self.counts[length] -= 1;
+            self.cycles.push(Cycle::new(length, self.lengths[length]));
+        }
+        let mut used = [false; N];
+        for cycle in &self.cycles {
+            // Every cycle starts at the smallest position not in a cycle yet,
+            // and takes its other positions from the free ones after it
+            let first = used.iter().position(|&u| !u).unwrap();
+            let free: Vec<usize> = (first + 1..N).filter(|&i| !used[i]).collect();
+            let mut prev = first;
+            used[first] = true;
+            for &o in &cycle.order {
+                let next = free[cycle.support.indices()[o]];
+                self.indices[prev] = next;
+                used[next] = true;
+                prev = next;
+            }
+            self.indices[prev] = first;
+        }
+    }
+
+    pub fn step(&mut self) {
+        let mut placed: usize = self.cycles.iter().map(|c| self.lengths[c.length]).sum();
+        while let Some(mut cycle) = self.cycles.pop() {
+            let len = self.lengths[cycle.length];
+            placed -= len;
+            let free = N - placed - 1;
+            if next_permutation(&mut cycle.order) {
+                self.cycles.push(cycle);
+                self.fill();
+                return;
+            }
+            cycle.support.step(free);
+            if !cycle.support.is_done(free) {
+                cycle.order = (0..len - 1).collect();
+                self.cycles.push(cycle);
+                self.fill();
+                return;
+            }
+            // Try a shorter cycle for this position instead
+            self.counts[cycle.length] += 1;
+            let shorter = (cycle.length + 1..self.lengths.len()).find(|&j| self.counts[j] > 0);
+            if let Some(length) = shorter {
+                self.counts[length] -= 1;
+                self.cycles.push(Cycle::new(length, self.lengths[length]));
+                self.fill();
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+/// Rearrange `order` into the next permutation in lexicographic order,
+/// returning `false` if it was the last one.
+fn next_permutation(order: &mut [usize]) -> bool {
+    let i = match order.windows(2).rposition(|w| w[0] < w[1]) {
+        Some(i) => i,
+        None => return false,
+    };
+    let j = order.iter().rposition(|&x| x > order[i]).unwrap();
+    order.swap(i, j);
+    order[i + 1..].reverse();
+    true
+}
+
+/// Return an iterator over the permutations of `N` positions with the given
+/// cycle type.
+///
+/// The cycle type lists the length of every cycle of the permutation, in any
+/// order, with fixed points as cycles of length one; see [`Perm::cycle_type`].
+/// Permutations are put together one cycle at a time: the smallest position
+/// which isn't in a cycle yet starts the next one, a length is picked for it
+/// from the ones still to be placed, and its other positions are picked as a
+/// combination of the free positions, which are then arranged in every order
+/// around the cycle. Lengths are tried from longest to shortest, and the
+/// combinations and arrangements in lexicographic order, so no permutation
+/// comes up twice and none with a different cycle type is ever generated.
+///
+/// Returns `None` if a length is zero or the lengths don't add up to `N`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::cycle_type_permutations;
+///
+/// // A single 3-cycle and the rest fixed
+/// let mut permutations = cycle_type_permutations::<4>(&[3, 1]).unwrap();
+/// assert_eq!(permutations.next().unwrap().as_array(), &[1, 2, 0, 3]);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[2, 0, 1, 3]);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[1, 3, 2, 0]);
+/// assert_eq!(permutations.count(), 5);
+///
+/// assert!(cycle_type_permutations::<4>(&[3, 2]).is_none());
+/// ```
+pub fn cycle_type_permutations<const N: usize>(
+    cycle_type: &[usize],
+) -> Option<CycleTypePermutations<N>> {
+    let size = cycle_type
+        .iter()
+        .try_fold(0usize, |acc, &len| acc.checked_add(len));
+    if cycle_type.contains(&0) || size != Some(N) {
+        None
+    } else {
+        Some(CycleTypePermutations::new(cycle_type))
+    }
+}
+
+/// An iterator that returns the permutations with a given cycle type.
+///
+/// This `struct` is created by the [`cycle_type_permutations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CycleTypePermutations<const N: usize> {
+    gen: LazyCycleTypeGenerator<N>,
+}
+
+impl<const N: usize> CycleTypePermutations<N> {
+    pub(crate) fn new(cycle_type: &[usize]) -> Self {
+        Self {
+            gen: LazyCycleTypeGenerator::new(cycle_type.to_vec()),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for CycleTypePermutations<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = Perm::new_unchecked(*self.gen.indices());
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for CycleTypePermutations<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::counting::factorial;
+    use crate::make_array;
+    use crate::SliceExt;
+    use alloc::vec;
+
+    #[test]
+    fn count() {
+        // `N!` divided by `l^m * m!` for every length `l` occurring `m` times
+        assert_eq!(
+            cycle_type_permutations::<5>(&[3, 1, 1]).unwrap().count(),
+            20
+        );
+        assert_eq!(cycle_type_permutations::<4>(&[2, 2]).unwrap().count(), 3);
+        assert_eq!(
+            cycle_type_permutations::<6>(&[2, 2, 2]).unwrap().count(),
+            15
+        );
+        assert_eq!(
+            cycle_type_permutations::<7>(&[7]).unwrap().count(),
+            factorial(6).unwrap()
+        );
+        assert_eq!(cycle_type_permutations::<8>(&[1; 8]).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let indices: [usize; 6] = make_array(|i| i);
+        let all: Vec<Perm<6>> = indices
+            .permutations::<6>()
+            .map(|p| Perm::new(make_array(|i| *p[i])).unwrap())
+            .collect();
+        for cycle_type in [vec![6], vec![4, 2], vec![2, 2, 1, 1], vec![3, 2, 1]] {
+            let mut expected: Vec<_> = all
+                .iter()
+                .copied()
+                .filter(|p| p.cycle_type() == cycle_type)
+                .collect();
+            let mut found: Vec<_> = cycle_type_permutations::<6>(&cycle_type).unwrap().collect();
+            expected.sort_unstable();
+            found.sort_unstable();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn invalid_cycle_types() {
+        assert!(cycle_type_permutations::<3>(&[2]).is_none());
+        assert!(cycle_type_permutations::<3>(&[3, 0]).is_none());
+        assert!(cycle_type_permutations::<3>(&[usize::MAX, 4]).is_none());
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = cycle_type_permutations::<0>(&[]).unwrap();
+        assert_eq!(permutations.next().map(|p| *p.as_array()), Some([]));
+        assert!(permutations.next().is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyDyckGenerator<const N: usize> {
+    // `true` is an opening parenthesis, `false` a closing one
+    word: [bool; N],
+    done: bool,
+}
+
+impl<const N: usize> LazyDyckGenerator<N> {
+    pub fn new() -> Self {
+        let mut word = [false; N];
+        word[..N / 2].fill(true);
+        Self {
+            word,
+            done: !N.is_multiple_of(2),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn word(&self) -> &[bool; N] {
+        &self.word
+    }
+
+    pub fn step(&mut self) {
+        // Balance of the word up to (but not including) position `i`
+        let mut balances = [0; N];
+        let mut balance = 0;
+        for (i, &open) in self.word.iter().enumerate() {
+            balances[i] = balance;
+            balance = if open { balance + 1 } else { balance - 1 };
+        }
+        // Find the last opening parenthesis which can be closed instead while
+        // still leaving room to close everything afterwards
+        let mut i = N;
+        while i > 0 {
+            i -= 1;
+            if !self.word[i] || balances[i] == 0 {
+                continue;
+            }
+            let open = balances[i] - 1;
+            let rest = N - i - 1;
+            if rest < open {
+                continue;
+            }
+            // Complete the word with the smallest possible suffix
+            let opens = (rest - open) / 2;
+            self.word[i] = false;
+            self.word[i + 1..i + 1 + opens].fill(true);
+            self.word[i + 1 + opens..].fill(false);
+            return;
+        }
+        self.done = true;
+    }
+}
+
+/// Return an iterator over the Dyck words of length `N`, which are the balanced
+/// sequences of `N / 2` pairs of parentheses.
+///
+/// The iterator produces a new array per iteration in which `true` is an opening
+/// parenthesis and `false` a closing one. Words are produced in lexicographic
+/// order with opening parentheses sorting first; there are as many of them as the
+/// Catalan number of `N / 2`. If `N` is odd the resulting iterator will yield no
+/// items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::dyck_words;
+///
+/// let mut words = dyck_words::<6>();
+/// assert_eq!(words.next(), Some([true, true, true, false, false, false])); // ((()))
+/// assert_eq!(words.next(), Some([true, true, false, true, false, false])); // (()())
+/// assert_eq!(words.next(), Some([true, true, false, false, true, false])); // (())()
+/// assert_eq!(words.next(), Some([true, false, true, true, false, false])); // ()(())
+/// assert_eq!(words.next(), Some([true, false, true, false, true, false])); // ()()()
+/// assert_eq!(words.next(), None);
+/// ```
+pub fn dyck_words<const N: usize>() -> DyckWords<N> {
+    DyckWords::new()
+}
+
+/// An iterator that returns the balanced parenthesis sequences of a given length.
+///
+/// This `struct` is created by the [`dyck_words`] function. See its documentation
+/// for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct DyckWords<const N: usize> {
+    gen: LazyDyckGenerator<N>,
+}
+
+impl<const N: usize> DyckWords<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyDyckGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for DyckWords<N> {
+    type Item = [bool; N];
+
+    fn next(&mut self) -> Option<[bool; N]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.word();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for DyckWords<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut words = dyck_words::<4>();
+        assert_eq!(words.next(), Some([true, true, false, false]));
+        assert_eq!(words.next(), Some([true, false, true, false]));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000108
+        assert_eq!(dyck_words::<2>().count(), 1);
+        assert_eq!(dyck_words::<8>().count(), 14);
+        assert_eq!(dyck_words::<12>().count(), 132);
+        assert_eq!(dyck_words::<16>().count(), 1430);
+    }
+
+    #[test]
+    fn balanced() {
+        for word in dyck_words::<10>() {
+            let mut balance = 0i32;
+            for open in word {
+                balance += if open { 1 } else { -1 };
+                assert!(balance >= 0);
+            }
+            assert_eq!(balance, 0);
+        }
+    }
+
+    #[test]
+    fn none_on_odd_length() {
+        let mut words = dyck_words::<5>();
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut words = dyck_words::<0>();
+        assert_eq!(words.next(), Some([]));
+        assert_eq!(words.next(), None);
+    }
+}
@@ -0,0 +1,38 @@
+use core::fmt;
+
+/// The error returned when asking for arrays longer than the input.
+///
+/// This is returned by the checked constructors like [`try_combinations`], which
+/// would otherwise create an iterator that yields no items.
+///
+/// [`try_combinations`]: crate::SliceExt::try_combinations
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KTooLarge {
+    /// The requested length of the arrays.
+    pub k: usize,
+    /// The length of the input.
+    pub len: usize,
+}
+
+impl fmt::Display for KTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot choose {} elements out of {}", self.k, self.len)
+    }
+}
+
+impl core::error::Error for KTooLarge {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use alloc::string::ToString;
+
+    #[test]
+    fn checked_constructors() {
+        assert!([1, 2].try_combinations::<2>().is_ok());
+        assert!([1, 2].try_permutations::<0>().is_ok());
+        let err = [1, 2].try_permutations::<3>().err().unwrap();
+        assert_eq!((err.k, err.len), (3, 2));
+        assert_eq!(err.to_string(), "cannot choose 3 elements out of 2");
+    }
+}
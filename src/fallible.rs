@@ -0,0 +1,251 @@
+//! Combinations and permutations of iterators whose elements may be errors.
+
+use crate::{combinations, permutations};
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+mod sealed {
+    /// Iterator items which are either an element or an error.
+    pub trait Fallible {
+        type Ok;
+        type Error;
+
+        fn into_result(self) -> Result<Self::Ok, Self::Error>;
+    }
+}
+
+pub(crate) use sealed::Fallible;
+
+impl<T, E> Fallible for Result<T, E> {
+    type Ok = T;
+    type Error = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+type Element<I> = <<I as Iterator>::Item as Fallible>::Ok;
+type Error<I> = <<I as Iterator>::Item as Fallible>::Error;
+
+/// Read elements from `iter` until `items` has more than `max_index` of them,
+/// or the first error.
+fn fill<I>(
+    iter: &mut I,
+    items: &mut Vec<Element<I>>,
+    max_index: Option<usize>,
+) -> Result<(), Error<I>>
+where
+    I: Iterator,
+    I::Item: Fallible,
+{
+    if let Some(max_index) = max_index {
+        while items.len() <= max_index {
+            match iter.next() {
+                Some(item) => items.push(item.into_result()?),
+                None => break,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An iterator that returns k-length combinations of the elements from an
+/// iterator of results, stopping at the first error.
+///
+/// This `struct` is created by the [`try_combinations`] method on [`IterExt`].
+/// See its documentation for more.
+///
+/// [`try_combinations`]: crate::IterExt::try_combinations
+/// [`IterExt`]: crate::IterExt
+#[must_use = "iterators do nothing unless consumed"]
+pub struct TryCombinations<I, const K: usize>
+where
+    I: Iterator,
+    I::Item: Fallible,
+{
+    iter: I,
+    items: Vec<Element<I>>,
+    state: combinations::State<K>,
+    failed: bool,
+}
+
+impl<I, const K: usize> TryCombinations<I, K>
+where
+    I: Iterator,
+    I::Item: Fallible,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            items: Vec::new(),
+            state: combinations::State::new(),
+            failed: false,
+        }
+    }
+}
+
+// A derived impl wouldn't require the buffered elements to be `Clone`
+impl<I, const K: usize> Clone for TryCombinations<I, K>
+where
+    I: Iterator + Clone,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            items: self.items.clone(),
+            state: self.state.clone(),
+            failed: self.failed,
+        }
+    }
+}
+
+impl<I, const K: usize> Iterator for TryCombinations<I, K>
+where
+    I: Iterator,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+    type Item = Result<[Element<I>; K], Error<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        if let Err(err) = fill(&mut self.iter, &mut self.items, self.state.max_index()) {
+            self.failed = true;
+            return Some(Err(err));
+        }
+        self.state.get_and_step(&self.items, Clone::clone).map(Ok)
+    }
+}
+
+impl<I, const K: usize> FusedIterator for TryCombinations<I, K>
+where
+    I: FusedIterator,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+}
+
+/// An iterator that returns k-length permutations of the elements from an
+/// iterator of results, stopping at the first error.
+///
+/// This `struct` is created by the [`try_permutations`] method on [`IterExt`].
+/// See its documentation for more.
+///
+/// [`try_permutations`]: crate::IterExt::try_permutations
+/// [`IterExt`]: crate::IterExt
+#[must_use = "iterators do nothing unless consumed"]
+pub struct TryPermutations<I, const K: usize>
+where
+    I: Iterator,
+    I::Item: Fallible,
+{
+    iter: I,
+    items: Vec<Element<I>>,
+    state: permutations::State<K>,
+    failed: bool,
+}
+
+impl<I, const K: usize> TryPermutations<I, K>
+where
+    I: Iterator,
+    I::Item: Fallible,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            items: Vec::new(),
+            state: permutations::State::new(),
+            failed: false,
+        }
+    }
+}
+
+// A derived impl wouldn't require the buffered elements to be `Clone`
+impl<I, const K: usize> Clone for TryPermutations<I, K>
+where
+    I: Iterator + Clone,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            items: self.items.clone(),
+            state: self.state.clone(),
+            failed: self.failed,
+        }
+    }
+}
+
+impl<I, const K: usize> Iterator for TryPermutations<I, K>
+where
+    I: Iterator,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+    type Item = Result<[Element<I>; K], Error<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        if let Err(err) = fill(&mut self.iter, &mut self.items, self.state.max_index()) {
+            self.failed = true;
+            return Some(Err(err));
+        }
+        self.state.get_and_step(&self.items, Clone::clone).map(Ok)
+    }
+}
+
+impl<I, const K: usize> FusedIterator for TryPermutations<I, K>
+where
+    I: FusedIterator,
+    I::Item: Fallible,
+    Element<I>: Clone,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn all_ok() {
+        let all: Vec<_> = (0..6).map(Ok::<_, ()>).try_combinations::<3>().collect();
+        assert!(all.into_iter().eq((0..6).combinations::<3>().map(Ok)));
+        let all: Vec<_> = (0..4).map(Ok::<_, ()>).try_permutations::<2>().collect();
+        assert!(all.into_iter().eq((0..4).permutations::<2>().map(Ok)));
+    }
+
+    #[test]
+    fn stops_at_error() {
+        let items = vec![Ok(1), Ok(2), Ok(3), Err("bad"), Ok(5)];
+        let mut combinations = items.clone().into_iter().try_combinations::<2>();
+        assert_eq!(combinations.next(), Some(Ok([1, 2])));
+        assert_eq!(combinations.next(), Some(Ok([1, 3])));
+        assert_eq!(combinations.next(), Some(Ok([2, 3])));
+        assert_eq!(combinations.next(), Some(Err("bad")));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+        let all: Result<Vec<_>, _> = items.into_iter().try_permutations::<3>().collect();
+        assert_eq!(all, Err("bad"));
+    }
+
+    #[test]
+    fn reads_lazily() {
+        // The error is never reached, since no combination needs the element
+        let items = vec![Ok(1), Ok(2), Err(())];
+        let mut combinations = items.into_iter().try_combinations::<2>();
+        assert_eq!(combinations.next(), Some(Ok([1, 2])));
+        let mut empty = vec![Err::<u8, _>(())].into_iter().try_permutations::<0>();
+        assert_eq!(empty.next(), Some(Ok([])));
+        assert_eq!(empty.next(), None);
+    }
+}
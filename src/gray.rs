@@ -0,0 +1,204 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyGrayCodeGenerator<const N: usize> {
+    word: [bool; N],
+    odd: bool,
+    done: bool,
+}
+
+impl<const N: usize> LazyGrayCodeGenerator<N> {
+    pub fn new() -> Self {
+        Self {
+            word: [false; N],
+            odd: false,
+            done: false,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn word(&self) -> &[bool; N] {
+        &self.word
+    }
+
+    /// Flip a single position, returning it.
+    pub fn step(&mut self) -> Option<usize> {
+        // With an even number of set positions flip the first one, otherwise
+        // flip the one right after the first set position
+        let i = if self.odd {
+            self.word.iter().position(|&bit| bit).unwrap() + 1
+        } else {
+            0
+        };
+        if i < N {
+            self.word[i] = !self.word[i];
+            self.odd = !self.odd;
+            Some(i)
+        } else {
+            self.done = true;
+            None
+        }
+    }
+}
+
+/// Return an iterator over all `N`-bit words in binary reflected Gray code order.
+///
+/// The iterator produces a new `[bool; N]` array per iteration, starting from all
+/// `false`, and consecutive words differ in exactly one position. Position `0`
+/// corresponds to the lowest bit, which flips most often. All `2^N` words are
+/// produced.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::gray_codes;
+///
+/// let mut words = gray_codes::<2>();
+/// assert_eq!(words.next(), Some([false, false]));
+/// assert_eq!(words.next(), Some([true, false]));
+/// assert_eq!(words.next(), Some([true, true]));
+/// assert_eq!(words.next(), Some([false, true]));
+/// assert_eq!(words.next(), None);
+/// ```
+pub fn gray_codes<const N: usize>() -> GrayCodes<N> {
+    GrayCodes::new()
+}
+
+/// An iterator that returns the words of the binary reflected Gray code.
+///
+/// This `struct` is created by the [`gray_codes`] function. See its documentation
+/// for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct GrayCodes<const N: usize> {
+    gen: LazyGrayCodeGenerator<N>,
+}
+
+impl<const N: usize> GrayCodes<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyGrayCodeGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for GrayCodes<N> {
+    type Item = [bool; N];
+
+    fn next(&mut self) -> Option<[bool; N]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.word();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for GrayCodes<N> {}
+
+/// Return an iterator over the positions flipped when walking the `N`-bit binary
+/// reflected Gray code.
+///
+/// Starting from all `false`, flipping each yielded position in turn visits the
+/// same words as [`gray_codes`], which allows evaluating every subset
+/// incrementally. `2^N - 1` positions are produced.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::gray_code_deltas;
+///
+/// let deltas: Vec<_> = gray_code_deltas::<3>().collect();
+/// assert_eq!(deltas, [0, 1, 0, 2, 0, 1, 0]);
+/// ```
+pub fn gray_code_deltas<const N: usize>() -> GrayCodeDeltas<N> {
+    GrayCodeDeltas::new()
+}
+
+/// An iterator that returns the positions flipped by the binary reflected Gray
+/// code.
+///
+/// This `struct` is created by the [`gray_code_deltas`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct GrayCodeDeltas<const N: usize> {
+    gen: LazyGrayCodeGenerator<N>,
+}
+
+impl<const N: usize> GrayCodeDeltas<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyGrayCodeGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for GrayCodeDeltas<N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.gen.is_done() {
+            None
+        } else {
+            self.gen.step()
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for GrayCodeDeltas<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut words = gray_codes::<3>();
+        assert_eq!(words.next(), Some([false, false, false]));
+        assert_eq!(words.next(), Some([true, false, false]));
+        assert_eq!(words.next(), Some([true, true, false]));
+        assert_eq!(words.next(), Some([false, true, false]));
+        assert_eq!(words.next(), Some([false, true, true]));
+        assert_eq!(words.next(), Some([true, true, true]));
+        assert_eq!(words.next(), Some([true, false, true]));
+        assert_eq!(words.next(), Some([false, false, true]));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn matches_binary_reflection() {
+        for (i, word) in gray_codes::<6>().enumerate() {
+            let gray = i ^ (i >> 1);
+            for (bit, &set) in word.iter().enumerate() {
+                assert_eq!(set, gray & 1 << bit != 0);
+            }
+        }
+    }
+
+    #[test]
+    fn deltas_replay_words() {
+        let mut word = [false; 5];
+        let mut words = gray_codes::<5>();
+        assert_eq!(words.next(), Some(word));
+        for i in gray_code_deltas::<5>() {
+            word[i] = !word[i];
+            assert_eq!(words.next(), Some(word));
+        }
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut words = gray_codes::<0>();
+        assert_eq!(words.next(), Some([]));
+        assert_eq!(words.next(), None);
+        assert_eq!(gray_code_deltas::<0>().next(), None);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::combinations::LazyLexCombinationGenerator;
+use crate::make_array;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over the arrays which differ from `template` in at most
+/// `distance` positions, using values from `alphabet` as substitutes.
+///
+/// The iterator produces a new array per iteration, starting with `template`
+/// itself. Arrays are ordered by the number of substituted positions, then by
+/// the positions which are substituted, and then by the substitutes taken from
+/// `alphabet`. Values in `alphabet` which are equal to the template value at a
+/// position are skipped there, so every array is produced once as long as
+/// `alphabet` contains no duplicates.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::hamming_ball;
+///
+/// let mut ball = hamming_ball(['a', 'b'], &['a', 'b', 'c'], 1);
+/// assert_eq!(ball.next(), Some(['a', 'b']));
+/// assert_eq!(ball.next(), Some(['b', 'b']));
+/// assert_eq!(ball.next(), Some(['c', 'b']));
+/// assert_eq!(ball.next(), Some(['a', 'a']));
+/// assert_eq!(ball.next(), Some(['a', 'c']));
+/// assert_eq!(ball.next(), None);
+/// ```
+pub fn hamming_ball<T, const N: usize>(
+    template: [T; N],
+    alphabet: &[T],
+    distance: usize,
+) -> Hamming<'_, T, N>
+where
+    T: Clone + PartialEq,
+{
+    Hamming::new(template, alphabet, 0, distance)
+}
+
+/// Return an iterator over the arrays which differ from `template` in exactly
+/// `distance` positions, using values from `alphabet` as substitutes.
+///
+/// This yields the arrays produced by [`hamming_ball`] which are at exactly
+/// `distance` from `template`, in the same order.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::hamming_sphere;
+///
+/// let mut sphere = hamming_sphere([0, 0, 0], &[0, 1], 2);
+/// assert_eq!(sphere.next(), Some([1, 1, 0]));
+/// assert_eq!(sphere.next(), Some([1, 0, 1]));
+/// assert_eq!(sphere.next(), Some([0, 1, 1]));
+/// assert_eq!(sphere.next(), None);
+/// ```
+pub fn hamming_sphere<T, const N: usize>(
+    template: [T; N],
+    alphabet: &[T],
+    distance: usize,
+) -> Hamming<'_, T, N>
+where
+    T: Clone + PartialEq,
+{
+    Hamming::new(template, alphabet, distance, distance)
+}
+
+/// An iterator that returns the arrays within a Hamming distance of a template.
+///
+/// This `struct` is created by the [`hamming_ball`] and [`hamming_sphere`]
+/// functions. See their documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Hamming<'a, T, const N: usize> {
+    template: [T; N],
+    alphabet: &'a [T],
+    substitutes: [Vec<usize>; N],
+    positions: Vec<usize>,
+    distance: usize,
+    max_distance: usize,
+    gen: LazyLexCombinationGenerator,
+    digits: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T, const N: usize> Hamming<'a, T, N>
+where
+    T: PartialEq,
+{
+    pub(crate) fn new(
+        template: [T; N],
+        alphabet: &'a [T],
+        min_distance: usize,
+        max_distance: usize,
+    ) -> Self {
+        let substitutes: [Vec<usize>; N] = make_array(|i| {
+            (0..alphabet.len())
+                .filter(|&j| alphabet[j] != template[i])
+                .collect()
+        });
+        // Positions without any substitute can never be changed
+        let positions: Vec<usize> = (0..N).filter(|&i| !substitutes[i].is_empty()).collect();
+        let max_distance = max_distance.min(positions.len());
+        Self {
+            template,
+            alphabet,
+            substitutes,
+            positions,
+            distance: min_distance,
+            max_distance,
+            gen: LazyLexCombinationGenerator::new(min_distance),
+            digits: vec![0; min_distance],
+            done: min_distance > max_distance,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Hamming<'a, T, N>
+where
+    T: Clone,
+{
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<[T; N]> {
+        if self.done {
+            return None;
+        }
+        if self.gen.is_done(self.positions.len()) {
+            if self.distance == self.max_distance {
+                self.done = true;
+                return None;
+            }
+            self.distance += 1;
+            self.gen = LazyLexCombinationGenerator::new(self.distance);
+            self.digits = vec![0; self.distance];
+        }
+        let mut res = self.template.clone();
+        for (&i, &digit) in self.gen.indices().iter().zip(&self.digits) {
+            let position = self.positions[i];
+            res[position] = self.alphabet[self.substitutes[position][digit]].clone();
+        }
+        // Advance the substitutes with the last one varying fastest, and move on
+        // to the next positions once they have all been used
+        let mut j = self.distance;
+        loop {
+            if j == 0 {
+                self.gen.step(self.positions.len());
+                break;
+            }
+            j -= 1;
+            let position = self.positions[self.gen.indices()[j]];
+            self.digits[j] += 1;
+            if self.digits[j] < self.substitutes[position].len() {
+                break;
+            }
+            self.digits[j] = 0;
+        }
+        Some(res)
+    }
+}
+
+impl<'a, T, const N: usize> FusedIterator for Hamming<'a, T, N> where T: Clone {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut sphere = hamming_sphere([0, 1], &[0, 1, 2], 2);
+        assert_eq!(sphere.next(), Some([1, 0]));
+        assert_eq!(sphere.next(), Some([1, 2]));
+        assert_eq!(sphere.next(), Some([2, 0]));
+        assert_eq!(sphere.next(), Some([2, 2]));
+        assert_eq!(sphere.next(), None);
+        assert_eq!(sphere.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // Binary strings of length 5 within distance 2: 1 + 5 + 10
+        assert_eq!(hamming_ball([0; 5], &[0, 1], 2).count(), 16);
+        assert_eq!(hamming_ball([0; 5], &[0, 1], 5).count(), 32);
+        // Each substituted position has three other nucleotides to choose from
+        let template = *b"ACGT";
+        let sphere = hamming_sphere(template, b"ACGT", 2);
+        assert_eq!(sphere.count(), 6 * 3 * 3);
+        assert!(hamming_ball(template, b"ACGT", 1).all(|word| word
+            .iter()
+            .zip(&template)
+            .filter(|(a, b)| a != b)
+            .count()
+            <= 1));
+    }
+
+    #[test]
+    fn skips_positions_without_substitutes() {
+        let mut ball = hamming_ball([1, 3], &[1, 2], 2);
+        assert_eq!(ball.next(), Some([1, 3]));
+        assert_eq!(ball.next(), Some([2, 3]));
+        assert_eq!(ball.next(), Some([1, 1]));
+        assert_eq!(ball.next(), Some([1, 2]));
+        assert_eq!(ball.next(), Some([2, 1]));
+        assert_eq!(ball.next(), Some([2, 2]));
+        assert_eq!(ball.next(), None);
+    }
+
+    #[test]
+    fn none_on_distance_too_big() {
+        let mut sphere = hamming_sphere([1, 2], &[1, 2, 3], 3);
+        assert_eq!(sphere.next(), None);
+        let mut sphere = hamming_sphere([1, 2], &[1], 2);
+        assert_eq!(sphere.next(), None);
+        assert_eq!(sphere.next(), None);
+    }
+
+    #[test]
+    fn template_on_distance_zero() {
+        let mut ball = hamming_ball([1, 2], &[3], 0);
+        assert_eq!(ball.next(), Some([1, 2]));
+        assert_eq!(ball.next(), None);
+        let mut sphere = hamming_sphere([1, 2], &[], 0);
+        assert_eq!(sphere.next(), Some([1, 2]));
+        assert_eq!(sphere.next(), None);
+    }
+}
@@ -0,0 +1,306 @@
+//! Iterator adaptors which buffer their input in a fixed-capacity vector.
+//!
+//! [`Combinations`] and [`Permutations`] keep the elements they have read in a
+//! growable `Vec`. Where the input shouldn't end up on the heap, the adaptors
+//! returned by [`combinations_heapless`] and [`permutations_heapless`] can be
+//! used instead, which keep them in a [`heapless::Vec`] with room for `CAP`
+//! elements. They don't need the `alloc` feature, so they also work on targets
+//! without an allocator.
+//!
+//! [`Combinations`]: crate::Combinations
+//! [`Permutations`]: crate::Permutations
+//! [`combinations_heapless`]: crate::IterExt::combinations_heapless
+//! [`permutations_heapless`]: crate::IterExt::permutations_heapless
+
+use crate::counting::size_hint;
+use crate::{combinations, permutations};
+use ::heapless::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// Read elements from `iter` until `items` has more than `max_index` of them.
+///
+/// Return `false` if an element was read while `items` was full. That element
+/// is dropped, and nothing more should be read from `iter`.
+fn fill<I, const CAP: usize>(
+    iter: &mut I,
+    items: &mut Vec<I::Item, CAP>,
+    max_index: Option<usize>,
+) -> bool
+where
+    I: Iterator,
+{
+    if let Some(max_index) = max_index {
+        while items.len() <= max_index {
+            match iter.next() {
+                Some(item) => {
+                    if items.push(item).is_err() {
+                        return false;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+    true
+}
+
+/// Return the size hint of an adaptor with `buffered` elements read from
+/// `iter`, given the number of arrays left for any number of elements.
+///
+/// The adaptor stops instead of reading more than `CAP` elements, so only the
+/// arrays of the first `CAP` elements are counted.
+fn size_hint_capped<I, F, const CAP: usize>(
+    iter: &I,
+    buffered: usize,
+    truncated: bool,
+    remaining: F,
+) -> (usize, Option<usize>)
+where
+    I: Iterator,
+    F: Fn(usize) -> Option<usize>,
+{
+    if truncated {
+        return (0, Some(0));
+    }
+    let (lower, upper) = iter.size_hint();
+    let capped = |len: usize| remaining(buffered.saturating_add(len).min(CAP));
+    let (lower, _) = size_hint(capped(lower));
+    // Even an unbounded input only gets read up to `CAP` elements
+    (lower, capped(upper.unwrap_or(usize::MAX)))
+}
+
+/// An iterator that returns k-length combinations of values from `iter`,
+/// buffering at most `CAP` of them.
+///
+/// This `struct` is created by the [`combinations_heapless`] method on
+/// [`IterExt`]. See its documentation for more.
+///
+/// [`combinations_heapless`]: crate::IterExt::combinations_heapless
+/// [`IterExt`]: crate::IterExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct HeaplessCombinations<I, const K: usize, const CAP: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    items: Vec<I::Item, CAP>,
+    state: combinations::State<K>,
+    truncated: bool,
+}
+
+impl<I, const K: usize, const CAP: usize> HeaplessCombinations<I, K, CAP>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            items: Vec::new(),
+            state: combinations::State::new(),
+            truncated: false,
+        }
+    }
+
+    fn fill(&mut self) {
+        if !self.truncated {
+            self.truncated = !fill(&mut self.iter, &mut self.items, self.state.max_index());
+        }
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`Combinations::is_exhausted`] for more.
+    ///
+    /// [`Combinations::is_exhausted`]: crate::Combinations::is_exhausted
+    pub fn is_exhausted(&mut self) -> bool {
+        self.fill();
+        self.truncated || self.state.is_done(self.items.len())
+    }
+
+    /// Return `true` if the iterator stopped early because the input had more
+    /// than `CAP` elements.
+    ///
+    /// This only becomes `true` once the next combination needs an element which
+    /// doesn't fit, after the combinations of the first `CAP` elements.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<I, const K: usize, const CAP: usize> Iterator for HeaplessCombinations<I, K, CAP>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        self.fill();
+        if self.truncated {
+            return None;
+        }
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_capped::<_, _, CAP>(&self.iter, self.items.len(), self.truncated, |len| {
+            self.state.remaining(len)
+        })
+    }
+}
+
+impl<I, const K: usize, const CAP: usize> FusedIterator for HeaplessCombinations<I, K, CAP>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator that returns k-length permutations of values from `iter`,
+/// buffering at most `CAP` of them.
+///
+/// This `struct` is created by the [`permutations_heapless`] method on
+/// [`IterExt`]. See its documentation for more.
+///
+/// [`permutations_heapless`]: crate::IterExt::permutations_heapless
+/// [`IterExt`]: crate::IterExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct HeaplessPermutations<I, const K: usize, const CAP: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    items: Vec<I::Item, CAP>,
+    state: permutations::State<K>,
+    truncated: bool,
+}
+
+impl<I, const K: usize, const CAP: usize> HeaplessPermutations<I, K, CAP>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            items: Vec::new(),
+            state: permutations::State::new(),
+            truncated: false,
+        }
+    }
+
+    fn fill(&mut self) {
+        if !self.truncated {
+            self.truncated = !fill(&mut self.iter, &mut self.items, self.state.max_index());
+        }
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// See [`Combinations::is_exhausted`] for more.
+    ///
+    /// [`Combinations::is_exhausted`]: crate::Combinations::is_exhausted
+    pub fn is_exhausted(&mut self) -> bool {
+        self.fill();
+        self.truncated || self.state.is_done(self.items.len())
+    }
+
+    /// Return `true` if the iterator stopped early because the input had more
+    /// than `CAP` elements.
+    ///
+    /// This only becomes `true` once the next permutation needs an element which
+    /// doesn't fit, after the permutations of the first `CAP` elements.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<I, const K: usize, const CAP: usize> Iterator for HeaplessPermutations<I, K, CAP>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        self.fill();
+        if self.truncated {
+            return None;
+        }
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_capped::<_, _, CAP>(&self.iter, self.items.len(), self.truncated, |len| {
+            self.state.remaining(len)
+        })
+    }
+}
+
+impl<I, const K: usize, const CAP: usize> FusedIterator for HeaplessPermutations<I, K, CAP>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+
+    #[test]
+    fn matches_vec_buffer() {
+        let combinations = (0..8).combinations_heapless::<3, 8>();
+        assert_eq!(combinations.size_hint(), (56, Some(56)));
+        assert!(combinations.eq((0..8).combinations::<3>()));
+        let permutations = (0..5).permutations_heapless::<3, 5>();
+        assert_eq!(permutations.size_hint(), (60, Some(60)));
+        assert!(permutations.eq((0..5).permutations::<3>()));
+        let mut empty = (0..5).permutations_heapless::<0, 0>();
+        assert!(!empty.is_exhausted());
+        assert_eq!(empty.next(), Some([]));
+        assert!(empty.is_exhausted());
+        assert_eq!(empty.next(), None);
+        let mut combinations = (0..2).combinations_heapless::<3, 4>();
+        assert!(combinations.is_exhausted());
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn size_hint_within_capacity() {
+        let mut combinations = (0..10).combinations_heapless::<2, 4>();
+        assert_eq!(combinations.size_hint(), (6, Some(6)));
+        combinations.next();
+        assert_eq!(combinations.size_hint(), (5, Some(5)));
+        let permutations = (0..).permutations_heapless::<2, 3>();
+        assert_eq!(permutations.size_hint(), (6, Some(6)));
+        let combinations = (0..3).filter(|_| true).combinations_heapless::<2, 8>();
+        assert_eq!(combinations.size_hint(), (0, Some(3)));
+    }
+
+    #[test]
+    fn over_capacity() {
+        // The combinations of the first four elements come out fine before
+        // the fifth one is needed
+        let mut combinations = (0..5).combinations_heapless::<2, 4>();
+        assert_eq!(combinations.by_ref().take(6).count(), 6);
+        assert!(!combinations.is_truncated());
+        assert_eq!(combinations.next(), None);
+        assert!(combinations.is_truncated());
+        assert!(combinations.is_exhausted());
+        assert_eq!(combinations.size_hint(), (0, Some(0)));
+        assert_eq!(combinations.next(), None);
+
+        let mut permutations = (0..).permutations_heapless::<2, 3>();
+        assert_eq!(permutations.by_ref().take(6).count(), 6);
+        assert!(permutations.is_exhausted());
+        assert!(permutations.is_truncated());
+        assert_eq!(permutations.next(), None);
+
+        // Filling the buffer exactly doesn't truncate anything
+        let mut combinations = (0..4).combinations_heapless::<2, 4>();
+        assert_eq!(combinations.by_ref().count(), 6);
+        assert!(!combinations.is_truncated());
+    }
+}
@@ -0,0 +1,88 @@
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+
+mod sealed {
+    /// Iterators which can report the positions of the elements in their next
+    /// item.
+    pub trait IndexedSource<const K: usize>: Iterator {
+        fn next_with_indices(&mut self) -> Option<([usize; K], Self::Item)>;
+    }
+}
+
+pub(crate) use sealed::IndexedSource;
+
+/// An iterator that returns the arrays of another iterator along with the
+/// positions of their elements.
+///
+/// This `struct` is created by the `with_indices` method on the slice
+/// iterators, like [`SliceCombinations::with_indices`]. See its documentation
+/// for more.
+///
+/// [`SliceCombinations::with_indices`]: crate::SliceCombinations::with_indices
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct WithIndices<I, const K: usize> {
+    iter: I,
+}
+
+impl<I, const K: usize> WithIndices<I, K> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<I, const K: usize> Iterator for WithIndices<I, K>
+where
+    I: IndexedSource<K>,
+{
+    type Item = ([usize; K], I::Item);
+
+    fn next(&mut self) -> Option<([usize; K], I::Item)> {
+        self.iter.next_with_indices()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, const K: usize> ExactSizeIterator for WithIndices<I, K> where
+    I: IndexedSource<K> + ExactSizeIterator
+{
+}
+
+impl<I, const K: usize> FusedIterator for WithIndices<I, K> where I: IndexedSource<K> + FusedIterator
+{}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+
+    #[test]
+    fn indices_match_values() {
+        let items = [3, 1, 4, 1, 5];
+        let mut indexed = items.combinations::<3>().with_indices();
+        assert_eq!(indexed.next(), Some(([0, 1, 2], [&3, &1, &4])));
+        assert_eq!(indexed.size_hint(), (9, Some(9)));
+        assert!(indexed
+            .map(|(_, values)| values)
+            .eq(items.combinations::<3>().skip(1)));
+        for (indices, values) in items.permutations::<3>().with_indices() {
+            assert_eq!(indices.map(|i| &items[i]), values);
+        }
+        for (indices, values) in items.combinations_copied::<2>().with_indices() {
+            assert_eq!(indices.map(|i| items[i]), values);
+        }
+        for (indices, values) in items.permutations_copied::<2>().with_indices() {
+            assert_eq!(indices.map(|i| items[i]), values);
+        }
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        assert_eq!([1, 2].combinations::<3>().with_indices().next(), None);
+        assert_eq!(
+            [1, 2].permutations_copied::<0>().with_indices().next(),
+            Some(([], []))
+        );
+    }
+}
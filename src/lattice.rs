@@ -0,0 +1,142 @@
+use crate::combinations::LazyCombinationGenerator;
+use crate::counting::size_hint;
+use core::iter::{FusedIterator, Iterator};
+
+/// A single unit step of a monotone lattice path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LatticeStep {
+    /// A step along the width of the grid.
+    Right,
+    /// A step along the height of the grid.
+    Up,
+}
+
+/// Return an iterator over the monotone lattice paths across an `M` by `N`
+/// grid.
+///
+/// Each path is yielded as an array of the `L` steps taken from the bottom left
+/// to the top right corner, `M` of which are [`LatticeStep::Right`] and `N` of
+/// which are [`LatticeStep::Up`]. `L` has to be `M + N`, which is checked at
+/// compile time. The positions of the right steps in each path are the
+/// combinations of `0..L`, in the same order as [`SliceExt::combinations`]
+/// produces them, so there are `L` choose `M` paths.
+///
+/// [`SliceExt::combinations`]: crate::SliceExt::combinations
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::{lattice_paths, LatticeStep::*};
+///
+/// let mut paths = lattice_paths::<2, 1, 3>();
+/// assert_eq!(paths.next(), Some([Right, Right, Up]));
+/// assert_eq!(paths.next(), Some([Right, Up, Right]));
+/// assert_eq!(paths.next(), Some([Up, Right, Right]));
+/// assert_eq!(paths.next(), None);
+/// ```
+///
+/// Count the paths which never go above the diagonal, as in the ballot problem:
+///
+/// ```
+/// use const_combinations::{lattice_paths, LatticeStep};
+///
+/// let below = lattice_paths::<4, 4, 8>().filter(|path| {
+///     path.iter()
+///         .scan(0i32, |height, &step| {
+///             *height += if step == LatticeStep::Up { 1 } else { -1 };
+///             Some(*height)
+///         })
+///         .all(|height| height <= 0)
+/// });
+/// assert_eq!(below.count(), 14);
+/// ```
+#[doc(alias = "staircase_walks")]
+pub fn lattice_paths<const M: usize, const N: usize, const L: usize>() -> LatticePaths<M, N, L> {
+    LatticePaths::new()
+}
+
+/// An iterator that returns the monotone lattice paths across a grid.
+///
+/// This `struct` is created by the [`lattice_paths`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct LatticePaths<const M: usize, const N: usize, const L: usize> {
+    // Positions of the right steps
+    gen: LazyCombinationGenerator<M>,
+}
+
+impl<const M: usize, const N: usize, const L: usize> LatticePaths<M, N, L> {
+    pub(crate) fn new() -> Self {
+        const { assert!(L == M + N, "`L` has to be `M + N`") };
+        Self {
+            gen: LazyCombinationGenerator::new(),
+        }
+    }
+}
+
+impl<const M: usize, const N: usize, const L: usize> Iterator for LatticePaths<M, N, L> {
+    type Item = [LatticeStep; L];
+
+    fn next(&mut self) -> Option<[LatticeStep; L]> {
+        if self.gen.is_done(L) {
+            None
+        } else {
+            let mut path = [LatticeStep::Up; L];
+            for &i in self.gen.indices() {
+                path[i] = LatticeStep::Right;
+            }
+            self.gen.step();
+            Some(path)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.gen.remaining(L))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<[LatticeStep; L]> {
+        self.gen.advance_by(L, n).ok()?;
+        self.next()
+    }
+}
+
+impl<const M: usize, const N: usize, const L: usize> FusedIterator for LatticePaths<M, N, L> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::combination_masks;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn matches_combination_masks() {
+        let masks =
+            lattice_paths::<3, 4, 7>().map(|path| path.map(|step| step == LatticeStep::Right));
+        assert!(masks.eq(combination_masks::<7, 3>()));
+    }
+
+    #[test]
+    fn count() {
+        assert_eq!(lattice_paths::<3, 3, 6>().count(), 20);
+        assert_eq!(lattice_paths::<5, 2, 7>().count(), 21);
+        let all: Vec<_> = lattice_paths::<4, 6, 10>().collect();
+        let mut paths = lattice_paths::<4, 6, 10>();
+        assert_eq!(paths.size_hint(), (210, Some(210)));
+        assert_eq!(paths.nth(100), Some(all[100]));
+        assert_eq!(paths.size_hint(), (109, Some(109)));
+    }
+
+    #[test]
+    fn straight_paths() {
+        let mut paths = lattice_paths::<0, 3, 3>();
+        assert_eq!(paths.next(), Some([LatticeStep::Up; 3]));
+        assert_eq!(paths.next(), None);
+        let mut paths = lattice_paths::<2, 0, 2>();
+        assert_eq!(paths.next(), Some([LatticeStep::Right; 2]));
+        assert_eq!(paths.next(), None);
+        let mut paths = lattice_paths::<0, 0, 0>();
+        assert_eq!(paths.next(), Some([]));
+        assert_eq!(paths.next(), None);
+    }
+}
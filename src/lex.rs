@@ -0,0 +1,210 @@
+use crate::counting::size_hint;
+use crate::{get_item, make_array};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return the number of arrangements of `k` out of `n` items, or `None` if it
+/// doesn't fit in a `u128`.
+fn arrangements(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    (n - k + 1..=n).try_fold(1u128, |product, i| product.checked_mul(i as u128))
+}
+
+#[derive(Clone)]
+pub struct LazyLexPermutationGenerator<const K: usize> {
+    // The first `K` indices are the current permutation, and the rest are the
+    // unused ones in ascending order
+    indices: Vec<usize>,
+    // `cycles[i]` is the number of indices position `i` has left to take,
+    // including the current one
+    cycles: [usize; K],
+    done: bool,
+}
+
+impl<const K: usize> LazyLexPermutationGenerator<K> {
+    pub fn new(item_count: usize) -> Self {
+        Self {
+            indices: (0..item_count).collect(),
+            cycles: make_array(|i| item_count.saturating_sub(i)),
+            done: K > item_count,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices[..K]
+    }
+
+    /// Return the position of the current permutation in the iteration order.
+    pub fn rank(&self) -> Option<u128> {
+        let n = self.indices.len();
+        self.cycles
+            .iter()
+            .enumerate()
+            .try_fold(0u128, |rank, (i, &cycle)| {
+                // Every index this position has already taken came first
+                let block = arrangements(n - 1 - i, K - 1 - i)?;
+                rank.checked_add(((n - i - cycle) as u128).checked_mul(block)?)
+            })
+    }
+
+    /// Return the number of permutations left, including the current one.
+    pub fn remaining(&self) -> Option<u128> {
+        if self.done {
+            Some(0)
+        } else {
+            Some(arrangements(self.indices.len(), K)? - self.rank()?)
+        }
+    }
+
+    pub fn step(&mut self) {
+        // Find the last position which has indices left to take, and swap the
+        // next larger unused one into it
+        let n = self.indices.len();
+        let mut i = K;
+        while i > 0 {
+            i -= 1;
+            self.cycles[i] -= 1;
+            if self.cycles[i] == 0 {
+                // Every index has been in this position, so move the current
+                // one to the back, which leaves the unused ones sorted again
+                self.indices[i..].rotate_left(1);
+                self.cycles[i] = n - i;
+            } else {
+                self.indices.swap(i, n - self.cycles[i]);
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+/// An iterator that returns k-length permutations of values from `slice` in
+/// lexicographic order.
+///
+/// This `struct` is created by the [`lex_permutations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`lex_permutations`]: crate::SliceExt::lex_permutations
+/// [`SliceExt`]: crate::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceLexPermutations<'a, T, const K: usize> {
+    items: &'a [T],
+    gen: LazyLexPermutationGenerator<K>,
+}
+
+impl<'a, T, const K: usize> SliceLexPermutations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            gen: LazyLexPermutationGenerator::new(items.len()),
+        }
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// See [`SlicePermutations::remaining_len`] for more.
+    ///
+    /// [`SlicePermutations::remaining_len`]: crate::SlicePermutations::remaining_len
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.gen.remaining()
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// See [`SlicePermutations::is_empty`] for more.
+    ///
+    /// [`SlicePermutations::is_empty`]: crate::SlicePermutations::is_empty
+    pub fn is_empty(&self) -> bool {
+        self.gen.is_done()
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceLexPermutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let indices = self.gen.indices();
+            // SAFETY: the generator only hands out indices below the number of
+            // items it was created with
+            let res = make_array(|i| unsafe { get_item(self.items, indices[i]) });
+            self.gen.step();
+            Some(res)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.gen.remaining().and_then(|n| usize::try_from(n).ok()))
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceLexPermutations<'_, T, K> {}
+
+#[cfg(test)]
+mod test {
+    use crate::counting::factorial_u128;
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let items = [0, 1, 2, 3];
+        let mut permutations = items.lex_permutations::<2>();
+        assert_eq!(permutations.next(), Some([&0, &1]));
+        assert_eq!(permutations.next(), Some([&0, &2]));
+        assert_eq!(permutations.next(), Some([&0, &3]));
+        assert_eq!(permutations.next(), Some([&1, &0]));
+        assert_eq!(permutations.next(), Some([&1, &2]));
+        assert_eq!(permutations.nth(6), Some([&3, &2]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn matches_sorted_permutations() {
+        let items: Vec<_> = (0..7).collect();
+        let mut expected: Vec<_> = items.permutations::<4>().collect();
+        expected.sort_unstable();
+        assert!(items.lex_permutations::<4>().eq(expected));
+    }
+
+    #[test]
+    fn size_hint() {
+        let items = [0; 8];
+        let mut permutations = items.lex_permutations::<5>();
+        for remaining in (0..=6720).rev() {
+            assert_eq!(permutations.size_hint(), (remaining, Some(remaining)));
+            permutations.next();
+        }
+        let items: Vec<u8> = (0..30).collect();
+        let permutations = items.lex_permutations::<30>();
+        assert_eq!(permutations.size_hint(), (usize::MAX, None));
+        assert_eq!(permutations.remaining_len(), factorial_u128(30));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut permutations = [1, 2].lex_permutations::<3>();
+        assert_eq!(permutations.size_hint(), (0, Some(0)));
+        assert!(permutations.is_empty());
+        assert!(![1, 2].lex_permutations::<2>().is_empty());
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_k_zero() {
+        let mut permutations = [1, 2].lex_permutations::<0>();
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+    }
+}
@@ -12,17 +12,218 @@
 //! assert_eq!(combinations.next(), Some([2, 3, 4]));
 //! assert_eq!(combinations.next(), None);
 //! ```
+//!
+//! # Thread safety
+//!
+//! Every iterator in this crate is [`Send`] and [`Sync`] when the iterators,
+//! items and closures it holds are. The iterators which share their items
+//! between clones, like [`ForkedCombinations`], keep them behind an `Arc` and
+//! so additionally need the items to be `Sync` to be `Send`.
+//!
+//! # Without an allocator
+//!
+//! Most of the crate keeps elements on the heap and needs the `alloc` feature,
+//! which is on by default. Without it, the slice adaptors, the generators which
+//! only hold indices and, with the `heapless` feature, `combinations_heapless`
+//! and `permutations_heapless` are still available.
 
 #![no_std]
 #![feature(maybe_uninit_uninit_array)]
+#![feature(iter_advance_by)]
+#![feature(try_trait_v2)]
+#![feature(step_trait)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "rand")]
+use ::rand::Rng;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "allocator_api")]
+use buffer::Allocator;
+#[cfg(feature = "alloc")]
+use core::iter::Sum;
+#[cfg(feature = "alloc")]
+use core::ops::Add;
+#[cfg(feature = "alloc")]
+use core::str::Chars;
+#[cfg(feature = "alloc")]
+use fallible::Fallible;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "alloc")]
+mod arena;
+#[cfg(feature = "alloc")]
+mod assignments;
+#[cfg(feature = "alloc")]
+mod backtrack;
+#[cfg(feature = "alloc")]
+mod best_first;
+mod bits;
+#[cfg(feature = "bitvec")]
+mod bitvec;
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "alloc")]
+mod buffer;
+#[cfg(feature = "alloc")]
+mod chunked;
 mod combinations;
+mod compositions;
+pub mod counting;
+mod cross;
+#[cfg(feature = "alloc")]
+mod cycle_type;
+mod dyck;
+mod error;
+#[cfg(feature = "alloc")]
+mod fallible;
+mod gray;
+#[cfg(feature = "alloc")]
+mod hamming;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod indexed;
+#[cfg(feature = "alloc")]
+mod lattice;
+#[cfg(feature = "alloc")]
+mod lex;
+mod macros;
+#[cfg(feature = "alloc")]
+mod multiset;
+mod necklaces;
+mod noncrossing;
+mod owned;
+#[cfg(feature = "alloc")]
+mod pairwise;
+#[cfg(feature = "alloc")]
+mod partitions;
+mod perm;
 mod permutations;
+mod product;
+#[cfg(feature = "alloc")]
+mod pruned;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "alloc")]
+mod restricted;
+#[cfg(feature = "alloc")]
+mod rewind;
+#[cfg(feature = "alloc")]
+mod round_robin;
+mod set_partitions;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod streaming;
+#[cfg(feature = "alloc")]
+mod subsets;
+#[cfg(feature = "alloc")]
+mod tableaux;
 
-pub use combinations::{Combinations, SliceCombinations};
-pub use permutations::{Permutations, SlicePermutations};
+#[cfg(feature = "alloc")]
+pub use arena::{Arena, InArena};
+#[cfg(feature = "alloc")]
+pub use assignments::{assignments, surjective_assignments, Assignments, SurjectiveAssignments};
+#[cfg(feature = "alloc")]
+pub use backtrack::{SliceConstrainedPermutations, SlicePositionPermutations};
+#[cfg(feature = "alloc")]
+pub use best_first::SliceBestFirstCombinations;
+pub use bits::{
+    bit_combinations, bit_combinations_u128, combination_masks, BitCombinations, CombinationMasks,
+};
+#[cfg(feature = "bitvec")]
+pub use bitvec::{
+    combination_bitarrays, combination_bitvecs, BitSliceExt, CombinationBitArrays,
+    CombinationBitVecs,
+};
+#[cfg(feature = "alloc")]
+pub use boxed::Boxed;
+#[cfg(feature = "alloc")]
+pub use chunked::Chunked;
+pub use combinations::{
+    next_combination, range_combinations, write_combinations, CombinationsSnapshot, Complement,
+    RangeCombinations, SliceCombinations, SliceCombinationsMut, SliceComplementCombinations,
+    SliceCopiedCombinations, SliceDisjointCombinations, SliceSpacedCombinations,
+};
+#[cfg(feature = "alloc")]
+pub use combinations::{Combinations, ForkedCombinations, SharedCombinations};
+pub use compositions::{compositions, weak_compositions, Compositions, WeakCompositions};
+pub use cross::{cross_combinations, CrossCombinations};
+#[cfg(feature = "alloc")]
+pub use cycle_type::{cycle_type_permutations, CycleTypePermutations};
+pub use dyck::{dyck_words, DyckWords};
+pub use error::KTooLarge;
+#[cfg(feature = "alloc")]
+pub use fallible::{TryCombinations, TryPermutations};
+pub use gray::{gray_code_deltas, gray_codes, GrayCodeDeltas, GrayCodes};
+#[cfg(feature = "alloc")]
+pub use hamming::{hamming_ball, hamming_sphere, Hamming};
+#[cfg(feature = "heapless")]
+pub use heapless::{HeaplessCombinations, HeaplessPermutations};
+pub use indexed::WithIndices;
+#[cfg(feature = "alloc")]
+pub use lattice::{lattice_paths, LatticePaths, LatticeStep};
+#[cfg(feature = "alloc")]
+pub use lex::SliceLexPermutations;
+#[doc(hidden)]
+pub use macros::__private;
+#[cfg(feature = "alloc")]
+pub use multiset::SliceMultisetCombinations;
+pub use necklaces::{lyndon_words, necklaces, LyndonWords, Necklaces};
+pub use noncrossing::{noncrossing_partitions, NonCrossingPartitions};
+pub use owned::IntoCombinations;
+#[cfg(feature = "alloc")]
+pub use pairwise::pairwise;
+#[cfg(feature = "alloc")]
+pub use partitions::{partitions, Partitions};
+#[cfg(feature = "alloc")]
+pub use perm::Cycles;
+pub use perm::{invert, permute_by, Parity, Perm};
+pub use permutations::{
+    heap_swaps, HeapSwaps, PermutationsSnapshot, SliceCircularPermutations,
+    SliceCopiedPermutations, SliceParityPermutations, SlicePermutations, SlicePermutationsMut,
+    WithParity,
+};
+#[cfg(feature = "alloc")]
+pub use permutations::{CircularPermutations, ForkedPermutations, Permutations};
+pub use product::{mixed_radix, multi_cartesian_product, MixedRadix, MultiCartesianProduct};
+#[cfg(feature = "alloc")]
+pub use pruned::{
+    CheckedAdd, SliceSubsetSums, SliceTargetSumCombinations, SliceWeightBoundedCombinations,
+};
+#[cfg(feature = "rand")]
+pub use rand::random_derangement;
+#[cfg(feature = "rayon")]
+pub use rayon::ParRanks;
+#[cfg(feature = "alloc")]
+pub use restricted::{
+    alternating_permutations, cyclic_permutations, descent_permutations, fixed_point_permutations,
+    involutions, AlternatingPermutations, CyclicPermutations, DescentPermutations,
+    FixedPointPermutations, Involutions,
+};
+#[cfg(feature = "alloc")]
+pub use rewind::{Rewindable, RewindableCombinations, RewindablePermutations};
+#[cfg(feature = "alloc")]
+pub use round_robin::{round_robin, RoundRobin};
+pub use set_partitions::{set_partitions, set_partitions_into, SetPartitions, SetPartitionsInto};
+#[cfg(feature = "futures")]
+pub use stream::{BudgetedStream, CombinationsStream, PermutationsStream};
+#[cfg(feature = "alloc")]
+pub use streaming::{LendingIterator, StreamingCombinations, StreamingPermutations};
+#[cfg(feature = "alloc")]
+pub use subsets::SubsetsBySize;
+#[cfg(feature = "alloc")]
+pub use tableaux::{young_tableaux, YoungTableaux};
 
 /// An extension trait adding `combinations` and `permutations` to `Iterator`.
 pub trait IterExt: Iterator {
@@ -33,6 +234,14 @@ pub trait IterExt: Iterator {
     /// elements. If `K` is greater than the length of the input iterator the
     /// resulting iterator adaptor will yield no items.
     ///
+    /// Combinations are produced in colexicographic order of the positions of
+    /// their elements: ordered by their last element first, then by the one
+    /// before it, and so on. Every combination of the first `n` elements comes
+    /// before any combination that uses a later one, so the adaptor only ever
+    /// needs to look as far ahead in the input as its last index. For ranges
+    /// of integers, [`range_combinations`] computes the elements from their
+    /// positions instead, without buffering anything.
+    ///
     /// # Examples
     ///
     /// ```
@@ -56,6 +265,8 @@ pub trait IterExt: Iterator {
     /// assert_eq!(combinations.next(), Some([2, 2]));
     /// assert_eq!(combinations.next(), None);
     /// ```
+    #[doc(alias = "colex")]
+    #[cfg(feature = "alloc")]
     fn combinations<const K: usize>(self) -> Combinations<Self, K>
     where
         Self: Sized,
@@ -64,6 +275,68 @@ pub trait IterExt: Iterator {
         Combinations::new(self)
     }
 
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from an iterator, with room for `capacity` buffered elements.
+    ///
+    /// [`combinations`] reserves room for as many elements as the lower bound of the
+    /// iterator's `size_hint`. When that bound is lower than the number of
+    /// elements, for example after a `filter`, passing the expected number here
+    /// avoids growing the buffer while enumerating.
+    ///
+    /// [`combinations`]: IterExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::IterExt;
+    /// let mut combinations = (1..4).filter(|n| n % 2 == 1).combinations_with_capacity::<2>(2);
+    /// assert_eq!(combinations.next(), Some([1, 3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn combinations_with_capacity<const K: usize>(self, capacity: usize) -> Combinations<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Combinations::with_capacity(self, capacity)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from an iterator, wrapping every element in a shared pointer.
+    ///
+    /// Every element is converted into a `P`, usually an [`Rc`] or [`Arc`], once
+    /// when it is first read. The produced arrays then contain clones of these
+    /// pointers, so elements which are expensive to clone are never copied no
+    /// matter how many combinations they appear in. If `K` is greater than the
+    /// length of the input iterator the resulting iterator adaptor will yield no
+    /// items.
+    ///
+    /// [`Rc`]: alloc::rc::Rc
+    /// [`Arc`]: alloc::sync::Arc
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    /// use std::rc::Rc;
+    ///
+    /// let words = vec![String::from("foo"), String::from("bar"), String::from("baz")];
+    /// let mut combinations = words.into_iter().combinations_shared::<Rc<String>, 2>();
+    /// let [a, b] = combinations.next().unwrap();
+    /// assert_eq!((a.as_str(), b.as_str()), ("foo", "bar"));
+    /// let [c, _] = combinations.next().unwrap();
+    /// assert!(Rc::ptr_eq(&a, &c));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn combinations_shared<P, const K: usize>(self) -> SharedCombinations<Self, P, K>
+    where
+        Self: Sized,
+        P: From<Self::Item> + Clone,
+    {
+        SharedCombinations::new(self)
+    }
+
     /// Return an iterator adaptor that iterates over the k-length permutations of
     /// the elements from an iterator.
     ///
@@ -94,6 +367,7 @@ pub trait IterExt: Iterator {
     /// assert_eq!(permutations.next(), Some([2, 2])); // Note: these are the same
     /// assert_eq!(permutations.next(), None);
     /// ```
+    #[cfg(feature = "alloc")]
     fn permutations<const K: usize>(self) -> Permutations<Self, K>
     where
         Self: Sized,
@@ -101,6 +375,448 @@ pub trait IterExt: Iterator {
     {
         Permutations::new(self)
     }
+
+    /// Return an iterator adaptor that iterates over the k-length permutations of
+    /// the elements from an iterator, with room for `capacity` buffered elements.
+    ///
+    /// [`permutations`] reserves room for as many elements as the lower bound of the
+    /// iterator's `size_hint`. When that bound is lower than the number of
+    /// elements, for example after a `filter`, passing the expected number here
+    /// avoids growing the buffer while enumerating.
+    ///
+    /// [`permutations`]: IterExt::permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::IterExt;
+    /// let mut permutations = (1..4).filter(|n| n % 2 == 1).permutations_with_capacity::<2>(2);
+    /// assert_eq!(permutations.next(), Some([1, 3]));
+    /// assert_eq!(permutations.next(), Some([3, 1]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn permutations_with_capacity<const K: usize>(self, capacity: usize) -> Permutations<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Permutations::with_capacity(self, capacity)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from an iterator, buffering the elements in `alloc`.
+    ///
+    /// This behaves like [`combinations`], but the elements read from the
+    /// iterator are kept in a buffer allocated with `alloc` rather than the
+    /// global allocator.
+    ///
+    /// [`combinations`]: IterExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use const_combinations::IterExt;
+    /// use std::alloc::Global;
+    ///
+    /// let mut combinations = (1..4).combinations_in::<2, _>(Global);
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// assert_eq!(combinations.next(), Some([1, 3]));
+    /// assert_eq!(combinations.next(), Some([2, 3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    fn combinations_in<const K: usize, A>(self, alloc: A) -> Combinations<Self, K, A>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        A: Allocator,
+    {
+        Combinations::new_in(self, alloc)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length permutations of
+    /// the elements from an iterator, buffering the elements in `alloc`.
+    ///
+    /// This behaves like [`permutations`], but the elements read from the
+    /// iterator are kept in a buffer allocated with `alloc` rather than the
+    /// global allocator.
+    ///
+    /// [`permutations`]: IterExt::permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use const_combinations::IterExt;
+    /// use std::alloc::Global;
+    ///
+    /// let mut permutations = (0..2).permutations_in::<2, _>(Global);
+    /// assert_eq!(permutations.next(), Some([0, 1]));
+    /// assert_eq!(permutations.next(), Some([1, 0]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    fn permutations_in<const K: usize, A>(self, alloc: A) -> Permutations<Self, K, A>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        A: Allocator,
+    {
+        Permutations::new_in(self, alloc)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from an iterator, buffering them in a [`heapless::Vec`].
+    ///
+    /// This behaves like [`combinations`], but keeps the elements it has read in
+    /// a vector with a fixed capacity of `CAP` elements instead of a growable
+    /// one, so it never allocates.
+    ///
+    /// If the input has more than `CAP` elements, the iterator stops once the
+    /// combinations of the first `CAP` elements have been produced, and
+    /// [`is_truncated`] returns `true` from then on.
+    ///
+    /// [`combinations`]: IterExt::combinations
+    /// [`heapless::Vec`]: ::heapless::Vec
+    /// [`is_truncated`]: HeaplessCombinations::is_truncated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (1..4).combinations_heapless::<2, 3>();
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// assert_eq!(combinations.next(), Some([1, 3]));
+    /// assert_eq!(combinations.next(), Some([2, 3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    #[cfg(feature = "heapless")]
+    fn combinations_heapless<const K: usize, const CAP: usize>(
+        self,
+    ) -> HeaplessCombinations<Self, K, CAP>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        HeaplessCombinations::new(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length permutations of
+    /// the elements from an iterator, buffering them in a [`heapless::Vec`].
+    ///
+    /// This behaves like [`permutations`], but keeps the elements it has read in
+    /// a vector with a fixed capacity of `CAP` elements instead of a growable
+    /// one, so it never allocates.
+    ///
+    /// If the input has more than `CAP` elements, the iterator stops once the
+    /// permutations of the first `CAP` elements have been produced, and
+    /// [`is_truncated`] returns `true` from then on.
+    ///
+    /// [`permutations`]: IterExt::permutations
+    /// [`heapless::Vec`]: ::heapless::Vec
+    /// [`is_truncated`]: HeaplessPermutations::is_truncated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = (0..2).permutations_heapless::<2, 4>();
+    /// assert_eq!(permutations.next(), Some([0, 1]));
+    /// assert_eq!(permutations.next(), Some([1, 0]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "heapless")]
+    fn permutations_heapless<const K: usize, const CAP: usize>(
+        self,
+    ) -> HeaplessPermutations<Self, K, CAP>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        HeaplessPermutations::new(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from an iterator of results.
+    ///
+    /// This behaves like [`combinations`] for the `Ok` elements, and produces
+    /// `Ok` arrays. Elements are only read as they are needed, and the first
+    /// `Err` read from the iterator is produced in place of the next array,
+    /// after which the adaptor is done. Collecting into a `Result` therefore
+    /// stops at the first error without reading any further.
+    ///
+    /// [`combinations`]: IterExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let lines = vec!["1", "2", "x", "4"];
+    /// let mut combinations = lines.into_iter().map(str::parse::<u8>).try_combinations();
+    /// assert_eq!(combinations.next(), Some(Ok([1, 2])));
+    /// assert!(matches!(combinations.next(), Some(Err(_))));
+    /// assert_eq!(combinations.next(), None);
+    ///
+    /// let all: Result<Vec<[u8; 2]>, _> = vec![Ok(1), Ok(2), Ok(3), Err("eof")]
+    ///     .into_iter()
+    ///     .try_combinations()
+    ///     .collect();
+    /// assert_eq!(all, Err("eof"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn try_combinations<const K: usize>(self) -> TryCombinations<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Fallible,
+    {
+        TryCombinations::new(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length permutations of
+    /// the elements from an iterator of results.
+    ///
+    /// This behaves like [`permutations`] for the `Ok` elements, and stops at
+    /// the first `Err` like [`try_combinations`] does.
+    ///
+    /// [`permutations`]: IterExt::permutations
+    /// [`try_combinations`]: IterExt::try_combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = vec![Ok(1), Ok(2), Err(())].into_iter().try_permutations();
+    /// assert_eq!(permutations.next(), Some(Ok([1, 2])));
+    /// assert_eq!(permutations.next(), Some(Ok([2, 1])));
+    /// assert_eq!(permutations.next(), Some(Err(())));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn try_permutations<const K: usize>(self) -> TryPermutations<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Fallible,
+    {
+        TryPermutations::new(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length circular
+    /// permutations of the elements from an iterator.
+    ///
+    /// Arrangements which are rotations of each other are only produced once: the
+    /// earliest element of each combination is held in the first position while
+    /// the others are permuted, so rotations are never generated in the first
+    /// place. This produces `(K - 1)!` arrangements per combination, which is
+    /// useful for things like seatings around a table.
+    ///
+    /// The iterator produces a new array per iteration, and clones the iterator
+    /// elements. If `K` is greater than the length of the input iterator the
+    /// resulting iterator adaptor will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::IterExt;
+    /// let mut permutations = (0..4).circular_permutations();
+    /// assert_eq!(permutations.next(), Some([0, 1, 2]));
+    /// assert_eq!(permutations.next(), Some([0, 2, 1]));
+    /// assert_eq!(permutations.next(), Some([0, 1, 3]));
+    /// assert_eq!(permutations.next(), Some([0, 3, 1]));
+    /// assert_eq!(permutations.next(), Some([0, 2, 3]));
+    /// assert_eq!(permutations.next(), Some([0, 3, 2]));
+    /// assert_eq!(permutations.next(), Some([1, 2, 3]));
+    /// assert_eq!(permutations.next(), Some([1, 3, 2]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn circular_permutations<const K: usize>(self) -> CircularPermutations<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        CircularPermutations::new(self)
+    }
+
+    /// Return an iterator adaptor that yields the items of an iterator in
+    /// batches of `M`.
+    ///
+    /// Each call to `next` produces a new `[Self::Item; M]` array, which amortizes
+    /// the per-call overhead when the items are processed in bulk. If the input
+    /// runs out before a batch is filled, the leftover items can be retrieved with
+    /// [`Chunked::into_remainder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut chunks = (1..5).combinations::<3>().chunked::<3>();
+    /// assert_eq!(chunks.next(), Some([[1, 2, 3], [1, 2, 4], [1, 3, 4]]));
+    /// assert_eq!(chunks.next(), None);
+    /// assert_eq!(chunks.into_remainder(), Some(vec![[2, 3, 4]]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn chunked<const M: usize>(self) -> Chunked<Self, M>
+    where
+        Self: Sized,
+    {
+        Chunked::new(self)
+    }
+
+    /// Return an iterator adaptor whose `combinations` and `permutations` traverse
+    /// the iterator again instead of buffering its elements.
+    ///
+    /// Regular [`combinations`] and [`permutations`] keep every element they have
+    /// seen so far in a buffer, which grows with the length of the iterator. When
+    /// the iterator is cheap to clone and knows its exact length, like a range, the
+    /// adaptors returned by [`Rewindable::combinations`] and
+    /// [`Rewindable::permutations`] instead take the elements of every array from
+    /// a fresh clone of the iterator, which keeps their memory use independent of
+    /// its length. Iterating the adaptor itself yields the original elements.
+    ///
+    /// [`combinations`]: IterExt::combinations
+    /// [`permutations`]: IterExt::permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (0..u32::MAX).rewindable().combinations();
+    /// assert_eq!(combinations.next(), Some([0, 1]));
+    /// assert_eq!(combinations.next(), Some([0, 2]));
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn rewindable(self) -> Rewindable<Self>
+    where
+        Self: Sized + Clone + ExactSizeIterator,
+    {
+        Rewindable::new(self)
+    }
+
+    /// Return a stream which yields the items of the iterator, handing control
+    /// back to the executor after every `budget` items.
+    ///
+    /// Enumerating a large number of combinations or permutations inside an
+    /// async task would otherwise keep the executor busy until it's done. The
+    /// stream instead returns `Poll::Pending` once every `budget` items, after
+    /// waking itself, so other tasks get to run in between batches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{CombinationsStream, IterExt, SliceExt};
+    /// use futures_core::Stream;
+    /// use std::pin::Pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// let items = [1, 2, 3, 4];
+    /// let mut stream: CombinationsStream<'_, i32, 2> = items.combinations().into_stream(4);
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut polls = Vec::new();
+    /// loop {
+    ///     let poll = Pin::new(&mut stream).poll_next(&mut cx);
+    ///     if poll == Poll::Ready(None) {
+    ///         break;
+    ///     }
+    ///     polls.push(poll);
+    /// }
+    /// // The six combinations come in a batch of four and a batch of two
+    /// assert_eq!(polls.len(), 7);
+    /// assert_eq!(polls[3], Poll::Ready(Some([&1, &4])));
+    /// assert_eq!(polls[4], Poll::Pending);
+    /// assert_eq!(polls[5], Poll::Ready(Some([&2, &4])));
+    /// ```
+    #[cfg(feature = "futures")]
+    fn into_stream(self, budget: usize) -> BudgetedStream<Self>
+    where
+        Self: Sized,
+    {
+        BudgetedStream::new(self, budget)
+    }
+
+    /// Return an iterator adaptor which moves every array into room taken from
+    /// `arena`, and returns a reference to it.
+    ///
+    /// Arrays which have to outlive the loop would otherwise each pay for an
+    /// allocation of their own, like a `Box`. The arena can be a closure which
+    /// returns the requested number of uninitialized slots, or with the
+    /// `bumpalo` feature a `&Bump`. Like anything else in an arena the arrays are
+    /// never dropped, so elements which own resources are leaked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena returns a different number of slots than requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::uninit(); 12];
+    /// let mut free = &mut storage[..];
+    /// let pairs: Vec<&mut [u8; 2]> = (1..=4)
+    ///     .combinations()
+    ///     .in_arena(|len| {
+    ///         let (slots, rest) = std::mem::take(&mut free).split_at_mut(len);
+    ///         free = rest;
+    ///         slots
+    ///     })
+    ///     .collect();
+    /// assert_eq!(pairs.len(), 6);
+    /// assert_eq!(*pairs[5], [3, 4]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn in_arena<'a, A>(self, arena: A) -> InArena<'a, Self, A>
+    where
+        Self: Sized,
+    {
+        InArena::new(self, arena)
+    }
+
+    /// Choose `K` elements of the iterator uniformly at random.
+    ///
+    /// The iterator is consumed in a single pass without knowing its length up
+    /// front, using reservoir sampling, and only the `K` chosen elements are kept
+    /// at any time. Every set of `K` elements is equally likely to be chosen, but
+    /// the order of the elements in the array is not specified. Returns `None`
+    /// if the iterator has fewer than `K` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    /// use rand::rngs::SmallRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(1);
+    /// let [a, b, c] = (1..=100).choose_array(&mut rng).unwrap();
+    /// assert!(a != b && b != c && a != c);
+    /// assert_eq!((1..3).choose_array::<_, 3>(&mut rng), None);
+    /// ```
+    #[cfg(feature = "rand")]
+    #[doc(alias = "choose_multiple")]
+    #[doc(alias = "reservoir_sample")]
+    #[cfg(feature = "alloc")]
+    fn choose_array<R, const K: usize>(self, rng: &mut R) -> Option<[Self::Item; K]>
+    where
+        Self: Sized,
+        R: Rng + ?Sized,
+    {
+        rand::choose_array(self, rng)
+    }
 }
 
 impl<I> IterExt for I where I: Iterator {}
@@ -114,6 +830,9 @@ pub trait SliceExt<T> {
     /// elements of the slice. If `K` is greater than the length of the input slice the
     /// resulting iterator will yield no items.
     ///
+    /// Combinations are produced in colexicographic order of the positions of
+    /// their elements, just like [`IterExt::combinations`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -137,6 +856,7 @@ pub trait SliceExt<T> {
     /// assert_eq!(combinations.next(), Some([&2, &2]));
     /// assert_eq!(combinations.next(), None);
     /// ```
+    #[doc(alias = "colex")]
     fn combinations<const K: usize>(&self) -> SliceCombinations<T, K>;
 
     /// Return an iterator that iterates over the k-length permutations of
@@ -170,16 +890,1140 @@ pub trait SliceExt<T> {
     /// assert_eq!(permutations.next(), None);
     /// ```
     fn permutations<const K: usize>(&self) -> SlicePermutations<T, K>;
-}
 
-impl<T> SliceExt<T> for [T] {
-    fn combinations<const K: usize>(&self) -> SliceCombinations<T, K> {
-        SliceCombinations::new(self)
-    }
-    fn permutations<const K: usize>(&self) -> SlicePermutations<T, K> {
-        SlicePermutations::new(self)
-    }
-}
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// elements from a slice which are even.
+    ///
+    /// The parity of a permutation is that of the arrangement of its elements
+    /// relative to their order in the slice, so for `K` equal to the length of the
+    /// slice this enumerates the alternating group. Since the parity flips with
+    /// every step of the underlying generator, odd permutations are stepped over
+    /// without building them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut permutations = [1, 2, 3].even_permutations::<3>();
+    /// assert_eq!(permutations.next(), Some([&1, &2, &3]));
+    /// assert_eq!(permutations.next(), Some([&3, &1, &2]));
+    /// assert_eq!(permutations.next(), Some([&2, &3, &1]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    fn even_permutations<const K: usize>(&self) -> SliceParityPermutations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// elements from a slice which are odd.
+    ///
+    /// See [`even_permutations`] for details.
+    ///
+    /// [`even_permutations`]: SliceExt::even_permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut permutations = [1, 2, 3].odd_permutations::<3>();
+    /// assert_eq!(permutations.next(), Some([&2, &1, &3]));
+    /// assert_eq!(permutations.next(), Some([&1, &3, &2]));
+    /// assert_eq!(permutations.next(), Some([&3, &2, &1]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    fn odd_permutations<const K: usize>(&self) -> SliceParityPermutations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// elements from a slice in lexicographic order.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. Permutations are produced in lexicographic order
+    /// of the positions of their elements in the slice. Unlike [`permutations`],
+    /// which goes through the orderings of every combination with Heap's
+    /// algorithm, each permutation is computed directly from the previous one,
+    /// and its positions index straight into the slice. This is usually faster
+    /// when every permutation is consumed.
+    ///
+    /// [`permutations`]: SliceExt::permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut permutations = [1, 2, 3].lex_permutations::<2>();
+    /// assert_eq!(permutations.next(), Some([&1, &2]));
+    /// assert_eq!(permutations.next(), Some([&1, &3]));
+    /// assert_eq!(permutations.next(), Some([&2, &1]));
+    /// assert_eq!(permutations.next(), Some([&2, &3]));
+    /// assert_eq!(permutations.next(), Some([&3, &1]));
+    /// assert_eq!(permutations.next(), Some([&3, &2]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn lex_permutations<const K: usize>(&self) -> SliceLexPermutations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice, along with the elements left out of each one.
+    ///
+    /// This produces the same combinations as [`combinations`], each paired with
+    /// an iterator over the remaining elements in the order of the slice, which
+    /// splits the slice into two groups without having to work out the second
+    /// group by hand.
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let players = ["ann", "bob", "cat", "dan"];
+    /// let mut teams = players.combinations_with_complement::<2>();
+    /// let (team_a, team_b) = teams.next().unwrap();
+    /// assert_eq!(team_a, [&"ann", &"bob"]);
+    /// assert_eq!(team_b.collect::<Vec<_>>(), [&"cat", &"dan"]);
+    /// let (team_a, team_b) = teams.next().unwrap();
+    /// assert_eq!(team_a, [&"ann", &"cat"]);
+    /// assert_eq!(team_b.collect::<Vec<_>>(), [&"bob", &"dan"]);
+    /// ```
+    fn combinations_with_complement<const K: usize>(&self)
+        -> SliceComplementCombinations<'_, T, K>;
+
+    /// Return an iterator that iterates over the ways to pick two disjoint groups
+    /// of `K1` and `K2` elements from a slice.
+    ///
+    /// The groups are ordered, so picking `a` for the first group and `b` for the
+    /// second is different from the other way around. Elements which aren't in
+    /// either group are left out. For every combination of the first group, the
+    /// combinations of the remaining elements are produced for the second group,
+    /// both in the same order as [`combinations`]. If `K1 + K2` is greater than
+    /// the length of the slice the resulting iterator will yield no items.
+    ///
+    /// To split a slice into more groups, nest this with
+    /// [`combinations_with_complement`].
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    /// [`combinations_with_complement`]: SliceExt::combinations_with_complement
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut matches = ["ann", "bob", "cat"].disjoint_combinations::<1, 2>();
+    /// assert_eq!(matches.next(), Some(([&"ann"], [&"bob", &"cat"])));
+    /// assert_eq!(matches.next(), Some(([&"bob"], [&"ann", &"cat"])));
+    /// assert_eq!(matches.next(), Some(([&"cat"], [&"ann", &"bob"])));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[doc(alias = "partitions")]
+    #[doc(alias = "groups")]
+    fn disjoint_combinations<const K1: usize, const K2: usize>(
+        &self,
+    ) -> SliceDisjointCombinations<'_, T, K1, K2>;
+
+    /// Return a lending iterator over the k-length combinations of mutable
+    /// references to the elements from a slice.
+    ///
+    /// This produces the combinations in the same order as [`combinations`], so
+    /// elements can be updated for every combination without keeping track of
+    /// indices. Since an element is part of many combinations, each array of
+    /// references has to be dropped before the next one can be taken, which is
+    /// why the returned type has an inherent `next` method instead of being an
+    /// [`Iterator`].
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut counts = [0, 0, 0, 0];
+    /// let mut combinations = counts.combinations_mut();
+    /// while let Some([a, b]) = combinations.next() {
+    ///     *a += 1;
+    ///     *b += 1;
+    /// }
+    /// assert_eq!(counts, [3, 3, 3, 3]);
+    /// ```
+    fn combinations_mut<const K: usize>(&mut self) -> SliceCombinationsMut<'_, T, K>;
+
+    /// Return a lending iterator over the k-length permutations of mutable
+    /// references to the elements from a slice.
+    ///
+    /// This produces the permutations in the same order as [`permutations`],
+    /// with the references to each permutation's elements in its order. Each
+    /// array of references has to be dropped before the next one can be taken,
+    /// like with [`combinations_mut`].
+    ///
+    /// [`permutations`]: SliceExt::permutations
+    /// [`combinations_mut`]: SliceExt::combinations_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut items = [1, 2, 3];
+    /// let mut permutations = items.permutations_mut();
+    /// while let Some([a, b]) = permutations.next() {
+    ///     // Move one from the first element to the second
+    ///     *a -= 1;
+    ///     *b += 1;
+    /// }
+    /// assert_eq!(items, [1, 2, 3]);
+    /// ```
+    fn permutations_mut<const K: usize>(&mut self) -> SlicePermutationsMut<'_, T, K>;
+
+    /// Return an iterator over the k-length combinations of the elements from a
+    /// slice, or an error if `K` is greater than the length of the slice.
+    ///
+    /// This is the same as [`combinations`], except that asking for more
+    /// elements than there are is reported instead of producing an iterator
+    /// which yields no items. For combining elements which may be errors, see
+    /// [`IterExt::try_combinations`] instead.
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{KTooLarge, SliceExt};
+    ///
+    /// let mut combinations = [1, 2, 3].try_combinations::<2>().unwrap();
+    /// assert_eq!(combinations.next(), Some([&1, &2]));
+    ///
+    /// let err = [1, 2, 3].try_combinations::<4>().err();
+    /// assert_eq!(err, Some(KTooLarge { k: 4, len: 3 }));
+    /// ```
+    fn try_combinations<const K: usize>(&self) -> Result<SliceCombinations<'_, T, K>, KTooLarge>;
+
+    /// Return an iterator over the k-length permutations of the elements from a
+    /// slice, or an error if `K` is greater than the length of the slice.
+    ///
+    /// This is the checked counterpart of [`permutations`], like
+    /// [`try_combinations`] is for [`combinations`].
+    ///
+    /// [`permutations`]: SliceExt::permutations
+    /// [`try_combinations`]: SliceExt::try_combinations
+    /// [`combinations`]: SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// assert_eq!([1, 2].try_permutations::<2>().unwrap().count(), 2);
+    /// assert!([1, 2].try_permutations::<3>().is_err());
+    /// ```
+    fn try_permutations<const K: usize>(&self) -> Result<SlicePermutations<'_, T, K>, KTooLarge>;
+
+    /// Return an iterator that iterates over the k-length combinations of
+    /// copies of the elements from a slice.
+    ///
+    /// This produces the same combinations as [`combinations`], but copies the
+    /// elements into the arrays instead of returning references to them, which
+    /// avoids a round of dereferencing for small `Copy` types like integers.
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut combinations = [1, 2, 3, 4].combinations_copied();
+    /// assert_eq!(combinations.next(), Some([1, 2, 3]));
+    /// assert_eq!(combinations.next(), Some([1, 2, 4]));
+    /// assert_eq!(combinations.next(), Some([1, 3, 4]));
+    /// assert_eq!(combinations.next(), Some([2, 3, 4]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    ///
+    /// This is convenient for byte strings, where owned `[u8; K]` arrays can be
+    /// used as keys or written out directly:
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut keys = b"abc".combinations_copied();
+    /// assert_eq!(keys.next(), Some(*b"ab"));
+    /// assert_eq!(keys.next(), Some(*b"ac"));
+    /// assert_eq!(keys.next(), Some(*b"bc"));
+    /// assert_eq!(keys.next(), None);
+    /// ```
+    #[doc(alias = "bytes_combinations")]
+    fn combinations_copied<const K: usize>(&self) -> SliceCopiedCombinations<'_, T, K>
+    where
+        T: Copy;
+
+    /// Return an iterator that iterates over the k-length permutations of
+    /// copies of the elements from a slice.
+    ///
+    /// This produces the same permutations as [`permutations`], but copies the
+    /// elements into the arrays instead of returning references to them.
+    ///
+    /// [`permutations`]: SliceExt::permutations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut permutations = [0, 1, 2].permutations_copied();
+    /// assert_eq!(permutations.next(), Some([0, 1]));
+    /// assert_eq!(permutations.next(), Some([1, 0]));
+    /// assert_eq!(permutations.next(), Some([0, 2]));
+    /// assert_eq!(permutations.next(), Some([2, 0]));
+    /// assert_eq!(permutations.next(), Some([1, 2]));
+    /// assert_eq!(permutations.next(), Some([2, 1]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    ///
+    /// For byte strings this yields owned `[u8; K]` arrays:
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let frames: Vec<[u8; 2]> = b"\x00\xff".permutations_copied().collect();
+    /// assert_eq!(frames, [[0x00, 0xff], [0xff, 0x00]]);
+    /// ```
+    #[doc(alias = "bytes_permutations")]
+    fn permutations_copied<const K: usize>(&self) -> SliceCopiedPermutations<'_, T, K>
+    where
+        T: Copy;
+
+    /// Return an iterator that iterates over the k-length circular permutations of
+    /// the elements from a slice.
+    ///
+    /// Arrangements which are rotations of each other are only produced once, see
+    /// [`IterExt::circular_permutations`] for details.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to the
+    /// elements of the slice. If `K` is greater than the length of the input slice the
+    /// resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut permutations = ['a', 'b', 'c', 'd'].circular_permutations::<4>();
+    /// assert_eq!(permutations.next(), Some([&'a', &'b', &'c', &'d']));
+    /// assert_eq!(permutations.next(), Some([&'a', &'c', &'b', &'d']));
+    /// assert_eq!(permutations.next(), Some([&'a', &'d', &'b', &'c']));
+    /// assert_eq!(permutations.next(), Some([&'a', &'b', &'d', &'c']));
+    /// assert_eq!(permutations.next(), Some([&'a', &'c', &'d', &'b']));
+    /// assert_eq!(permutations.next(), Some([&'a', &'d', &'c', &'b']));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    fn circular_permutations<const K: usize>(&self) -> SliceCircularPermutations<'_, T, K>;
+
+    /// Return an iterator that iterates over all subsets of the elements from a
+    /// slice, ordered by size.
+    ///
+    /// Subsets are produced from smallest to largest, starting with the empty
+    /// subset, and subsets of the same size are produced in lexicographic order
+    /// of their positions in the slice (the "banker's sequence"). This is the
+    /// order to use when searching for the smallest subset that satisfies some
+    /// property.
+    ///
+    /// The iterator produces a new `Vec` per iteration, and returns references to
+    /// the elements of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut subsets = [1, 2, 3].subsets_by_size();
+    /// assert_eq!(subsets.next(), Some(vec![]));
+    /// assert_eq!(subsets.next(), Some(vec![&1]));
+    /// assert_eq!(subsets.next(), Some(vec![&2]));
+    /// assert_eq!(subsets.next(), Some(vec![&3]));
+    /// assert_eq!(subsets.next(), Some(vec![&1, &2]));
+    /// assert_eq!(subsets.next(), Some(vec![&1, &3]));
+    /// assert_eq!(subsets.next(), Some(vec![&2, &3]));
+    /// assert_eq!(subsets.next(), Some(vec![&1, &2, &3]));
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn subsets_by_size(&self) -> SubsetsBySize<'_, T>;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice whose positions are at least `gap` apart.
+    ///
+    /// Only combinations in which any two chosen positions differ by `gap` or more
+    /// are produced, and the others are skipped without being generated. A `gap`
+    /// of `0` or `1` produces the same items as [`combinations`].
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. If the slice is too short to fit `K` elements
+    /// with the given spacing the resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut combinations = [1, 2, 3, 4, 5].spaced_combinations(2);
+    /// assert_eq!(combinations.next(), Some([&1, &3]));
+    /// assert_eq!(combinations.next(), Some([&1, &4]));
+    /// assert_eq!(combinations.next(), Some([&2, &4]));
+    /// assert_eq!(combinations.next(), Some([&1, &5]));
+    /// assert_eq!(combinations.next(), Some([&2, &5]));
+    /// assert_eq!(combinations.next(), Some([&3, &5]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    ///
+    /// [`combinations`]: SliceExt::combinations
+    fn spaced_combinations<const K: usize>(&self, gap: usize) -> SliceSpacedCombinations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice which don't contain two neighbouring elements.
+    ///
+    /// This is the same as [`spaced_combinations`] with a gap of `2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut combinations = [1, 2, 3, 4].non_adjacent_combinations();
+    /// assert_eq!(combinations.next(), Some([&1, &3]));
+    /// assert_eq!(combinations.next(), Some([&1, &4]));
+    /// assert_eq!(combinations.next(), Some([&2, &4]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    ///
+    /// [`spaced_combinations`]: SliceExt::spaced_combinations
+    fn non_adjacent_combinations<const K: usize>(&self) -> SliceSpacedCombinations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice which don't contain two neighbouring elements, where
+    /// the first and last elements of the slice are neighbours as well.
+    ///
+    /// This treats the slice as a ring. Combinations which would contain both
+    /// ends of the slice are skipped without being generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut combinations = [1, 2, 3, 4, 5].circular_non_adjacent_combinations();
+    /// assert_eq!(combinations.next(), Some([&1, &3]));
+    /// assert_eq!(combinations.next(), Some([&1, &4]));
+    /// assert_eq!(combinations.next(), Some([&2, &4]));
+    /// assert_eq!(combinations.next(), Some([&2, &5]));
+    /// assert_eq!(combinations.next(), Some([&3, &5]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    fn circular_non_adjacent_combinations<const K: usize>(
+        &self,
+    ) -> SliceSpacedCombinations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// elements from a slice which are accepted by a constraint.
+    ///
+    /// Permutations are built up one position at a time. For every position,
+    /// `accept` is called with the position, the elements chosen for the
+    /// positions before it, and a candidate element for that position. Once a
+    /// candidate is rejected no permutation starting with that prefix is
+    /// generated, so constraints prune the search instead of filtering its
+    /// results.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. Permutations are produced in lexicographic order
+    /// of the positions of their elements in the slice.
+    ///
+    /// # Examples
+    ///
+    /// Place four queens on a 4x4 board such that none of them attack each other,
+    /// with one queen per row and the permutation giving the column of each queen:
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut queens = [0usize, 1, 2, 3].constrained_permutations::<4, _>(|row, placed, &col| {
+    ///     placed
+    ///         .iter()
+    ///         .enumerate()
+    ///         .all(|(r, &&c)| c.abs_diff(col) != row - r)
+    /// });
+    /// assert_eq!(queens.next(), Some([&1, &3, &0, &2]));
+    /// assert_eq!(queens.next(), Some([&2, &0, &3, &1]));
+    /// assert_eq!(queens.next(), None);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn constrained_permutations<'a, const K: usize, F>(
+        &'a self,
+        accept: F,
+    ) -> SliceConstrainedPermutations<'a, T, F, K>
+    where
+        F: FnMut(usize, &[&'a T], &'a T) -> bool;
+
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// elements from a slice in which every element is in a position it is
+    /// allowed in.
+    ///
+    /// `allowed(pos, i)` tells whether `self[i]` may be placed at position
+    /// `pos`, and is called once for every such pair up front. Elements are
+    /// never tried in positions they aren't allowed in, so no permutation with
+    /// a forbidden placement is generated. This is a simpler and faster form of
+    /// [`constrained_permutations`] for constraints which don't depend on the
+    /// other elements.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. Permutations are produced in lexicographic order
+    /// of the positions of their elements in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// // Ann can't work the first shift, and Cat can only work the last one
+    /// let mut rota = ["ann", "bob", "cat"].permutations_where::<3, _>(|shift, i| match i {
+    ///     0 => shift != 0,
+    ///     2 => shift == 2,
+    ///     _ => true,
+    /// });
+    /// assert_eq!(rota.next(), Some([&"bob", &"ann", &"cat"]));
+    /// assert_eq!(rota.next(), None);
+    /// ```
+    ///
+    /// [`constrained_permutations`]: SliceExt::constrained_permutations
+    #[cfg(feature = "alloc")]
+    fn permutations_where<const K: usize, F>(
+        &self,
+        allowed: F,
+    ) -> SlicePositionPermutations<'_, T, K>
+    where
+        F: FnMut(usize, usize) -> bool;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice, where every element may be chosen up to as many
+    /// times as its multiplicity.
+    ///
+    /// `multiplicities[i]` is the number of copies of `self[i]` to choose from.
+    /// With every multiplicity at `1` these are the plain combinations, and with
+    /// every multiplicity at `K` or more they are the combinations with
+    /// replacement. Combinations which would exceed a multiplicity are skipped
+    /// without being generated.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. Combinations are produced in lexicographic order
+    /// of the positions of their elements in the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiplicities` and the slice have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut combinations = ["x", "y"].multiset_combinations::<3>(&[2, 3]);
+    /// assert_eq!(combinations.next(), Some([&"x", &"x", &"y"]));
+    /// assert_eq!(combinations.next(), Some([&"x", &"y", &"y"]));
+    /// assert_eq!(combinations.next(), Some([&"y", &"y", &"y"]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    #[doc(alias = "combinations_with_replacement")]
+    #[cfg(feature = "alloc")]
+    fn multiset_combinations<const K: usize>(
+        &self,
+        multiplicities: &[usize],
+    ) -> SliceMultisetCombinations<'_, T, K>;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice whose total weight is at most `budget`.
+    ///
+    /// The slice must be sorted by `weight`, from light to heavy. This lets the
+    /// search skip every combination starting with a prefix that has no way to
+    /// stay within the budget, without generating any of them, and every
+    /// prefix it does extend leads to at least one combination. `weight` is
+    /// called once per element.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice. Combinations are produced in lexicographic order
+    /// of the positions of their elements in the slice. If the slice isn't sorted
+    /// by weight, some combinations within the budget may be missed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let parcels = [("pen", 1), ("book", 3), ("laptop", 5), ("monitor", 9)];
+    /// let mut loads = parcels.weight_bounded_combinations::<2, _, _>(6, |&(_, kg)| kg);
+    /// assert_eq!(loads.next(), Some([&("pen", 1), &("book", 3)]));
+    /// assert_eq!(loads.next(), Some([&("pen", 1), &("laptop", 5)]));
+    /// assert_eq!(loads.next(), None);
+    /// ```
+    #[doc(alias = "knapsack")]
+    #[cfg(feature = "alloc")]
+    fn weight_bounded_combinations<'a, const K: usize, W, F>(
+        &'a self,
+        budget: W,
+        weight: F,
+    ) -> SliceWeightBoundedCombinations<'a, T, W, K>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+        F: FnMut(&'a T) -> W;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice which add up to `target`.
+    ///
+    /// The elements are searched from the smallest up, so for every position
+    /// the search knows the smallest and largest sums any completion can
+    /// reach, and skips all combinations starting with a prefix for which the
+    /// target is out of reach without generating them. The elements may be
+    /// negative, and the slice doesn't have to be sorted.
+    ///
+    /// The iterator produces a new array per iteration, and returns references to
+    /// the elements of the slice in the order they have there. Combinations are
+    /// produced in lexicographic order of the positions of their elements in
+    /// the slice sorted by value, which for a sorted slice is the slice itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut combinations = [-2, 0, 1, 3, 4].combinations_with_sum::<3>(2);
+    /// assert_eq!(combinations.next(), Some([&-2, &0, &4]));
+    /// assert_eq!(combinations.next(), Some([&-2, &1, &3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    #[doc(alias = "subset_sum")]
+    #[cfg(feature = "alloc")]
+    fn combinations_with_sum<const K: usize>(
+        &self,
+        target: T,
+    ) -> SliceTargetSumCombinations<'_, T, K>
+    where
+        T: Copy + Ord + CheckedAdd + Sum;
+
+    /// Return an iterator that iterates over the subsets of any size of the
+    /// elements from a slice which add up to `target`.
+    ///
+    /// The elements may not be negative. They are searched from the smallest
+    /// up, so once adding an element to a subset overshoots the target, the
+    /// larger elements after it and every subset containing them are skipped
+    /// without being generated. The slice doesn't have to be sorted.
+    ///
+    /// The iterator produces a new `Vec` per iteration, and returns references to
+    /// the elements of the slice in the order they have there. Subsets are
+    /// produced in lexicographic order of the positions of their elements in the
+    /// slice sorted by value, which for a sorted slice is the slice itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let mut subsets = [1, 2, 3, 4].subsets_with_sum(5);
+    /// assert_eq!(subsets.next(), Some(vec![&1, &4]));
+    /// assert_eq!(subsets.next(), Some(vec![&2, &3]));
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[doc(alias = "subset_sum")]
+    #[cfg(feature = "alloc")]
+    fn subsets_with_sum(&self, target: T) -> SliceSubsetSums<'_, T>
+    where
+        T: Copy + Ord + CheckedAdd + Sum;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements from a slice, from the highest to the lowest total score.
+    ///
+    /// The score of a combination is the sum of the `score` of its elements,
+    /// which is called once per element. `bound(start, count)` must return an
+    /// upper bound on the total score of any `count` elements from
+    /// `self[start..]`. The tighter the bound, the fewer partial combinations
+    /// are explored: a heap of them is kept ordered by their score plus the
+    /// bound on the rest, and the first complete combination to come out of
+    /// it is the best of those remaining.
+    ///
+    /// Only as much of the search as is needed to produce each combination is
+    /// done, so taking the best few with [`take`] is much cheaper than scoring
+    /// every combination and sorting them. Combinations with equal scores are
+    /// produced in lexicographic order of the positions of their elements. If
+    /// `bound` ever underestimates, combinations may come out of order.
+    ///
+    /// The iterator produces a new array per iteration along with its score, and
+    /// returns references to the elements of the slice.
+    ///
+    /// [`take`]: Iterator::take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let players = [("ann", 7), ("bob", 3), ("cat", 9), ("dan", 5)];
+    /// let best = |start: usize, count: usize| {
+    ///     let mut rest: Vec<u32> = players[start..].iter().map(|p| p.1).collect();
+    ///     rest.sort_unstable_by(|a, b| b.cmp(a));
+    ///     rest.iter().take(count).sum()
+    /// };
+    /// let mut teams = players.best_first_combinations::<2, _, _, _>(|p| p.1, best);
+    /// assert_eq!(teams.next(), Some(([&("ann", 7), &("cat", 9)], 16)));
+    /// assert_eq!(teams.next(), Some(([&("cat", 9), &("dan", 5)], 14)));
+    /// ```
+    #[doc(alias = "branch_and_bound")]
+    #[cfg(feature = "alloc")]
+    fn best_first_combinations<'a, const K: usize, S, F, B>(
+        &'a self,
+        score: F,
+        bound: B,
+    ) -> SliceBestFirstCombinations<'a, T, S, B, K>
+    where
+        S: Copy + Ord + Add<Output = S> + Sum,
+        F: FnMut(&'a T) -> S,
+        B: FnMut(usize, usize) -> S;
+
+    /// Return the `k` k-length combinations of the elements from a slice with
+    /// the smallest total key, from the smallest up.
+    ///
+    /// The key of a combination is the sum of the `key` of its elements, which
+    /// is called once per element. Rather than collecting every combination and
+    /// sorting them, the elements are searched in order of their keys while
+    /// keeping only the best `k` combinations found so far. A partial
+    /// combination is abandoned as soon as even the cheapest way to complete it
+    /// can't beat the worst of those, along with every way to continue it with
+    /// a larger element, so large spaces are cut down to little more than
+    /// the part that matters. Combinations with equal keys are ordered by the
+    /// positions of their elements in the order of the keys.
+    ///
+    /// Each array holds references to the elements in the order they have in
+    /// the slice. If there are fewer than `k` combinations, all of them are
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use const_combinations::SliceExt;
+    /// let weights = [7, 3, 9, 5, 1, 8];
+    /// let lightest = weights.k_smallest_by_key::<3, _, _>(2, |&w| w);
+    /// assert_eq!(lightest, [[&3, &5, &1], [&7, &3, &1]]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn k_smallest_by_key<'a, const K: usize, S, F>(&'a self, k: usize, key: F) -> Vec<[&'a T; K]>
+    where
+        S: Copy + Ord + Add<Output = S> + Sum,
+        F: FnMut(&'a T) -> S;
+}
+
+impl<T> SliceExt<T> for [T] {
+    fn combinations<const K: usize>(&self) -> SliceCombinations<T, K> {
+        SliceCombinations::new(self)
+    }
+    fn permutations<const K: usize>(&self) -> SlicePermutations<T, K> {
+        SlicePermutations::new(self)
+    }
+    fn even_permutations<const K: usize>(&self) -> SliceParityPermutations<'_, T, K> {
+        SliceParityPermutations::new(self, Parity::Even)
+    }
+    fn odd_permutations<const K: usize>(&self) -> SliceParityPermutations<'_, T, K> {
+        SliceParityPermutations::new(self, Parity::Odd)
+    }
+    #[cfg(feature = "alloc")]
+    fn lex_permutations<const K: usize>(&self) -> SliceLexPermutations<'_, T, K> {
+        SliceLexPermutations::new(self)
+    }
+    fn combinations_with_complement<const K: usize>(
+        &self,
+    ) -> SliceComplementCombinations<'_, T, K> {
+        SliceComplementCombinations::new(self)
+    }
+    fn disjoint_combinations<const K1: usize, const K2: usize>(
+        &self,
+    ) -> SliceDisjointCombinations<'_, T, K1, K2> {
+        SliceDisjointCombinations::new(self)
+    }
+    fn combinations_mut<const K: usize>(&mut self) -> SliceCombinationsMut<'_, T, K> {
+        SliceCombinationsMut::new(self)
+    }
+    fn permutations_mut<const K: usize>(&mut self) -> SlicePermutationsMut<'_, T, K> {
+        SlicePermutationsMut::new(self)
+    }
+    fn try_combinations<const K: usize>(&self) -> Result<SliceCombinations<'_, T, K>, KTooLarge> {
+        check_len::<K>(self.len())?;
+        Ok(SliceCombinations::new(self))
+    }
+    fn try_permutations<const K: usize>(&self) -> Result<SlicePermutations<'_, T, K>, KTooLarge> {
+        check_len::<K>(self.len())?;
+        Ok(SlicePermutations::new(self))
+    }
+    fn combinations_copied<const K: usize>(&self) -> SliceCopiedCombinations<'_, T, K>
+    where
+        T: Copy,
+    {
+        SliceCopiedCombinations::new(self)
+    }
+    fn permutations_copied<const K: usize>(&self) -> SliceCopiedPermutations<'_, T, K>
+    where
+        T: Copy,
+    {
+        SliceCopiedPermutations::new(self)
+    }
+    fn circular_permutations<const K: usize>(&self) -> SliceCircularPermutations<'_, T, K> {
+        SliceCircularPermutations::new(self)
+    }
+    #[cfg(feature = "alloc")]
+    fn subsets_by_size(&self) -> SubsetsBySize<'_, T> {
+        SubsetsBySize::new(self)
+    }
+    fn spaced_combinations<const K: usize>(&self, gap: usize) -> SliceSpacedCombinations<'_, T, K> {
+        SliceSpacedCombinations::new(self, gap)
+    }
+    fn non_adjacent_combinations<const K: usize>(&self) -> SliceSpacedCombinations<'_, T, K> {
+        SliceSpacedCombinations::new(self, 2)
+    }
+    fn circular_non_adjacent_combinations<const K: usize>(
+        &self,
+    ) -> SliceSpacedCombinations<'_, T, K> {
+        SliceSpacedCombinations::circular(self)
+    }
+    #[cfg(feature = "alloc")]
+    fn constrained_permutations<'a, const K: usize, F>(
+        &'a self,
+        accept: F,
+    ) -> SliceConstrainedPermutations<'a, T, F, K>
+    where
+        F: FnMut(usize, &[&'a T], &'a T) -> bool,
+    {
+        SliceConstrainedPermutations::new(self, accept)
+    }
+    #[cfg(feature = "alloc")]
+    fn multiset_combinations<const K: usize>(
+        &self,
+        multiplicities: &[usize],
+    ) -> SliceMultisetCombinations<'_, T, K> {
+        SliceMultisetCombinations::new(self, multiplicities)
+    }
+    #[cfg(feature = "alloc")]
+    fn weight_bounded_combinations<'a, const K: usize, W, F>(
+        &'a self,
+        budget: W,
+        weight: F,
+    ) -> SliceWeightBoundedCombinations<'a, T, W, K>
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+        F: FnMut(&'a T) -> W,
+    {
+        SliceWeightBoundedCombinations::new(self, budget, weight)
+    }
+    #[cfg(feature = "alloc")]
+    fn combinations_with_sum<const K: usize>(
+        &self,
+        target: T,
+    ) -> SliceTargetSumCombinations<'_, T, K>
+    where
+        T: Copy + Ord + CheckedAdd + Sum,
+    {
+        SliceTargetSumCombinations::new(self, target)
+    }
+    #[cfg(feature = "alloc")]
+    fn subsets_with_sum(&self, target: T) -> SliceSubsetSums<'_, T>
+    where
+        T: Copy + Ord + CheckedAdd + Sum,
+    {
+        SliceSubsetSums::new(self, target)
+    }
+    #[cfg(feature = "alloc")]
+    fn best_first_combinations<'a, const K: usize, S, F, B>(
+        &'a self,
+        score: F,
+        bound: B,
+    ) -> SliceBestFirstCombinations<'a, T, S, B, K>
+    where
+        S: Copy + Ord + Add<Output = S> + Sum,
+        F: FnMut(&'a T) -> S,
+        B: FnMut(usize, usize) -> S,
+    {
+        SliceBestFirstCombinations::new(self, score, bound)
+    }
+    #[cfg(feature = "alloc")]
+    fn k_smallest_by_key<'a, const K: usize, S, F>(&'a self, k: usize, key: F) -> Vec<[&'a T; K]>
+    where
+        S: Copy + Ord + Add<Output = S> + Sum,
+        F: FnMut(&'a T) -> S,
+    {
+        best_first::k_smallest_by_key(self, k, key)
+    }
+    #[cfg(feature = "alloc")]
+    fn permutations_where<const K: usize, F>(
+        &self,
+        allowed: F,
+    ) -> SlicePositionPermutations<'_, T, K>
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        SlicePositionPermutations::new(self, allowed)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An extension trait adding `char_combinations` and `char_permutations` to `str`.
+pub trait StrExt {
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// characters of a string.
+    ///
+    /// The characters are decoded again for every combination instead of being
+    /// collected up front, so nothing is allocated. If `K` is greater than the
+    /// number of characters the resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::StrExt;
+    ///
+    /// let mut combinations = "añb".char_combinations();
+    /// assert_eq!(combinations.next(), Some(['a', 'ñ']));
+    /// assert_eq!(combinations.next(), Some(['a', 'b']));
+    /// assert_eq!(combinations.next(), Some(['ñ', 'b']));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    fn char_combinations<const K: usize>(&self) -> RewindableCombinations<Chars<'_>, K>;
+
+    /// Return an iterator that iterates over the k-length permutations of the
+    /// characters of a string.
+    ///
+    /// Like [`char_combinations`], this doesn't allocate. If `K` is greater than
+    /// the number of characters the resulting iterator will yield no items.
+    ///
+    /// [`char_combinations`]: StrExt::char_combinations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::StrExt;
+    ///
+    /// let anagrams: Vec<String> = "tea"
+    ///     .char_permutations::<3>()
+    ///     .map(|word| word.iter().collect())
+    ///     .collect();
+    /// assert_eq!(anagrams, ["tea", "eta", "ate", "tae", "eat", "aet"]);
+    /// ```
+    fn char_permutations<const K: usize>(&self) -> RewindablePermutations<Chars<'_>, K>;
+}
+
+#[cfg(feature = "alloc")]
+impl StrExt for str {
+    fn char_combinations<const K: usize>(&self) -> RewindableCombinations<Chars<'_>, K> {
+        RewindableCombinations::with_len(self.chars().count(), self.chars())
+    }
+    fn char_permutations<const K: usize>(&self) -> RewindablePermutations<Chars<'_>, K> {
+        RewindablePermutations::with_len(self.chars().count(), self.chars())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An extension trait adding `combinations` to `BTreeSet`.
+pub trait BTreeSetExt<T> {
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements of a set.
+    ///
+    /// The iterator produces a new array per iteration, and returns references
+    /// to the elements of the set. The elements of each combination are in the
+    /// order of the set, and the combinations are in the same order as
+    /// [`SliceExt::combinations`] produces them for the sorted elements. Instead
+    /// of collecting the elements into a temporary slice, the set is walked
+    /// again for every combination, so nothing is allocated. If `K` is greater
+    /// than the size of the set the resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::BTreeSetExt;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set: BTreeSet<_> = vec![3, 1, 2].into_iter().collect();
+    /// let mut combinations = set.combinations();
+    /// assert_eq!(combinations.next(), Some([&1, &2]));
+    /// assert_eq!(combinations.next(), Some([&1, &3]));
+    /// assert_eq!(combinations.next(), Some([&2, &3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    fn combinations<const K: usize>(&self) -> RewindableCombinations<btree_set::Iter<'_, T>, K>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> BTreeSetExt<T> for BTreeSet<T> {
+    fn combinations<const K: usize>(&self) -> RewindableCombinations<btree_set::Iter<'_, T>, K> {
+        RewindableCombinations::new(self.iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An extension trait adding `key_combinations` to `BTreeMap`.
+pub trait BTreeMapExt<Key, Value> {
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// keys of a map.
+    ///
+    /// This works like [`BTreeSetExt::combinations`] on the keys, which come in
+    /// sorted order, and doesn't allocate. If `K` is greater than the size of
+    /// the map the resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::BTreeMapExt;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map: BTreeMap<_, _> = vec![("b", 2), ("a", 1), ("c", 3)].into_iter().collect();
+    /// let keys: Vec<_> = map.key_combinations::<2>().collect();
+    /// assert_eq!(keys, [[&"a", &"b"], [&"a", &"c"], [&"b", &"c"]]);
+    /// ```
+    fn key_combinations<const K: usize>(
+        &self,
+    ) -> RewindableCombinations<btree_map::Keys<'_, Key, Value>, K>;
+}
+
+#[cfg(feature = "alloc")]
+impl<Key, Value> BTreeMapExt<Key, Value> for BTreeMap<Key, Value> {
+    fn key_combinations<const K: usize>(
+        &self,
+    ) -> RewindableCombinations<btree_map::Keys<'_, Key, Value>, K> {
+        RewindableCombinations::new(self.keys())
+    }
+}
+
+/// An extension trait adding methods to arrays whose length is known at compile
+/// time.
+pub trait ArrayExt<T, const N: usize> {
+    /// Return all the k-length combinations of the elements of an array, collected
+    /// into an array.
+    ///
+    /// `C` has to be the number of combinations, [`binomial`]`(N, K)`, which is
+    /// checked at compile time. Since nothing is allocated the result can serve as
+    /// a lookup table on the stack. This isn't a `const fn`, so it can't fill a
+    /// `static`; for a table of indices computed at compile time, use
+    /// [`combinations_table!`] instead. The combinations are in the same order as
+    /// the ones produced by [`SliceExt::combinations`], and the elements are
+    /// cloned.
+    ///
+    /// [`binomial`]: counting::binomial
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::ArrayExt;
+    ///
+    /// let pairs: [[char; 2]; 3] = ['a', 'b', 'c'].combinations_array();
+    /// assert_eq!(pairs, [['a', 'b'], ['a', 'c'], ['b', 'c']]);
+    /// ```
+    ///
+    /// Picking the wrong number of combinations fails to compile:
+    ///
+    /// ```compile_fail
+    /// use const_combinations::ArrayExt;
+    ///
+    /// let pairs: [[char; 2]; 4] = ['a', 'b', 'c'].combinations_array();
+    /// ```
+    fn combinations_array<const K: usize, const C: usize>(&self) -> [[T; K]; C]
+    where
+        T: Clone;
+
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements of an array, taking ownership of the array.
+    ///
+    /// The elements stay in the array, which the iterator holds on to, and are
+    /// cloned into every combination. Unlike going through [`IntoIterator`] and
+    /// [`IterExt::combinations`], nothing is allocated. If `K` is greater than
+    /// `N` the resulting iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::ArrayExt;
+    ///
+    /// let mut combinations = [1, 2, 3].into_combinations();
+    /// assert_eq!(combinations.next(), Some([1, 2]));
+    /// assert_eq!(combinations.next(), Some([1, 3]));
+    /// assert_eq!(combinations.next(), Some([2, 3]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    fn into_combinations<const K: usize>(self) -> IntoCombinations<[T; N], K>;
+}
+
+impl<T, const N: usize> ArrayExt<T, N> for [T; N] {
+    fn combinations_array<const K: usize, const C: usize>(&self) -> [[T; K]; C]
+    where
+        T: Clone,
+    {
+        const {
+            assert!(
+                matches!(counting::binomial(N, K), Some(c) if c == C),
+                "`C` has to be the number of `K`-combinations of `N` items",
+            )
+        };
+        let mut combinations = SliceExt::combinations::<K>(&self[..]);
+        core::array::from_fn(|_| match combinations.next() {
+            Some(combination) => combination.map(T::clone),
+            None => unreachable!(),
+        })
+    }
+
+    fn into_combinations<const K: usize>(self) -> IntoCombinations<[T; N], K> {
+        IntoCombinations::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An extension trait adding `into_combinations` to `Vec`.
+pub trait VecExt<T> {
+    /// Return an iterator that iterates over the k-length combinations of the
+    /// elements of a `Vec`, taking ownership of the `Vec`.
+    ///
+    /// The elements stay in the `Vec`, which the iterator holds on to, and are
+    /// cloned into every combination. Unlike going through [`IntoIterator`] and
+    /// [`IterExt::combinations`], the elements aren't moved into a second
+    /// buffer. If `K` is greater than the length of the `Vec` the resulting
+    /// iterator will yield no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::VecExt;
+    ///
+    /// let words = vec![String::from("a"), String::from("b"), String::from("c")];
+    /// let mut combinations = words.into_combinations::<2>();
+    /// assert_eq!(combinations.next(), Some([String::from("a"), String::from("b")]));
+    /// assert_eq!(combinations.count(), 2);
+    /// ```
+    fn into_combinations<const K: usize>(self) -> IntoCombinations<Vec<T>, K>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> VecExt<T> for Vec<T> {
+    fn into_combinations<const K: usize>(self) -> IntoCombinations<Vec<T>, K> {
+        IntoCombinations::new(self)
+    }
+}
+
+fn check_len<const K: usize>(len: usize) -> Result<(), KTooLarge> {
+    if K > len {
+        Err(KTooLarge { k: K, len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Return the element at `index`, skipping the bounds check when the `unchecked`
+/// feature is enabled.
+///
+/// # Safety
+///
+/// `index` must be less than `items.len()`. The combination generators only hand
+/// out indices up to their maximum index, and they are only read after
+/// `is_done(items.len())` has ruled out that this maximum is out of bounds.
+#[inline(always)]
+unsafe fn get_item<T>(items: &[T], index: usize) -> &T {
+    #[cfg(feature = "unchecked")]
+    {
+        debug_assert!(index < items.len());
+        items.get_unchecked(index)
+    }
+    #[cfg(not(feature = "unchecked"))]
+    {
+        &items[index]
+    }
+}
 
 fn make_array<T, F, const N: usize>(f: F) -> [T; N]
 where
@@ -197,3 +2041,129 @@ where
     }
     unsafe { out.as_ptr().cast::<[T; N]>().read() }
 }
+
+#[cfg(feature = "alloc")]
+fn make_boxed_array<T, F, const N: usize>(f: F) -> Box<[T; N]>
+where
+    F: Fn(usize) -> T,
+{
+    // Fill the array in place instead of building it on the stack and moving it
+    let mut out = Box::<[T; N]>::new_uninit();
+    let ptr = out.as_mut_ptr().cast::<T>();
+    for i in 0..N {
+        unsafe { ptr.add(i).write(f(i)) };
+    }
+    unsafe { out.assume_init() }
+}
+
+#[cfg(feature = "alloc")]
+fn try_make_array<T, F, const N: usize>(mut f: F) -> Result<[T; N], Vec<T>>
+where
+    F: FnMut(usize) -> Option<T>,
+{
+    use core::mem::MaybeUninit;
+
+    let mut out: [MaybeUninit<T>; N] = MaybeUninit::uninit_array();
+    for i in 0..N {
+        match f(i) {
+            Some(t) => out[i] = MaybeUninit::new(t),
+            None => {
+                // Move the initialized prefix out so it isn't leaked
+                let partial = out[..i].iter().map(|t| unsafe { t.as_ptr().read() });
+                return Err(partial.collect());
+            }
+        }
+    }
+    Ok(unsafe { out.as_ptr().cast::<[T; N]>().read() })
+}
+
+#[cfg(test)]
+mod auto_trait_test {
+    use super::*;
+    use alloc::vec::IntoIter;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    // Checked for any `T`, not just the one it is called with below
+    fn iterators<T: Send + Sync>() {
+        assert_send_sync::<Combinations<IntoIter<T>, 3>>();
+        assert_send_sync::<Permutations<IntoIter<T>, 3>>();
+        assert_send_sync::<CircularPermutations<IntoIter<T>, 3>>();
+        assert_send_sync::<ForkedCombinations<T, 3>>();
+        assert_send_sync::<ForkedPermutations<T, 3>>();
+        assert_send_sync::<SharedCombinations<IntoIter<T>, T, 3>>();
+        assert_send_sync::<TryCombinations<IntoIter<Result<T, T>>, 3>>();
+        assert_send_sync::<TryPermutations<IntoIter<Result<T, T>>, 3>>();
+        assert_send_sync::<IntoCombinations<[T; 4], 3>>();
+        assert_send_sync::<CombinationsSnapshot<3>>();
+        assert_send_sync::<PermutationsSnapshot<3>>();
+        assert_send_sync::<IntoCombinations<Vec<T>, 3>>();
+        assert_send_sync::<RangeCombinations<u64, 3>>();
+        assert_send_sync::<StreamingCombinations<IntoIter<T>, 3>>();
+        assert_send_sync::<StreamingPermutations<IntoIter<T>, 3>>();
+        assert_send_sync::<Chunked<IntoIter<T>, 3>>();
+        assert_send_sync::<Rewindable<IntoIter<T>>>();
+        assert_send_sync::<RewindableCombinations<IntoIter<T>, 3>>();
+        assert_send_sync::<RewindablePermutations<IntoIter<T>, 3>>();
+        assert_send_sync::<WithParity<Permutations<IntoIter<T>, 3>>>();
+        assert_send_sync::<Boxed<Combinations<IntoIter<T>, 3>>>();
+        assert_send_sync::<WithIndices<SliceCombinations<'_, T, 3>, 3>>();
+        assert_send_sync::<SliceCombinations<'_, T, 3>>();
+        assert_send_sync::<SliceCombinationsMut<'_, T, 3>>();
+        assert_send_sync::<SliceComplementCombinations<'_, T, 3>>();
+        assert_send_sync::<Complement<'_, T, 3>>();
+        assert_send_sync::<SliceCopiedCombinations<'_, T, 3>>();
+        assert_send_sync::<SliceDisjointCombinations<'_, T, 2, 3>>();
+        assert_send_sync::<SliceSpacedCombinations<'_, T, 3>>();
+        assert_send_sync::<SlicePermutations<'_, T, 3>>();
+        assert_send_sync::<SlicePermutationsMut<'_, T, 3>>();
+        assert_send_sync::<SliceCopiedPermutations<'_, T, 3>>();
+        assert_send_sync::<SliceCircularPermutations<'_, T, 3>>();
+        assert_send_sync::<SliceParityPermutations<'_, T, 3>>();
+        assert_send_sync::<SliceLexPermutations<'_, T, 3>>();
+        assert_send_sync::<SlicePositionPermutations<'_, T, 3>>();
+        assert_send_sync::<SliceConstrainedPermutations<'_, T, fn(&[&T]) -> bool, 3>>();
+        assert_send_sync::<CrossCombinations<'_, '_, T, T, 2, 3>>();
+        assert_send_sync::<Hamming<'_, T, 3>>();
+        assert_send_sync::<MultiCartesianProduct<'_, T, 3>>();
+        assert_send_sync::<SubsetsBySize<'_, T>>();
+        assert_send_sync::<SliceMultisetCombinations<'_, T, 3>>();
+        assert_send_sync::<SliceWeightBoundedCombinations<'_, T, T, 3>>();
+        assert_send_sync::<SliceTargetSumCombinations<'_, T, 3>>();
+        assert_send_sync::<SliceSubsetSums<'_, T>>();
+        assert_send_sync::<SliceBestFirstCombinations<'_, T, u32, fn(usize, usize) -> u32, 3>>();
+    }
+
+    #[test]
+    fn generators() {
+        assert_send_sync::<BitCombinations<u64, 3>>();
+        assert_send_sync::<CombinationMasks<8, 3>>();
+        assert_send_sync::<Compositions<3>>();
+        assert_send_sync::<WeakCompositions<3>>();
+        assert_send_sync::<DyckWords<3>>();
+        assert_send_sync::<GrayCodes<3>>();
+        assert_send_sync::<GrayCodeDeltas<3>>();
+        assert_send_sync::<Necklaces<3>>();
+        assert_send_sync::<LyndonWords<3>>();
+        assert_send_sync::<Partitions>();
+        assert_send_sync::<HeapSwaps<3>>();
+        assert_send_sync::<Perm<3>>();
+        assert_send_sync::<Cycles<3>>();
+        assert_send_sync::<MixedRadix<3>>();
+        assert_send_sync::<CycleTypePermutations<3>>();
+        assert_send_sync::<DescentPermutations<3>>();
+        assert_send_sync::<FixedPointPermutations<3>>();
+        assert_send_sync::<Involutions<3>>();
+        assert_send_sync::<CyclicPermutations<3>>();
+        assert_send_sync::<AlternatingPermutations<3>>();
+        assert_send_sync::<NonCrossingPartitions<3>>();
+        assert_send_sync::<SetPartitions<3>>();
+        assert_send_sync::<SetPartitionsInto<3, 2>>();
+        assert_send_sync::<Assignments<3, 2>>();
+        assert_send_sync::<SurjectiveAssignments<3, 2>>();
+        assert_send_sync::<LatticePaths<2, 1, 3>>();
+        assert_send_sync::<YoungTableaux<3>>();
+        assert_send_sync::<KTooLarge>();
+        iterators::<u8>();
+    }
+}
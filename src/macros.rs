@@ -0,0 +1,242 @@
+/// Run a block for every `K`-combination of the indices `0..N`, in a way that
+/// works in `const` contexts.
+///
+/// `const_for_combinations!(N, K, |indices| body)` binds `indices` to each
+/// `[usize; K]` index array in turn, in the same order as the `combinations`
+/// iterators produce them, and runs `body` for it. Iterators can't be used in
+/// a `const fn` yet, so this expands to a plain `while` loop stepping through
+/// the combinations with [`next_combination`], which can. `K` has to be a
+/// constant since it's the length of the arrays, while `N` can be any `usize`
+/// expression and is only evaluated once. `break` and `continue` work as in any
+/// other loop.
+///
+/// [`next_combination`]: crate::next_combination
+///
+/// # Examples
+///
+/// Build a lookup table of the sums of all pairs at compile time:
+///
+/// ```
+/// use const_combinations::const_for_combinations;
+///
+/// const fn pair_sums<const N: usize, const C: usize>() -> [usize; C] {
+///     let mut table = [0; C];
+///     let mut rank = 0;
+///     const_for_combinations!(N, 2, |indices| {
+///         table[rank] = indices[0] + indices[1];
+///         rank += 1;
+///     });
+///     table
+/// }
+///
+/// const SUMS: [usize; 6] = pair_sums::<4, 6>();
+/// assert_eq!(SUMS, [1, 2, 3, 3, 4, 5]);
+/// ```
+#[macro_export]
+macro_rules! const_for_combinations {
+    ($n:expr, $k:expr, |$indices:pat_param| $body:expr $(,)?) => {{
+        let n: usize = $n;
+        let mut first: [usize; $k] = [0; $k];
+        let mut i = 0;
+        while i < first.len() {
+            first[i] = i;
+            i += 1;
+        }
+        let mut current = match first.last() {
+            Some(&last) if last >= n => None,
+            _ => Some(first),
+        };
+        while let Some(indices) = current {
+            current = $crate::next_combination(indices, n);
+            let $indices = indices;
+            $body;
+        }
+    }};
+}
+
+/// Build a `[[usize; K]; C]` table of every `K`-combination of the indices
+/// `0..N` at compile time, where `C` is the number of combinations.
+///
+/// `combinations_table!(N, K)` expands to a `const` block, so the table is
+/// always computed by the compiler and can be put straight into a `static` or
+/// `const` without a build script. The rows are in the same order as the
+/// `combinations` iterators produce them, so the row at position `r` is the
+/// combination of rank `r`. `N` and `K` have to be constants, and the table
+/// fails to compile if `C` doesn't fit in a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::combinations_table;
+///
+/// static PAIRS: [[usize; 2]; 6] = combinations_table!(4, 2);
+/// assert_eq!(PAIRS, [[0, 1], [0, 2], [1, 2], [0, 3], [1, 3], [2, 3]]);
+/// ```
+#[macro_export]
+macro_rules! combinations_table {
+    ($n:expr, $k:expr $(,)?) => {
+        const {
+            $crate::__private::combinations_table::<
+                { $n },
+                { $k },
+                { $crate::__private::combinations_len($n, $k) },
+            >()
+        }
+    };
+}
+
+/// Build a `[[usize; K]; C]` table of every `K`-permutation of the indices
+/// `0..N` at compile time, where `C` is the number of permutations.
+///
+/// This is the permutation counterpart of [`combinations_table!`], with the
+/// rows in the same order as the `permutations` iterators produce them.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::permutations_table;
+///
+/// const ORDERS: &[[usize; 2]] = &permutations_table!(3, 2);
+/// assert_eq!(ORDERS, [[0, 1], [1, 0], [0, 2], [2, 0], [1, 2], [2, 1]]);
+/// ```
+#[macro_export]
+macro_rules! permutations_table {
+    ($n:expr, $k:expr $(,)?) => {
+        const {
+            $crate::__private::permutations_table::<
+                { $n },
+                { $k },
+                { $crate::__private::permutations_len($n, $k) },
+            >()
+        }
+    };
+}
+
+/// Items the exported macros expand to, which aren't part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::counting::{binomial, factorial};
+    use crate::permutations::heap_step;
+    use crate::write_combinations;
+
+    pub const fn combinations_len(n: usize, k: usize) -> usize {
+        match binomial(n, k) {
+            Some(len) => len,
+            None => panic!("too many combinations for a table"),
+        }
+    }
+
+    pub const fn permutations_len(n: usize, k: usize) -> usize {
+        // Every combination is arranged in each of the `k!` orders
+        let len = match factorial(k) {
+            Some(orders) => orders.checked_mul(combinations_len(n, k)),
+            None => None,
+        };
+        match len {
+            Some(len) => len,
+            None => panic!("too many permutations for a table"),
+        }
+    }
+
+    pub const fn combinations_table<const N: usize, const K: usize, const C: usize>(
+    ) -> [[usize; K]; C] {
+        let mut table = [[0; K]; C];
+        write_combinations::<N, K>(&mut table);
+        table
+    }
+
+    pub const fn permutations_table<const N: usize, const K: usize, const C: usize>(
+    ) -> [[usize; K]; C] {
+        let mut table = [[0; K]; C];
+        let mut rank = 0;
+        const_for_combinations!(N, K, |indices| {
+            let mut order = [0; K];
+            let mut counters = [0; K];
+            let mut i = 0;
+            while i < K {
+                order[i] = i;
+                i += 1;
+            }
+            loop {
+                let mut i = 0;
+                while i < K {
+                    table[rank][i] = indices[order[i]];
+                    i += 1;
+                }
+                rank += 1;
+                if heap_step(&mut order, &mut counters).is_none() {
+                    break;
+                }
+            }
+        });
+        table
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+    use alloc::vec::Vec;
+
+    const fn masks<const N: usize, const K: usize, const C: usize>() -> [u32; C] {
+        let mut table = [0; C];
+        let mut rank = 0;
+        const_for_combinations!(N, K, |indices| {
+            let mut mask = 0;
+            let mut i = 0;
+            while i < K {
+                mask |= 1 << indices[i];
+                i += 1;
+            }
+            table[rank] = mask;
+            rank += 1;
+        });
+        table
+    }
+
+    #[test]
+    fn matches_combinations() {
+        const MASKS: [u32; 35] = masks::<7, 3, 35>();
+        let expected: Vec<u32> = (0..7)
+            .combinations::<3>()
+            .map(|[a, b, c]| 1 << a | 1 << b | 1 << c)
+            .collect();
+        assert_eq!(MASKS[..], expected[..]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let mut count = 0;
+        const_for_combinations!(3, 0, |[]| count += 1);
+        assert_eq!(count, 1);
+        const_for_combinations!(2, 3, |_| count += 1);
+        assert_eq!(count, 1);
+        const_for_combinations!(0, 0, |_| count += 1);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn control_flow() {
+        let mut seen = Vec::new();
+        const_for_combinations!(5, 2, |[a, b]| {
+            if a == b - 1 {
+                continue;
+            }
+            if a == 2 {
+                break;
+            }
+            seen.push([a, b]);
+        });
+        assert_eq!(seen, [[0, 2], [0, 3], [1, 3], [0, 4], [1, 4]]);
+    }
+
+    #[test]
+    fn tables() {
+        static COMBINATIONS: [[usize; 3]; 35] = combinations_table!(7, 3);
+        assert!(COMBINATIONS.iter().copied().eq((0..7).combinations::<3>()));
+        static PERMUTATIONS: [[usize; 3]; 120] = permutations_table!(6, 3);
+        assert!(PERMUTATIONS.iter().copied().eq((0..6).permutations::<3>()));
+        assert_eq!(permutations_table!(2, 3), [[0; 3]; 0]);
+        assert_eq!(permutations_table!(0, 0), [[]]);
+    }
+}
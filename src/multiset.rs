@@ -0,0 +1,170 @@
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// Generates the non-decreasing index arrays in which every index `i` appears
+/// at most as often as its multiplicity, in lexicographic order.
+#[derive(Clone)]
+pub struct LazyMultisetGenerator<const K: usize> {
+    indices: [usize; K],
+    // `capacity[i]` is the total multiplicity of the indices `i..`
+    capacity: Vec<usize>,
+    done: bool,
+}
+
+impl<const K: usize> LazyMultisetGenerator<K> {
+    pub fn new(multiplicities: &[usize]) -> Self {
+        // Multiplicities above `K` make no difference, and clamping them keeps the
+        // sums from overflowing
+        let mut capacity = Vec::with_capacity(multiplicities.len() + 1);
+        let mut total = 0;
+        capacity.push(total);
+        for &m in multiplicities.iter().rev() {
+            total += m.min(K);
+            capacity.push(total);
+        }
+        capacity.reverse();
+        let mut gen = Self {
+            indices: [0; K],
+            capacity,
+            done: false,
+        };
+        gen.done = !gen.fill(0, 0);
+        gen
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn indices(&self) -> &[usize; K] {
+        &self.indices
+    }
+
+    fn multiplicity(&self, index: usize) -> usize {
+        self.capacity[index] - self.capacity[index + 1]
+    }
+
+    /// Fill the positions from `pos` onwards with the smallest indices starting
+    /// at `first`, returning `false` if they don't fit.
+    fn fill(&mut self, pos: usize, first: usize) -> bool {
+        if self.capacity.get(first).is_none_or(|&c| c < K - pos) {
+            return false;
+        }
+        let mut index = first;
+        let mut left = self.multiplicity(index);
+        for i in pos..K {
+            while left == 0 {
+                index += 1;
+                left = self.multiplicity(index);
+            }
+            self.indices[i] = index;
+            left -= 1;
+        }
+        true
+    }
+
+    pub fn step(&mut self) {
+        // Increase the rightmost index which leaves room for the ones after it
+        for pos in (0..K).rev() {
+            if self.fill(pos, self.indices[pos] + 1) {
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+/// An iterator that returns k-length combinations of the elements from a
+/// slice, using every element up to a given number of times.
+///
+/// This `struct` is created by the [`multiset_combinations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`multiset_combinations`]: super::SliceExt::multiset_combinations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceMultisetCombinations<'a, T, const K: usize> {
+    items: &'a [T],
+    gen: LazyMultisetGenerator<K>,
+}
+
+impl<'a, T, const K: usize> SliceMultisetCombinations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T], multiplicities: &[usize]) -> Self {
+        assert_eq!(items.len(), multiplicities.len(), "lengths differ");
+        Self {
+            items,
+            gen: LazyMultisetGenerator::new(multiplicities),
+        }
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceMultisetCombinations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = self.gen.indices().map(|i| &self.items[i]);
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceMultisetCombinations<'_, T, K> {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let mut combinations = ['a', 'b', 'c'].multiset_combinations::<3>(&[2, 0, 3]);
+        assert_eq!(combinations.next(), Some([&'a', &'a', &'c']));
+        assert_eq!(combinations.next(), Some([&'a', &'c', &'c']));
+        assert_eq!(combinations.next(), Some([&'c', &'c', &'c']));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn between_plain_and_replacement() {
+        let items = [1, 2, 3, 4, 5];
+        // With every multiplicity at one these are the plain combinations
+        let mut plain: Vec<_> = items.combinations::<3>().collect();
+        plain.sort();
+        let multiset: Vec<_> = items.multiset_combinations::<3>(&[1; 5]).collect();
+        assert_eq!(multiset, plain);
+        // With every multiplicity at `K` there is no limit at all
+        let count = items.multiset_combinations::<3>(&[3; 5]).count();
+        assert_eq!(count, 35);
+        let count = items.multiset_combinations::<3>(&[usize::MAX; 5]).count();
+        assert_eq!(count, 35);
+        let count = items.multiset_combinations::<3>(&[2; 5]).count();
+        assert_eq!(count, 30);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1, 2].multiset_combinations::<4>(&[2, 1]);
+        assert_eq!(combinations.next(), None);
+        let mut combinations = [0u8; 0].multiset_combinations::<1>(&[]);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].multiset_combinations::<0>(&[0, 0]);
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "lengths differ")]
+    fn lengths_differ() {
+        let _ = [1, 2].multiset_combinations::<1>(&[1]);
+    }
+}
@@ -0,0 +1,227 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyPrenecklaceGenerator<const N: usize> {
+    word: [usize; N],
+    // Length of the longest Lyndon prefix of `word`
+    period: usize,
+    k: usize,
+    done: bool,
+}
+
+impl<const N: usize> LazyPrenecklaceGenerator<N> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            word: [0; N],
+            period: 1,
+            k,
+            done: N > 0 && k == 0,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn word(&self) -> &[usize; N] {
+        &self.word
+    }
+
+    pub fn is_necklace(&self) -> bool {
+        N.is_multiple_of(self.period)
+    }
+
+    pub fn is_lyndon_word(&self) -> bool {
+        N > 0 && self.period == N
+    }
+
+    pub fn step(&mut self) {
+        // Fredricksen-Kessler-Maiorana: bump the last symbol which can still be
+        // incremented, and repeat the resulting prefix over the rest of the word
+        match self.word.iter().rposition(|&symbol| symbol + 1 < self.k) {
+            Some(i) => {
+                self.word[i] += 1;
+                for j in i + 1..N {
+                    self.word[j] = self.word[j - i - 1];
+                }
+                self.period = i + 1;
+            }
+            None => self.done = true,
+        }
+    }
+}
+
+/// Return an iterator over the necklaces of length `N` over an alphabet of `k`
+/// symbols.
+///
+/// A necklace is the lexicographically smallest word among all of its rotations,
+/// so every class of words which are equal under rotation is produced exactly
+/// once. Symbols are represented as `0..k`, and necklaces are produced in
+/// lexicographic order using the Fredricksen-Kessler-Maiorana algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::necklaces;
+///
+/// let mut necklaces = necklaces::<4>(2);
+/// assert_eq!(necklaces.next(), Some([0, 0, 0, 0]));
+/// assert_eq!(necklaces.next(), Some([0, 0, 0, 1]));
+/// assert_eq!(necklaces.next(), Some([0, 0, 1, 1]));
+/// assert_eq!(necklaces.next(), Some([0, 1, 0, 1]));
+/// assert_eq!(necklaces.next(), Some([0, 1, 1, 1]));
+/// assert_eq!(necklaces.next(), Some([1, 1, 1, 1]));
+/// assert_eq!(necklaces.next(), None);
+/// ```
+pub fn necklaces<const N: usize>(k: usize) -> Necklaces<N> {
+    Necklaces::new(k)
+}
+
+/// An iterator that returns the necklaces of a given length.
+///
+/// This `struct` is created by the [`necklaces`] function. See its documentation
+/// for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Necklaces<const N: usize> {
+    gen: LazyPrenecklaceGenerator<N>,
+}
+
+impl<const N: usize> Necklaces<N> {
+    pub(crate) fn new(k: usize) -> Self {
+        Self {
+            gen: LazyPrenecklaceGenerator::new(k),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for Necklaces<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        while !self.gen.is_done() {
+            let res = self.gen.is_necklace().then(|| *self.gen.word());
+            self.gen.step();
+            if res.is_some() {
+                return res;
+            }
+        }
+        None
+    }
+}
+
+impl<const N: usize> FusedIterator for Necklaces<N> {}
+
+/// Return an iterator over the Lyndon words of length `N` over an alphabet of `k`
+/// symbols.
+///
+/// A Lyndon word is a necklace which is strictly smaller than all of its
+/// rotations, meaning it is not periodic. Symbols are represented as `0..k`, and
+/// Lyndon words are produced in lexicographic order using the
+/// Fredricksen-Kessler-Maiorana algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::lyndon_words;
+///
+/// let mut words = lyndon_words::<4>(2);
+/// assert_eq!(words.next(), Some([0, 0, 0, 1]));
+/// assert_eq!(words.next(), Some([0, 0, 1, 1]));
+/// assert_eq!(words.next(), Some([0, 1, 1, 1]));
+/// assert_eq!(words.next(), None);
+/// ```
+pub fn lyndon_words<const N: usize>(k: usize) -> LyndonWords<N> {
+    LyndonWords::new(k)
+}
+
+/// An iterator that returns the Lyndon words of a given length.
+///
+/// This `struct` is created by the [`lyndon_words`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct LyndonWords<const N: usize> {
+    gen: LazyPrenecklaceGenerator<N>,
+}
+
+impl<const N: usize> LyndonWords<N> {
+    pub(crate) fn new(k: usize) -> Self {
+        Self {
+            gen: LazyPrenecklaceGenerator::new(k),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for LyndonWords<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        while !self.gen.is_done() {
+            let res = self.gen.is_lyndon_word().then(|| *self.gen.word());
+            self.gen.step();
+            if res.is_some() {
+                return res;
+            }
+        }
+        None
+    }
+}
+
+impl<const N: usize> FusedIterator for LyndonWords<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn necklace_count() {
+        // https://oeis.org/A000031
+        assert_eq!(necklaces::<1>(2).count(), 2);
+        assert_eq!(necklaces::<6>(2).count(), 14);
+        assert_eq!(necklaces::<8>(2).count(), 36);
+        // https://oeis.org/A001867
+        assert_eq!(necklaces::<4>(3).count(), 24);
+    }
+
+    #[test]
+    fn lyndon_count() {
+        // https://oeis.org/A001037
+        assert_eq!(lyndon_words::<1>(2).count(), 2);
+        assert_eq!(lyndon_words::<6>(2).count(), 9);
+        assert_eq!(lyndon_words::<8>(2).count(), 30);
+    }
+
+    #[test]
+    fn necklaces_are_minimal_rotations() {
+        for word in necklaces::<6>(3) {
+            for r in 1..6 {
+                let rotated: [usize; 6] = core::array::from_fn(|i| word[(i + r) % 6]);
+                assert!(word <= rotated);
+            }
+        }
+    }
+
+    #[test]
+    fn single_symbol() {
+        let mut necklaces = necklaces::<3>(1);
+        assert_eq!(necklaces.next(), Some([0, 0, 0]));
+        assert_eq!(necklaces.next(), None);
+        assert_eq!(lyndon_words::<3>(1).next(), None);
+    }
+
+    #[test]
+    fn none_on_empty_alphabet() {
+        let mut necklaces = necklaces::<3>(0);
+        assert_eq!(necklaces.next(), None);
+        assert_eq!(necklaces.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut necklaces = necklaces::<0>(2);
+        assert_eq!(necklaces.next(), Some([]));
+        assert_eq!(necklaces.next(), None);
+        assert_eq!(lyndon_words::<0>(2).next(), None);
+    }
+}
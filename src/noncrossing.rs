@@ -0,0 +1,186 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyNonCrossingGenerator<const N: usize> {
+    // Restricted growth string: `blocks[i]` is the block containing element `i`
+    blocks: [usize; N],
+    done: bool,
+}
+
+impl<const N: usize> LazyNonCrossingGenerator<N> {
+    pub fn new() -> Self {
+        Self {
+            blocks: [0; N],
+            done: false,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn blocks(&self) -> &[usize; N] {
+        &self.blocks
+    }
+
+    /// Collect the blocks which elements `end..` can still join without
+    /// crossing, innermost last, and return how many of them there are along
+    /// with the number of blocks in `blocks[..end]`.
+    fn open_blocks(&self, end: usize, open: &mut [usize; N]) -> (usize, usize) {
+        let mut len = 0;
+        let mut count = 0;
+        for &block in &self.blocks[..end] {
+            if block == count {
+                open[len] = block;
+                len += 1;
+                count += 1;
+            } else {
+                // Every block opened since is now enclosed by `block`
+                while open[len - 1] != block {
+                    len -= 1;
+                }
+            }
+        }
+        (len, count)
+    }
+
+    pub fn step(&mut self) {
+        let mut open = [0; N];
+        // Find the last element which can move to a later block, either one of
+        // the open ones or a brand new one
+        let mut i = N;
+        while i > 1 {
+            i -= 1;
+            let (len, count) = self.open_blocks(i, &mut open);
+            let block = self.blocks[i];
+            let next = open[..len].iter().copied().find(|&b| b > block);
+            if let Some(next) = next.or(Some(count).filter(|&c| c > block)) {
+                self.blocks[i] = next;
+                // The first block stays open throughout, so everything after
+                // can join it
+                self.blocks[i + 1..].fill(0);
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+/// Return an iterator over the non-crossing partitions of `N` points.
+///
+/// With the points `0..N` placed in order on a line or around a circle, a
+/// partition is non-crossing if no two of its blocks interleave: there are no
+/// `a < b < c < d` with `a` and `c` in one block and `b` and `d` in another.
+///
+/// Each partition is yielded as a block-assignment array in the same form and
+/// order as [`set_partitions`], skipping every crossing partition without
+/// generating it. There are as many of them as the `N`th Catalan number, the
+/// same as the Dyck words from [`dyck_words`] of length `2 * N`.
+///
+/// [`set_partitions`]: crate::set_partitions
+/// [`dyck_words`]: crate::dyck_words
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::noncrossing_partitions;
+///
+/// let mut partitions = noncrossing_partitions::<4>();
+/// assert_eq!(partitions.next(), Some([0, 0, 0, 0]));
+/// assert_eq!(partitions.nth(4), Some([0, 1, 0, 0]));
+/// // [0, 1, 0, 1] is skipped since {0, 2} and {1, 3} cross
+/// assert_eq!(partitions.next(), Some([0, 1, 0, 2]));
+/// assert_eq!(partitions.count(), 7);
+/// ```
+#[doc(alias = "non_crossing_partitions")]
+pub fn noncrossing_partitions<const N: usize>() -> NonCrossingPartitions<N> {
+    NonCrossingPartitions::new()
+}
+
+/// An iterator that returns the non-crossing partitions of a set as
+/// block-assignment arrays.
+///
+/// This `struct` is created by the [`noncrossing_partitions`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct NonCrossingPartitions<const N: usize> {
+    gen: LazyNonCrossingGenerator<N>,
+}
+
+impl<const N: usize> NonCrossingPartitions<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyNonCrossingGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for NonCrossingPartitions<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.blocks();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for NonCrossingPartitions<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::set_partitions;
+
+    fn crossing(blocks: &[usize]) -> bool {
+        let n = blocks.len();
+        (0..n).any(|a| {
+            (a + 1..n).any(|b| {
+                (b + 1..n).any(|c| {
+                    (c + 1..n).any(|d| {
+                        blocks[a] == blocks[c] && blocks[b] == blocks[d] && blocks[a] != blocks[b]
+                    })
+                })
+            })
+        })
+    }
+
+    #[test]
+    fn order() {
+        let mut partitions = noncrossing_partitions::<3>();
+        assert_eq!(partitions.next(), Some([0, 0, 0]));
+        assert_eq!(partitions.next(), Some([0, 0, 1]));
+        assert_eq!(partitions.next(), Some([0, 1, 0]));
+        assert_eq!(partitions.next(), Some([0, 1, 1]));
+        assert_eq!(partitions.next(), Some([0, 1, 2]));
+        assert_eq!(partitions.next(), None);
+        assert_eq!(partitions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000108
+        assert_eq!(noncrossing_partitions::<1>().count(), 1);
+        assert_eq!(noncrossing_partitions::<4>().count(), 14);
+        assert_eq!(noncrossing_partitions::<5>().count(), 42);
+        assert_eq!(noncrossing_partitions::<8>().count(), 1430);
+    }
+
+    #[test]
+    fn matches_filtered_set_partitions() {
+        let filtered = set_partitions::<7>().filter(|blocks| !crossing(blocks));
+        assert!(noncrossing_partitions::<7>().eq(filtered));
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut partitions = noncrossing_partitions::<0>();
+        assert_eq!(partitions.next(), Some([]));
+        assert_eq!(partitions.next(), None);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::combinations::State;
+use crate::counting::size_hint;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+use core::num::NonZero;
+
+mod sealed {
+    /// Owned collections of items which combinations can be taken from.
+    pub trait Storage {
+        type Item;
+
+        fn as_slice(&self) -> &[Self::Item];
+    }
+}
+
+pub(crate) use sealed::Storage;
+
+impl<T, const N: usize> Storage for [T; N] {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Storage for Vec<T> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+/// An iterator that returns k-length combinations of the values it owns.
+///
+/// The values are kept in the array or `Vec` they came in, so an array stays
+/// on the stack and a `Vec` isn't reallocated.
+///
+/// This `struct` is created by the [`into_combinations`] methods on
+/// [`ArrayExt`] and [`VecExt`]. See their documentation for more.
+///
+/// [`into_combinations`]: super::ArrayExt::into_combinations
+/// [`ArrayExt`]: super::ArrayExt
+/// [`VecExt`]: super::VecExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct IntoCombinations<S, const K: usize> {
+    items: S,
+    state: State<K>,
+}
+
+impl<S, const K: usize> IntoCombinations<S, K>
+where
+    S: Storage,
+{
+    pub(crate) fn new(items: S) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Return the values the combinations are taken from.
+    pub fn items(&self) -> &[S::Item] {
+        self.items.as_slice()
+    }
+
+    /// Return the storage the values are kept in, such as the original array.
+    pub fn into_inner(self) -> S {
+        self.items
+    }
+
+    /// Return the exact number of combinations left as a `u128`.
+    ///
+    /// See [`SliceCombinations::remaining_len`] for more.
+    ///
+    /// [`SliceCombinations::remaining_len`]: crate::SliceCombinations::remaining_len
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.as_slice().len())
+    }
+
+    /// Return `true` if there are no combinations left.
+    ///
+    /// See [`SliceCombinations::is_empty`] for more.
+    ///
+    /// [`SliceCombinations::is_empty`]: crate::SliceCombinations::is_empty
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.as_slice().len())
+    }
+}
+
+impl<S, const K: usize> Iterator for IntoCombinations<S, K>
+where
+    S: Storage,
+    S::Item: Clone,
+{
+    type Item = [S::Item; K];
+
+    fn next(&mut self) -> Option<[S::Item; K]> {
+        self.state
+            .get_and_step(self.items.as_slice(), S::Item::clone)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.as_slice().len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.as_slice().len(), n)
+    }
+}
+
+impl<S, const K: usize> FusedIterator for IntoCombinations<S, K>
+where
+    S: Storage,
+    S::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ArrayExt, IterExt, VecExt};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn matches_iterator() {
+        let array = [1, 2, 3, 4, 5];
+        assert!(array
+            .into_combinations::<3>()
+            .eq((1..6).combinations::<3>()));
+        let strings: Vec<String> = ["a", "b", "c", "d"].map(String::from).into();
+        let expected: Vec<_> = strings.clone().into_iter().combinations::<2>().collect();
+        assert!(strings.into_combinations::<2>().eq(expected));
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut combinations = vec![0u8; 6].into_combinations::<3>();
+        for len in (0..=20).rev() {
+            assert_eq!(combinations.size_hint(), (len, Some(len)));
+            combinations.next();
+        }
+        assert_eq!([1, 2].into_combinations::<3>().size_hint(), (0, Some(0)));
+        assert!([1, 2].into_combinations::<3>().is_empty());
+        assert!(combinations.is_empty());
+        let items: Vec<u32> = (0..200).collect();
+        assert_eq!(items.into_combinations::<100>().remaining_len(), None);
+        let mut combinations = [1, 2, 3, 4].into_combinations::<2>();
+        assert_eq!(combinations.nth(4), Some([2, 4]));
+        assert_eq!(combinations.next(), Some([3, 4]));
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn into_inner() {
+        let mut combinations = vec!['x', 'y'].into_combinations::<1>();
+        combinations.next();
+        assert_eq!(combinations.items(), ['x', 'y']);
+        assert_eq!(combinations.into_inner(), vec!['x', 'y']);
+    }
+}
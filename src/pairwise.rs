@@ -0,0 +1,155 @@
+use crate::product::mixed_radix;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Return a small set of assignments to `N` parameters which together contain
+/// every pair of values of any two parameters.
+///
+/// Each assignment holds, for every parameter, the index of a value in the
+/// corresponding slice. The assignments are built greedily in the style of
+/// AETG: every new assignment starts from a pair which isn't covered yet, and
+/// picks the value for each of the remaining parameters which covers the most
+/// new pairs. This is usually far smaller than the full product of the
+/// parameters, but not guaranteed to be the smallest possible set.
+///
+/// With fewer than two parameters there are no pairs to cover, and every value
+/// is used once instead. If any of the slices is empty there are no
+/// assignments.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::pairwise;
+///
+/// let os = ["linux", "macos", "windows"];
+/// let arch = ["x86", "arm"];
+/// let build = ["debug", "release"];
+/// let tests = pairwise([&os[..], &arch, &build]);
+/// assert!(tests.len() < 3 * 2 * 2);
+///
+/// // Every os runs on every arch
+/// for o in 0..os.len() {
+///     for a in 0..arch.len() {
+///         assert!(tests.iter().any(|t| t[0] == o && t[1] == a));
+///     }
+/// }
+/// ```
+pub fn pairwise<T, const N: usize>(params: [&[T]; N]) -> Vec<[usize; N]> {
+    let sizes = params.map(|param| param.len());
+    if N < 2 || sizes.contains(&0) {
+        return mixed_radix(sizes).collect();
+    }
+
+    // One table of uncovered value pairs for every pair of parameters `a < b`,
+    // indexed by `value_a * sizes[b] + value_b`
+    let mut uncovered: Vec<Vec<bool>> = Vec::new();
+    for a in 0..N {
+        for b in a + 1..N {
+            uncovered.push(vec![true; sizes[a] * sizes[b]]);
+        }
+    }
+    let table = |a: usize, b: usize| a * (2 * N - a - 1) / 2 + (b - a - 1);
+    let mut remaining: usize = uncovered.iter().map(Vec::len).sum();
+
+    let mut res = Vec::new();
+    while remaining > 0 {
+        // Seed the assignment with the first pair which isn't covered yet
+        let mut row = [0; N];
+        let mut assigned = [false; N];
+        'seed: for a in 0..N {
+            for b in a + 1..N {
+                if let Some(i) = uncovered[table(a, b)].iter().position(|&u| u) {
+                    row[a] = i / sizes[b];
+                    row[b] = i % sizes[b];
+                    assigned[a] = true;
+                    assigned[b] = true;
+                    break 'seed;
+                }
+            }
+        }
+
+        // Pick the value which covers the most new pairs for every other parameter
+        for c in 0..N {
+            if assigned[c] {
+                continue;
+            }
+            let gain = |value: usize| {
+                (0..N)
+                    .filter(|&p| assigned[p])
+                    .filter(|&p| {
+                        let (a, va, b, vb) = if p < c {
+                            (p, row[p], c, value)
+                        } else {
+                            (c, value, p, row[p])
+                        };
+                        uncovered[table(a, b)][va * sizes[b] + vb]
+                    })
+                    .count()
+            };
+            // Prefer the lowest value on ties
+            row[c] = (0..sizes[c])
+                .rev()
+                .max_by_key(|&value| gain(value))
+                .unwrap_or(0);
+            assigned[c] = true;
+        }
+
+        for a in 0..N {
+            for b in a + 1..N {
+                let covered = &mut uncovered[table(a, b)][row[a] * sizes[b] + row[b]];
+                if *covered {
+                    *covered = false;
+                    remaining -= 1;
+                }
+            }
+        }
+        res.push(row);
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn covers_all_pairs<const N: usize>(sizes: [usize; N], rows: &[[usize; N]]) -> bool {
+        (0..N).all(|a| {
+            (a + 1..N).all(|b| {
+                mixed_radix([sizes[a], sizes[b]])
+                    .all(|[va, vb]| rows.iter().any(|row| row[a] == va && row[b] == vb))
+            })
+        })
+    }
+
+    #[test]
+    fn covers_pairs() {
+        let rows = pairwise([&[(); 2][..], &[(); 2], &[(); 2]]);
+        assert!(covers_all_pairs([2, 2, 2], &rows));
+        assert!(rows.len() < 8);
+
+        let rows = pairwise([&[(); 3][..], &[(); 3], &[(); 3], &[(); 3]]);
+        assert!(covers_all_pairs([3, 3, 3, 3], &rows));
+        assert!(rows.len() < 27);
+
+        let rows = pairwise([&[(); 4][..], &[(); 1], &[(); 3], &[(); 2], &[(); 5]]);
+        assert!(covers_all_pairs([4, 1, 3, 2, 5], &rows));
+        assert!(rows.len() < 4 * 3 * 2 * 5);
+    }
+
+    #[test]
+    fn two_params_is_product() {
+        let rows = pairwise([&[1, 2, 3][..], &[4, 5]]);
+        assert_eq!(rows, [[0, 0], [0, 1], [1, 0], [1, 1], [2, 0], [2, 1]]);
+    }
+
+    #[test]
+    fn empty_on_empty_param() {
+        assert!(pairwise([&[1, 2][..], &[], &[3]]).is_empty());
+    }
+
+    #[test]
+    fn fewer_than_two_params() {
+        assert_eq!(pairwise([&[1, 2, 3][..]]), [[0], [1], [2]]);
+        assert_eq!(pairwise::<u8, 0>([]), [[]]);
+    }
+}
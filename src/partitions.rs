@@ -0,0 +1,118 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over the partitions of the integer `n`.
+///
+/// Each partition is yielded as a `Vec` of its parts in non-increasing order, and
+/// the partitions themselves are produced in reverse lexicographic order. The
+/// integer `0` has exactly one partition: the empty one.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::partitions;
+///
+/// let mut partitions = partitions(4);
+/// assert_eq!(partitions.next(), Some(vec![4]));
+/// assert_eq!(partitions.next(), Some(vec![3, 1]));
+/// assert_eq!(partitions.next(), Some(vec![2, 2]));
+/// assert_eq!(partitions.next(), Some(vec![2, 1, 1]));
+/// assert_eq!(partitions.next(), Some(vec![1, 1, 1, 1]));
+/// assert_eq!(partitions.next(), None);
+/// ```
+pub fn partitions(n: usize) -> Partitions {
+    Partitions::new(n)
+}
+
+/// An iterator that returns the partitions of an integer.
+///
+/// This `struct` is created by the [`partitions`] function. See its documentation
+/// for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Partitions {
+    parts: Vec<usize>,
+    done: bool,
+}
+
+impl Partitions {
+    pub(crate) fn new(n: usize) -> Self {
+        Self {
+            parts: if n == 0 { Vec::new() } else { vec![n] },
+            done: false,
+        }
+    }
+
+    fn step(&mut self) {
+        // Find the last part which can still be split up
+        let k = match self.parts.iter().rposition(|&part| part > 1) {
+            Some(k) => k,
+            None => {
+                self.done = true;
+                return;
+            }
+        };
+        // Everything after `k` is a one, so those are redistributed along with
+        // `parts[k]` itself using parts no larger than `parts[k] - 1`
+        let mut rest = self.parts[k] + self.parts.len() - k - 1;
+        let part = self.parts[k] - 1;
+        self.parts.truncate(k);
+        while rest > 0 {
+            let next = part.min(rest);
+            self.parts.push(next);
+            rest -= next;
+        }
+    }
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            None
+        } else {
+            let res = self.parts.clone();
+            self.step();
+            Some(res)
+        }
+    }
+}
+
+impl FusedIterator for Partitions {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut partitions = partitions(5);
+        assert_eq!(partitions.next(), Some(vec![5]));
+        assert_eq!(partitions.next(), Some(vec![4, 1]));
+        assert_eq!(partitions.next(), Some(vec![3, 2]));
+        assert_eq!(partitions.next(), Some(vec![3, 1, 1]));
+        assert_eq!(partitions.next(), Some(vec![2, 2, 1]));
+        assert_eq!(partitions.next(), Some(vec![2, 1, 1, 1]));
+        assert_eq!(partitions.next(), Some(vec![1, 1, 1, 1, 1]));
+        assert_eq!(partitions.next(), None);
+        assert_eq!(partitions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000041
+        let counts = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42];
+        for (n, &count) in counts.iter().enumerate() {
+            assert_eq!(partitions(n).count(), count);
+        }
+    }
+
+    #[test]
+    fn empty_partition_on_zero() {
+        let mut partitions = partitions(0);
+        assert_eq!(partitions.next(), Some(vec![]));
+        assert_eq!(partitions.next(), None);
+    }
+}
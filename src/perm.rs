@@ -0,0 +1,470 @@
+use crate::make_array;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+use core::iter::Iterator;
+
+/// Whether a permutation takes an even or odd number of swaps to build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Parity {
+    /// An even number of swaps, like the identity.
+    Even,
+    /// An odd number of swaps.
+    Odd,
+}
+
+impl Parity {
+    /// Return `1` for even and `-1` for odd permutations, which is the factor a
+    /// permutation contributes to a determinant.
+    pub fn sign(self) -> i8 {
+        match self {
+            Parity::Even => 1,
+            Parity::Odd => -1,
+        }
+    }
+
+    /// Return the parity of a permutation built with `swaps` swaps.
+    pub(crate) fn of_swaps(swaps: usize) -> Self {
+        if swaps.is_multiple_of(2) {
+            Parity::Even
+        } else {
+            Parity::Odd
+        }
+    }
+}
+
+/// A permutation of `N` positions.
+///
+/// A `Perm` holds, for every position of its output, the position of the input
+/// it takes its value from. Applying `[2, 0, 1]` to `['a', 'b', 'c']` gives
+/// `['c', 'a', 'b']`. This is the same convention as the index arrays produced
+/// when permuting `0..N`, so those can be turned into a `Perm` directly.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::Perm;
+///
+/// let rotate = Perm::new([2, 0, 1]).unwrap();
+/// assert_eq!(rotate.apply_to(&['a', 'b', 'c']), [&'c', &'a', &'b']);
+/// assert_eq!(rotate.compose(&rotate.inverse()), Perm::identity());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Perm<const N: usize> {
+    indices: [usize; N],
+}
+
+impl<const N: usize> Perm<N> {
+    /// Create a permutation from the position each output takes its value from.
+    ///
+    /// Returns `None` if `indices` isn't a permutation of `0..N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::Perm;
+    ///
+    /// assert!(Perm::new([1, 0, 2]).is_some());
+    /// assert!(Perm::new([1, 1, 2]).is_none());
+    /// assert!(Perm::new([1, 3, 2]).is_none());
+    /// ```
+    pub fn new(indices: [usize; N]) -> Option<Self> {
+        let mut seen = [false; N];
+        for &i in &indices {
+            if i >= N || seen[i] {
+                return None;
+            }
+            seen[i] = true;
+        }
+        Some(Self { indices })
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Create a permutation from indices which are known to be a permutation of
+    /// `0..N`.
+    pub(crate) fn new_unchecked(indices: [usize; N]) -> Self {
+        debug_assert!(Self::new(indices).is_some());
+        Self { indices }
+    }
+
+    /// Return the permutation which leaves every position in place.
+    pub fn identity() -> Self {
+        Self {
+            indices: make_array(|i| i),
+        }
+    }
+
+    /// Return the position each output takes its value from.
+    pub fn as_array(&self) -> &[usize; N] {
+        &self.indices
+    }
+
+    /// Return the permutation which applies `self` first and then `other`.
+    ///
+    /// Since a `Perm` maps output positions to input positions, the resulting
+    /// indices are `self[other[i]]`, and
+    /// `self.compose(&other).apply_to(x)` equals
+    /// `other.apply_to(&self.apply_to(x))` up to references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::Perm;
+    ///
+    /// let swap = Perm::new([1, 0, 2]).unwrap();
+    /// let rotate = Perm::new([2, 0, 1]).unwrap();
+    /// assert_eq!(swap.compose(&rotate).as_array(), &[2, 1, 0]);
+    /// assert_eq!(rotate.compose(&swap).as_array(), &[0, 2, 1]);
+    /// ```
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            indices: make_array(|i| self.indices[other.indices[i]]),
+        }
+    }
+
+    /// Return the permutation which undoes this one.
+    pub fn inverse(&self) -> Self {
+        let mut indices = [0; N];
+        invert(&self.indices, &mut indices);
+        Self { indices }
+    }
+
+    /// Rearrange `items` according to this permutation.
+    ///
+    /// This returns references to the elements of `items`, with the element at
+    /// position `self[i]` ending up at position `i`.
+    pub fn apply_to<'a, T>(&self, items: &'a [T; N]) -> [&'a T; N] {
+        make_array(|i| &items[self.indices[i]])
+    }
+
+    /// Rearrange `items` in place according to this permutation.
+    ///
+    /// This moves the element at position `self[i]` to position `i`, like
+    /// [`apply_to`] does, by swapping elements along each cycle. Nothing is
+    /// allocated and the elements are never cloned.
+    ///
+    /// [`apply_to`]: Perm::apply_to
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::Perm;
+    ///
+    /// let mut items = ['a', 'b', 'c', 'd'];
+    /// Perm::new([2, 0, 3, 1]).unwrap().apply_in_place(&mut items);
+    /// assert_eq!(items, ['c', 'a', 'd', 'b']);
+    /// ```
+    pub fn apply_in_place<T>(&self, items: &mut [T; N]) {
+        let mut done = [false; N];
+        for start in 0..N {
+            if done[start] {
+                continue;
+            }
+            // Pull every element of the cycle one step forward, which leaves the
+            // element from the start at the end of the cycle
+            let mut i = start;
+            done[i] = true;
+            while self.indices[i] != start {
+                let j = self.indices[i];
+                items.swap(i, j);
+                done[j] = true;
+                i = j;
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return an iterator over the disjoint cycles of this permutation.
+    ///
+    /// Each cycle starts at its smallest position `i` and lists `i`, `self[i]`,
+    /// `self[self[i]]` and so on. Cycles are ordered by their first position, and
+    /// positions which stay in place are included as cycles of length one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::Perm;
+    ///
+    /// let perm = Perm::new([2, 1, 3, 0]).unwrap();
+    /// let mut cycles = perm.cycles();
+    /// assert_eq!(cycles.next(), Some(vec![0, 2, 3]));
+    /// assert_eq!(cycles.next(), Some(vec![1]));
+    /// assert_eq!(cycles.next(), None);
+    /// ```
+    pub fn cycles(&self) -> Cycles<N> {
+        Cycles::new(*self)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return whether this permutation is even or odd.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{Parity, Perm};
+    ///
+    /// assert_eq!(Perm::new([1, 0, 2]).unwrap().parity(), Parity::Odd);
+    /// assert_eq!(Perm::new([1, 2, 0]).unwrap().parity(), Parity::Even);
+    /// ```
+    pub fn parity(&self) -> Parity {
+        // Every cycle of length `l` takes `l - 1` swaps
+        Parity::of_swaps(N - self.cycles().count())
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return the lengths of the cycles of this permutation, from longest to
+    /// shortest.
+    ///
+    /// The lengths form a partition of `N`, and two permutations are conjugate
+    /// exactly when they have the same cycle type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::Perm;
+    ///
+    /// let perm = Perm::new([2, 1, 3, 0, 5, 4]).unwrap();
+    /// assert_eq!(perm.cycle_type(), [3, 2, 1]);
+    /// ```
+    pub fn cycle_type(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.cycles().map(|cycle| cycle.len()).collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An iterator that returns the cycles of a permutation.
+///
+/// This `struct` is created by the [`cycles`] method on [`Perm`]. See its
+/// documentation for more.
+///
+/// [`cycles`]: Perm::cycles
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Cycles<const N: usize> {
+    perm: Perm<N>,
+    seen: [bool; N],
+    start: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> Cycles<N> {
+    pub(crate) fn new(perm: Perm<N>) -> Self {
+        Self {
+            perm,
+            seen: [false; N],
+            start: 0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> Iterator for Cycles<N> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        // Find the smallest position which isn't part of a cycle yet
+        while self.start < N && self.seen[self.start] {
+            self.start += 1;
+        }
+        if self.start == N {
+            return None;
+        }
+        let mut cycle = Vec::new();
+        let mut i = self.start;
+        while !self.seen[i] {
+            self.seen[i] = true;
+            cycle.push(i);
+            i = self.perm.indices[i];
+        }
+        Some(cycle)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> FusedIterator for Cycles<N> {}
+
+/// Rearrange `items` in place so that the element at position `indices[i]` ends
+/// up at position `i`.
+///
+/// This is [`Perm::apply_in_place`] for plain index arrays, like the ones produced
+/// by permuting `0..N`.
+///
+/// # Panics
+///
+/// Panics if `indices` isn't a permutation of `0..N`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::{permute_by, SliceExt};
+///
+/// let mut items = ["x", "y", "z"];
+/// let arrangement = [0, 1, 2].permutations_copied().nth(2).unwrap();
+/// permute_by(&mut items, arrangement);
+/// assert_eq!(items, ["z", "x", "y"]);
+/// ```
+pub fn permute_by<T, const N: usize>(items: &mut [T; N], indices: [usize; N]) {
+    Perm::new(indices)
+        .expect("indices are not a permutation")
+        .apply_in_place(items)
+}
+
+/// Write the inverse of the permutation `indices` into `out`.
+///
+/// After this `out[indices[i]] == i` for every `i`, so `out` undoes the
+/// arrangement described by `indices`. This takes linear time and doesn't
+/// allocate, which also makes it usable for index slices whose length is only
+/// known at runtime.
+///
+/// # Panics
+///
+/// Panics if `indices` and `out` have different lengths, or if `indices` isn't a
+/// permutation of `0..indices.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::{invert, permute_by};
+///
+/// let arrangement = [2, 0, 3, 1];
+/// let mut undo = [0; 4];
+/// invert(&arrangement, &mut undo);
+/// assert_eq!(undo, [1, 3, 0, 2]);
+///
+/// let mut items = ['a', 'b', 'c', 'd'];
+/// permute_by(&mut items, arrangement);
+/// permute_by(&mut items, undo);
+/// assert_eq!(items, ['a', 'b', 'c', 'd']);
+/// ```
+pub fn invert(indices: &[usize], out: &mut [usize]) {
+    assert_eq!(indices.len(), out.len(), "lengths differ");
+    // Positions which haven't been written yet hold an impossible index
+    out.fill(usize::MAX);
+    for (i, &j) in indices.iter().enumerate() {
+        assert!(
+            j < out.len() && out[j] == usize::MAX,
+            "indices are not a permutation"
+        );
+        out[j] = i;
+    }
+}
+
+impl<const N: usize> Default for Perm<N> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<const N: usize> From<Perm<N>> for [usize; N] {
+    fn from(perm: Perm<N>) -> Self {
+        perm.indices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SliceExt;
+
+    #[test]
+    fn group_laws() {
+        let indices: [usize; 4] = make_array(|i| i);
+        let perms: alloc::vec::Vec<Perm<4>> = indices
+            .permutations_copied::<4>()
+            .map(|p| Perm::new(p).unwrap())
+            .collect();
+        let id = Perm::identity();
+        for a in &perms {
+            assert_eq!(a.compose(&id), *a);
+            assert_eq!(id.compose(a), *a);
+            assert_eq!(a.compose(&a.inverse()), id);
+            assert_eq!(a.inverse().compose(a), id);
+            for b in &perms {
+                let items = ['a', 'b', 'c', 'd'];
+                let applied = b.apply_to(&a.apply_to(&items)).map(|&&c| c);
+                assert_eq!(a.compose(b).apply_to(&items).map(|&c| c), applied);
+                for c in &perms {
+                    assert_eq!(a.compose(b).compose(c), a.compose(&b.compose(c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validates() {
+        assert_eq!(Perm::new([0, 1, 2]), Some(Perm::identity()));
+        assert_eq!(Perm::new([0, 0, 2]), None);
+        assert_eq!(Perm::new([0, 1, 3]), None);
+        assert_eq!(Perm::<0>::new([]), Some(Perm::identity()));
+    }
+
+    #[test]
+    fn cycles() {
+        let perm = Perm::new([1, 2, 0, 4, 3, 5]).unwrap();
+        let cycles: alloc::vec::Vec<_> = perm.cycles().collect();
+        assert_eq!(cycles, [&[0, 1, 2][..], &[3, 4], &[5]]);
+        assert_eq!(perm.cycle_type(), [3, 2, 1]);
+        assert_eq!(Perm::<3>::identity().cycle_type(), [1, 1, 1]);
+        assert_eq!(Perm::<0>::identity().cycles().next(), None);
+    }
+
+    #[test]
+    fn cycle_type_counts() {
+        // https://oeis.org/A000166: derangements have no cycles of length one
+        let indices: [usize; 5] = make_array(|i| i);
+        let derangements = indices
+            .permutations_copied::<5>()
+            .filter(|&p| !Perm::new(p).unwrap().cycle_type().contains(&1))
+            .count();
+        assert_eq!(derangements, 44);
+        // Inverses have the same cycle type
+        for p in indices.permutations_copied::<5>() {
+            let perm = Perm::new(p).unwrap();
+            assert_eq!(perm.cycle_type(), perm.inverse().cycle_type());
+        }
+    }
+
+    #[test]
+    fn apply_in_place() {
+        let indices: [usize; 5] = make_array(|i| i);
+        let items = ['a', 'b', 'c', 'd', 'e'];
+        for p in indices.permutations_copied::<5>() {
+            let mut permuted = items;
+            permute_by(&mut permuted, p);
+            assert_eq!(permuted, p.map(|i| items[i]));
+        }
+        let mut empty: [u8; 0] = [];
+        permute_by(&mut empty, []);
+    }
+
+    #[test]
+    #[should_panic(expected = "indices are not a permutation")]
+    fn permute_by_invalid() {
+        permute_by(&mut [1, 2], [1, 1]);
+    }
+
+    #[test]
+    fn invert_slices() {
+        let mut out = [0; 5];
+        invert(&[4, 2, 0, 1, 3], &mut out);
+        assert_eq!(out, [2, 3, 1, 4, 0]);
+        invert(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "indices are not a permutation")]
+    fn invert_invalid() {
+        invert(&[0, 2, 2], &mut [0; 3]);
+    }
+
+    #[test]
+    fn apply_to() {
+        let rotate = Perm::new([1, 2, 0]).unwrap();
+        assert_eq!(rotate.apply_to(&[1, 2, 3]), [&2, &3, &1]);
+        assert_eq!(rotate.inverse().as_array(), &[2, 0, 1]);
+        assert_eq!(<[usize; 3]>::from(rotate), [1, 2, 0]);
+    }
+}
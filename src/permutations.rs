@@ -1,11 +1,35 @@
-use crate::{combinations::LazyCombinationGenerator, make_array};
+#[cfg(feature = "alloc")]
+use crate::boxed::{Boxed, BoxedSource};
+#[cfg(feature = "alloc")]
+use crate::buffer::{buffer_in, fill, into_arc, into_vec, Allocator, Buffer, Global};
+use crate::indexed::{IndexedSource, WithIndices};
+#[cfg(feature = "alloc")]
+use crate::make_boxed_array;
+use crate::perm::Parity;
+#[cfg(feature = "alloc")]
+use crate::streaming::StreamingPermutations;
+use crate::{
+    combinations::LazyCombinationGenerator,
+    counting::{factorial, factorial_u128, size_hint},
+    get_item, make_array,
+};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use core::iter::{FusedIterator, Iterator};
+use core::convert::Infallible;
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator, StepBy, Take};
+use core::num::NonZero;
+use core::ops::{Range, Try};
 
 #[derive(Clone)]
 pub struct LazyPermutationGenerator<const N: usize> {
     indices: [usize; N],
     counters: [usize; N],
+    // Number of leading indices which are never moved
+    fixed: usize,
     done: bool,
 }
 
@@ -14,10 +38,25 @@ impl<const N: usize> LazyPermutationGenerator<N> {
         Self {
             indices: make_array(|i| i),
             counters: [0; N],
+            fixed: 0,
             done: false,
         }
     }
 
+    pub fn fixing_first() -> Self {
+        Self {
+            fixed: N.min(1),
+            ..Self::new()
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self {
+            fixed: self.fixed,
+            ..Self::new()
+        };
+    }
+
     pub fn is_done(&self) -> bool {
         self.done
     }
@@ -26,46 +65,285 @@ impl<const N: usize> LazyPermutationGenerator<N> {
         &self.indices
     }
 
+    /// Return the number of permutations generated per combination.
+    pub fn len(&self) -> Option<usize> {
+        factorial(N - self.fixed)
+    }
+
+    /// Return the position of the current permutation in the iteration order.
+    pub fn rank(&self) -> usize {
+        // The counters of Heap's algorithm form a factorial number system
+        let mut weight = 1;
+        let mut rank = 0;
+        for (i, &counter) in self.counters[self.fixed..].iter().enumerate().skip(1) {
+            weight *= i;
+            rank += counter * weight;
+        }
+        rank
+    }
+
+    /// Return the number of steps taken from the first permutation, modulo 2.
+    pub fn rank_parity(&self) -> usize {
+        // Only the lowest counter has an odd weight, and the others would
+        // overflow the rank once there are more than 20 positions
+        self.counters[self.fixed..]
+            .get(1)
+            .map_or(0, |&counter| counter & 1)
+    }
+
+    /// Return the number of permutations generated per combination as a
+    /// `u128`.
+    pub fn len_u128(&self) -> Option<u128> {
+        factorial_u128(N - self.fixed)
+    }
+
+    /// Return the position of the current permutation as a `u128`, for when
+    /// the number of permutations doesn't fit in a `usize`.
+    pub fn rank_u128(&self) -> u128 {
+        let mut weight = 1;
+        let mut rank = 0;
+        for (i, &counter) in self.counters[self.fixed..].iter().enumerate().skip(1) {
+            weight *= i as u128;
+            rank += counter as u128 * weight;
+        }
+        rank
+    }
+
+    /// Jump to the permutation at `rank` in the iteration order, which has to
+    /// be below [`len`](Self::len).
+    pub fn seek(&mut self, rank: usize) {
+        self.reset();
+        let fixed = self.fixed;
+        let indices = &mut self.indices[fixed..];
+        let counters = &mut self.counters[fixed..];
+        // Read the counters off the rank, which is a factorial number
+        let mut rest = rank;
+        for (i, counter) in counters.iter_mut().enumerate().skip(1) {
+            *counter = rest % (i + 1);
+            rest /= i + 1;
+        }
+        // Replay the swaps from the outermost position in. Each swap comes
+        // after a full pass over the positions before it, so only the passes'
+        // end results are needed
+        for i in (1..indices.len()).rev() {
+            for t in 0..counters[i] {
+                heap_pass(&mut indices[..i]);
+                let j = if i & 1 == 0 { 0 } else { t };
+                indices.swap(i, j);
+            }
+        }
+    }
+
     pub fn step(&mut self) {
-        // Iterative version of Heap's algorithm
-        // https://en.wikipedia.org/wiki/Heap%27s_algorithm
-        let mut i = 1;
-        while i < N && self.counters[i] >= i {
-            self.counters[i] = 0;
-            i += 1;
-        }
-        if i < N {
-            if i & 1 == 0 {
-                self.indices.swap(i, 0);
-            } else {
-                self.indices.swap(i, self.counters[i]);
-            };
-            self.counters[i] += 1;
-        } else {
+        self.step_swap();
+    }
+
+    /// Advance to the next permutation, returning the positions which were
+    /// swapped to get there.
+    pub fn step_swap(&mut self) -> Option<(usize, usize)> {
+        let fixed = self.fixed;
+        let swap = heap_step(&mut self.indices[fixed..], &mut self.counters[fixed..]);
+        if swap.is_none() {
             self.done = true;
         }
+        swap.map(|(i, j)| (i + fixed, j + fixed))
+    }
+}
+
+/// Advance `indices` to the next permutation, returning the swapped positions,
+/// or `None` once all of them have been visited.
+pub(crate) const fn heap_step(
+    indices: &mut [usize],
+    counters: &mut [usize],
+) -> Option<(usize, usize)> {
+    // Iterative version of Heap's algorithm
+    // https://en.wikipedia.org/wiki/Heap%27s_algorithm
+    let n = indices.len();
+    let mut i = 1;
+    while i < n && counters[i] >= i {
+        counters[i] = 0;
+        i += 1;
+    }
+    if i < n {
+        let j = if i & 1 == 0 { 0 } else { counters[i] };
+        indices.swap(i, j);
+        counters[i] += 1;
+        Some((i, j))
+    } else {
+        None
+    }
+}
+
+/// Rearrange `indices` the way visiting all of their permutations with Heap's
+/// algorithm does, without visiting them.
+fn heap_pass(indices: &mut [usize]) {
+    let n = indices.len();
+    if n & 1 == 1 || n == 2 {
+        // An odd number of positions ends with the first and last swapped
+        indices.swap(0, n - 1);
+    } else if n > 2 {
+        // An even number ends with the last two moved to the front, except for
+        // the very last one, which swaps places with the first
+        indices.rotate_left(1);
+        indices[..n - 2].rotate_right(2);
+    }
+}
+
+/// Return an iterator over the swaps Heap's algorithm performs to visit every
+/// permutation of `N` positions.
+///
+/// Starting from any array of length `N`, swapping each yielded pair of
+/// positions in turn visits the same arrangements, in the same order, as
+/// [`permutations`] does for a slice of those `N` elements. Callers which keep
+/// an external structure in sync with the permutation can apply each swap
+/// directly rather than comparing whole arrays. `N! - 1` swaps are produced.
+///
+/// [`permutations`]: crate::SliceExt::permutations
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::heap_swaps;
+///
+/// let mut word = ['a', 'b', 'c'];
+/// for (i, j) in heap_swaps::<3>() {
+///     word.swap(i, j);
+/// }
+/// assert_eq!(word, ['c', 'b', 'a']);
+/// let swaps: Vec<_> = heap_swaps::<3>().collect();
+/// assert_eq!(swaps, [(1, 0), (2, 0), (1, 0), (2, 0), (1, 0)]);
+/// ```
+pub fn heap_swaps<const N: usize>() -> HeapSwaps<N> {
+    HeapSwaps::new()
+}
+
+/// An iterator that returns the swaps performed by Heap's algorithm.
+///
+/// This `struct` is created by the [`heap_swaps`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct HeapSwaps<const N: usize> {
+    gen: LazyPermutationGenerator<N>,
+}
+
+impl<const N: usize> HeapSwaps<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyPermutationGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for HeapSwaps<N> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.gen.is_done() {
+            None
+        } else {
+            self.gen.step_swap()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.gen.is_done() {
+            (0, Some(0))
+        } else {
+            size_hint(self.gen.len().map(|len| len - 1 - self.gen.rank()))
+        }
     }
 }
 
+impl<const N: usize> FusedIterator for HeapSwaps<N> {}
+
+/// A saved position of an iterator over permutations.
+///
+/// This `struct` is created by the `save` method on the permutations iterators,
+/// like [`Permutations::save`], and is handed back to their `restore` method to
+/// return to that position. It only holds the indices of the next
+/// permutation, so it's cheap to keep many of them around.
+#[derive(Clone)]
+pub struct PermutationsSnapshot<const K: usize> {
+    state: State<K>,
+}
+
 #[derive(Clone)]
-struct State<const K: usize> {
+pub(crate) struct State<const K: usize> {
     comb_gen: LazyCombinationGenerator<K>,
     perm_gen: LazyPermutationGenerator<K>,
 }
 
 impl<const K: usize> State<K> {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             comb_gen: LazyCombinationGenerator::new(),
             perm_gen: LazyPermutationGenerator::new(),
         }
     }
 
-    fn max_index(&self) -> Option<usize> {
+    fn circular() -> Self {
+        Self {
+            comb_gen: LazyCombinationGenerator::new(),
+            perm_gen: LazyPermutationGenerator::fixing_first(),
+        }
+    }
+
+    #[cfg(any(feature = "alloc", feature = "heapless"))]
+    pub(crate) fn max_index(&self) -> Option<usize> {
         self.comb_gen.max_index()
     }
 
-    fn get_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<[O; K]>
+    fn peek<'a, T, O, F>(&self, items: &'a [T], f: F) -> Option<[O; K]>
+    where
+        F: Fn(&'a T) -> O,
+        O: 'a,
+    {
+        if self.comb_gen.is_done(items.len()) {
+            None
+        } else {
+            let comb_indices = self.comb_gen.indices();
+            let perm_indices = self.perm_gen.indices();
+            // SAFETY: the combination generator isn't done, so every index is in
+            // bounds, and the permutation only reorders them
+            Some(make_array(|i| {
+                f(unsafe { get_item(items, comb_indices[perm_indices[i]]) })
+            }))
+        }
+    }
+
+    pub(crate) fn get_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<[O; K]>
+    where
+        F: Fn(&'a T) -> O,
+        O: 'a,
+    {
+        let res = self.peek(items, f)?;
+        self.step();
+        Some(res)
+    }
+
+    fn get_copied_and_step<T>(&mut self, items: &[T]) -> Option<[T; K]>
+    where
+        T: Copy,
+    {
+        if self.comb_gen.is_done(items.len()) {
+            None
+        } else {
+            // Index straight into the slice instead of going through a closure
+            let comb_indices = self.comb_gen.indices();
+            // SAFETY: the combination generator isn't done, so every index is in
+            // bounds, and the permutation only reorders them
+            let res = self
+                .perm_gen
+                .indices()
+                .map(|i| unsafe { *get_item(items, comb_indices[i]) });
+            self.step();
+            Some(res)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn get_boxed_and_step<'a, T, O, F>(&mut self, items: &'a [T], f: F) -> Option<Box<[O; K]>>
     where
         F: Fn(&'a T) -> O,
         O: 'a,
@@ -75,18 +353,147 @@ impl<const K: usize> State<K> {
         } else {
             let comb_indices = self.comb_gen.indices();
             let perm_indices = self.perm_gen.indices();
-            let res = make_array(|i| f(&items[comb_indices[perm_indices[i]]]));
-            self.perm_gen.step();
-            if self.perm_gen.is_done() {
-                // Reset the permutation generator and move to the next combination
-                self.perm_gen = LazyPermutationGenerator::new();
-                self.comb_gen.step();
+            // SAFETY: the combination generator isn't done, so every index is in
+            // bounds, and the permutation only reorders them
+            let res =
+                make_boxed_array(|i| f(unsafe { get_item(items, comb_indices[perm_indices[i]]) }));
+            self.step();
+            Some(res)
+        }
+    }
+
+    /// Feed every remaining permutation to `g`, stepping the generators in a
+    /// plain loop instead of going through an `Option` per permutation.
+    fn try_fold<'a, T, B, G, R>(&mut self, items: &'a [T], init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [&'a T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        while !self.comb_gen.is_done(items.len()) {
+            let comb_indices = self.comb_gen.indices();
+            // Look the elements of the combination up once, and only rearrange
+            // them for each of its permutations
+            // SAFETY: the combination generator isn't done, so every index is in
+            // bounds
+            let chosen: [&'a T; K] = make_array(|i| unsafe { get_item(items, comb_indices[i]) });
+            loop {
+                let res = self.perm_gen.indices().map(|i| chosen[i]);
+                self.perm_gen.step();
+                let last = self.perm_gen.is_done();
+                if last {
+                    self.perm_gen.reset();
+                    self.comb_gen.step();
+                }
+                acc = g(acc, res)?;
+                if last {
+                    break;
+                }
             }
+        }
+        R::from_output(acc)
+    }
+
+    fn fold<'a, T, B, G>(&mut self, items: &'a [T], init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [&'a T; K]) -> B,
+    {
+        match self.try_fold(items, init, |acc, res| Ok::<_, Infallible>(g(acc, res))) {
+            Ok(acc) => acc,
+            Err(never) => match never {},
+        }
+    }
+
+    pub(crate) fn get_indices_and_step(&mut self, item_count: usize) -> Option<[usize; K]> {
+        if self.comb_gen.is_done(item_count) {
+            None
+        } else {
+            let comb_indices = self.comb_gen.indices();
+            let res = self.perm_gen.indices().map(|i| comb_indices[i]);
+            self.step();
             Some(res)
         }
     }
+
+    /// Return the parity of the arrangement the next item has, relative to the
+    /// order of its elements in the input.
+    fn parity(&self) -> Parity {
+        // Every step of Heap's algorithm is a single swap
+        Parity::of_swaps(self.perm_gen.rank_parity())
+    }
+
+    /// Return the number of permutations left, including the current one.
+    pub(crate) fn remaining(&self, item_count: usize) -> Option<usize> {
+        let block = self.perm_gen.len()?;
+        match self.comb_gen.remaining(item_count)? {
+            0 => Some(0),
+            combs => (combs - 1)
+                .checked_mul(block)?
+                .checked_add(block - self.perm_gen.rank()),
+        }
+    }
+
+    pub(crate) fn is_done(&self, item_count: usize) -> bool {
+        self.comb_gen.is_done(item_count)
+    }
+
+    /// Return the number of permutations left as a `u128`, including the
+    /// current one.
+    pub(crate) fn remaining_u128(&self, item_count: usize) -> Option<u128> {
+        let block = self.perm_gen.len_u128()?;
+        match self.comb_gen.remaining_u128(item_count)? {
+            0 => Some(0),
+            combs => (combs - 1)
+                .checked_mul(block)?
+                .checked_add(block - self.perm_gen.rank_u128()),
+        }
+    }
+
+    fn advance_by(&mut self, item_count: usize, n: usize) -> Result<(), NonZero<usize>> {
+        let (block, remaining) = match (self.perm_gen.len(), self.remaining(item_count)) {
+            (Some(block), Some(remaining)) => (block, remaining),
+            _ => {
+                // Too many permutations to count, so take the slow path
+                for i in 0..n {
+                    if self.comb_gen.is_done(item_count) {
+                        return Err(NonZero::new(n - i).unwrap());
+                    }
+                    self.step();
+                }
+                return Ok(());
+            }
+        };
+        if n >= remaining {
+            if remaining > 0 {
+                self.comb_gen.finish();
+            }
+            return NonZero::new(n - remaining).map_or(Ok(()), Err);
+        }
+        // Skip whole combinations at once, and seek to the permutation of the
+        // one that is landed on
+        let rank = self.perm_gen.rank();
+        let rest = block - rank;
+        if n < rest {
+            self.perm_gen.seek(rank + n);
+        } else {
+            let n = n - rest;
+            self.comb_gen.advance_by(item_count, 1 + n / block).unwrap();
+            self.perm_gen.seek(n % block);
+        }
+        Ok(())
+    }
+
+    fn step(&mut self) {
+        self.perm_gen.step();
+        if self.perm_gen.is_done() {
+            // Reset the permutation generator and move to the next combination
+            self.perm_gen.reset();
+            self.comb_gen.step();
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 /// An iterator that returns k-length permutations of values from `iter`.
 ///
 /// This `struct` is created by the [`permutations`] method on [`IterExt`]. See its
@@ -96,85 +503,1203 @@ impl<const K: usize> State<K> {
 /// [`IterExt`]: super::IterExt
 #[derive(Clone)]
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct Permutations<I, const K: usize>
+pub struct Permutations<I, const K: usize, A = Global>
 where
     I: Iterator,
+    A: Allocator,
 {
     iter: I,
-    items: Vec<I::Item>,
+    items: Buffer<I::Item, A>,
     state: State<K>,
 }
 
+#[cfg(feature = "alloc")]
 impl<I, const K: usize> Permutations<I, K>
 where
     I: Iterator,
 {
     pub(crate) fn new(iter: I) -> Self {
+        Self::new_in(iter, Global)
+    }
+
+    pub(crate) fn with_capacity(iter: I, capacity: usize) -> Self {
+        Self::with_capacity_in(iter, capacity, Global)
+    }
+
+    /// Return an iterator adaptor which also yields the parity of each
+    /// permutation.
+    ///
+    /// The parity is that of the arrangement of the elements relative to their
+    /// order in the input. Heap's algorithm performs a single swap per step, so
+    /// this costs next to nothing to track.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{IterExt, Parity};
+    ///
+    /// let mut permutations = (0..3).permutations::<3>().with_parity();
+    /// assert_eq!(permutations.next(), Some(([0, 1, 2], Parity::Even)));
+    /// assert_eq!(permutations.next(), Some(([1, 0, 2], Parity::Odd)));
+    /// assert_eq!(permutations.next(), Some(([2, 0, 1], Parity::Even)));
+    /// ```
+    pub fn with_parity(self) -> WithParity<Self> {
+        WithParity::new(self)
+    }
+
+    /// Convert this into a lending iterator which reuses a single array for all
+    /// the permutations.
+    ///
+    /// Heap's algorithm swaps two elements per step, so the array is updated by
+    /// swapping them instead of cloning. Elements are only cloned when moving on
+    /// to another combination of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{IterExt, LendingIterator};
+    ///
+    /// let mut permutations = (0..3).permutations::<2>().streaming();
+    /// assert_eq!(permutations.next(), Some(&[0, 1]));
+    /// assert_eq!(permutations.next(), Some(&[1, 0]));
+    /// assert_eq!(permutations.next(), Some(&[0, 2]));
+    /// ```
+    pub fn streaming(self) -> StreamingPermutations<I, K>
+    where
+        I::Item: Clone,
+    {
+        StreamingPermutations::new(self.iter, into_vec(self.items), self.state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> Permutations<I, K, A>
+where
+    I: Iterator,
+    A: Allocator,
+{
+    pub(crate) fn new_in(iter: I, alloc: A) -> Self {
+        let mut this = Self::with_capacity_in(iter, 0, alloc);
+        if let (lower, Some(_)) = this.iter.size_hint() {
+            if K > 0 {
+                // The hint may be far larger than what fits in memory, in
+                // which case the buffer just grows as usual
+                let _ = this.items.try_reserve(lower);
+            }
+        }
+        this
+    }
+
+    pub(crate) fn with_capacity_in(iter: I, capacity: usize, alloc: A) -> Self {
+        let mut items = buffer_in(alloc);
+        items.reserve(capacity);
         Self {
             iter,
-            items: Vec::new(),
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Return the elements read from the input iterator so far.
+    ///
+    /// Elements are only read once they are needed, so this is a prefix of the
+    /// input which contains every element of the permutations returned so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = (1..10).permutations::<2>();
+    /// assert_eq!(permutations.items(), &[]);
+    /// assert_eq!(permutations.next(), Some([1, 2]));
+    /// assert_eq!(permutations.items(), &[1, 2]);
+    /// ```
+    pub fn items(&self) -> &[I::Item] {
+        &self.items
+    }
+
+    /// Reserve room in the buffer for at least `additional` more elements of
+    /// the input.
+    ///
+    /// See [`Combinations::reserve_items`] for more.
+    ///
+    /// [`Combinations::reserve_items`]: crate::Combinations::reserve_items
+    pub fn reserve_items(&mut self, additional: usize) {
+        self.items.reserve(additional);
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// This needs the input iterator to know its exact length. Unlike
+    /// [`len`](ExactSizeIterator::len), it doesn't panic when the count is too
+    /// large for a `usize`. See [`SlicePermutations::remaining_len`] for
+    /// more.
+    pub fn remaining_len(&self) -> Option<u128>
+    where
+        I: ExactSizeIterator,
+    {
+        let item_count = self.items.len().checked_add(self.iter.len())?;
+        self.state.remaining_u128(item_count)
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// See [`Combinations::is_exhausted`] for more.
+    ///
+    /// [`Combinations::is_exhausted`]: crate::Combinations::is_exhausted
+    pub fn is_exhausted(&mut self) -> bool {
+        if let Some(max_index) = self.state.max_index() {
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            fill(&mut self.items, &mut self.iter, missing_count);
+        }
+        self.state.is_done(self.items.len())
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// [`restore`].
+    ///
+    /// Only the position is saved, not the elements read so far, which makes
+    /// this much cheaper than cloning the iterator. Search algorithms can use
+    /// this to explore the permutations ahead and then roll back.
+    ///
+    /// [`restore`]: Permutations::restore
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = (0..3).permutations::<2>();
+    /// permutations.next();
+    /// let snapshot = permutations.save();
+    /// assert_eq!(permutations.nth(3), Some([1, 2]));
+    /// permutations.restore(snapshot);
+    /// assert_eq!(permutations.next(), Some([1, 0]));
+    /// ```
+    pub fn save(&self) -> PermutationsSnapshot<K> {
+        PermutationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with [`save`].
+    ///
+    /// Restoring a snapshot of another iterator continues from the same
+    /// position in the order of the permutations, reading more of the input if
+    /// it's needed.
+    ///
+    /// [`save`]: Permutations::save
+    pub fn restore(&mut self, snapshot: PermutationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Buffer the rest of the input and return an iterator over the remaining
+    /// permutations which shares the buffer between its clones.
+    ///
+    /// Cloning this iterator clones the whole buffer. The returned iterator
+    /// keeps the buffer behind an [`Arc`] instead, so it can be cloned into any
+    /// number of cursors over the same elements without copying them. Note that
+    /// this reads the input iterator to the end, even if it is infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = (0..3).permutations::<3>();
+    /// assert_eq!(permutations.next(), Some([0, 1, 2]));
+    /// let forked = permutations.fork();
+    /// let mut evens = forked.clone().step_by(2);
+    /// let mut odds = forked.skip(1).step_by(2);
+    /// assert_eq!(evens.next(), Some([1, 0, 2]));
+    /// assert_eq!(odds.next(), Some([2, 0, 1]));
+    /// ```
+    pub fn fork(mut self) -> ForkedPermutations<I::Item, K> {
+        if K > 0 {
+            self.items.extend(self.iter);
+        }
+        ForkedPermutations {
+            items: into_arc(self.items),
+            state: self.state,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> Iterator for Permutations<I, K, A>
+where
+    A: Allocator,
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        if K > 0 {
+            let max_index = self.state.max_index().unwrap();
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            if missing_count > 0 {
+                // Try to fill the buffer
+                fill(&mut self.items, &mut self.iter, missing_count);
+            }
+        }
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = self.items.len();
+        let remaining = |len: usize| self.state.remaining(buffered.checked_add(len)?);
+        let (lower, _) = size_hint(remaining(lower));
+        (lower, upper.and_then(remaining))
+    }
+
+    fn fold<B, G>(mut self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [I::Item; K]) -> B,
+    {
+        match self.try_fold(init, |acc, res| Ok::<_, Infallible>(g(acc, res))) {
+            Ok(acc) => acc,
+            Err(never) => match never {},
+        }
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [I::Item; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        loop {
+            if K > 0 {
+                // Read only the element the next permutation needs, like `next`
+                // does, so an infinite input is never buffered to the end
+                let max_index = self.state.max_index().unwrap();
+                let missing_count = (max_index + 1).saturating_sub(self.items.len());
+                fill(&mut self.items, &mut self.iter, missing_count);
+            }
+            if self.state.is_done(self.items.len()) {
+                return R::from_output(acc);
+            }
+            // Every permutation of the buffered elements comes before any
+            // which needs another one
+            acc = self
+                .state
+                .try_fold(&self.items, acc, |acc, res| g(acc, res.map(Clone::clone)))?;
+        }
+    }
+}
+
+// The count is exact whenever it fits in a `usize`, and `remaining_len` covers
+// the inputs where it doesn't
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> ExactSizeIterator for Permutations<I, K, A>
+where
+    A: Allocator,
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> FusedIterator for Permutations<I, K, A>
+where
+    A: Allocator,
+    // This should be `I: Iterator, Combinations<I, K>: FusedIterator`,
+    // but it exposes the implementation and makes for lousy docs.
+    // There is a test which will stop compiling if the bounds for
+    // `FusedIterator for Combinations` impl change.
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(feature = "alloc")]
+/// An iterator that returns k-length permutations of values from a shared buffer.
+///
+/// This `struct` is created by the [`fork`] method on [`Permutations`]. See its
+/// documentation for more.
+///
+/// [`fork`]: Permutations::fork
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct ForkedPermutations<T, const K: usize> {
+    items: Arc<[T]>,
+    state: State<K>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> ForkedPermutations<T, K> {
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Permutations::save`] for more.
+    pub fn save(&self) -> PermutationsSnapshot<K> {
+        PermutationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Permutations::restore`] for more.
+    pub fn restore(&mut self, snapshot: PermutationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// See [`SlicePermutations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no permutations left.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> Iterator for ForkedPermutations<T, K>
+where
+    T: Clone,
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> FusedIterator for ForkedPermutations<T, K> where T: Clone {}
+
+/// An iterator that returns k-length permutations of values from `slice`.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SlicePermutations<'a, T, const K: usize> {
+    pub(crate) items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> Iterator for SlicePermutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.state.get_and_step(self.items, |t| t)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+
+    fn fold<B, G>(mut self, init: B, g: G) -> B
+    where
+        G: FnMut(B, [&'a T; K]) -> B,
+    {
+        self.state.fold(self.items, init, g)
+    }
+
+    fn for_each<G>(mut self, mut g: G)
+    where
+        G: FnMut([&'a T; K]),
+    {
+        self.state.fold(self.items, (), |(), res| g(res));
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, g: G) -> R
+    where
+        G: FnMut(B, [&'a T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        self.state.try_fold(self.items, init, g)
+    }
+}
+
+impl<'a, T, const K: usize> SlicePermutations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Permutations::save`] for more.
+    pub fn save(&self) -> PermutationsSnapshot<K> {
+        PermutationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Permutations::restore`] for more.
+    pub fn restore(&mut self, snapshot: PermutationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the next permutation without advancing the iterator.
+    ///
+    /// This is computed from the current state, so unlike wrapping the
+    /// iterator in a [`Peekable`] no permutation is stored.
+    ///
+    /// [`Peekable`]: core::iter::Peekable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = [1, 2, 3];
+    /// let mut permutations = items.permutations::<2>();
+    /// assert_eq!(permutations.peek(), Some([&1, &2]));
+    /// assert_eq!(permutations.next(), Some([&1, &2]));
+    /// assert_eq!(permutations.peek(), Some([&2, &1]));
+    /// ```
+    pub fn peek(&self) -> Option<[&'a T; K]> {
+        self.state.peek(self.items, |t| t)
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// This counts up to `u128::MAX`, well past what a `usize` can hold, and
+    /// returns `None` beyond that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items: Vec<u32> = (0..30).collect();
+    /// let mut permutations = items.permutations::<30>();
+    /// assert_eq!(
+    ///     permutations.remaining_len(),
+    ///     Some(265_252_859_812_191_058_636_308_480_000_000)
+    /// );
+    /// permutations.next();
+    /// assert_eq!(
+    ///     permutations.remaining_len(),
+    ///     Some(265_252_859_812_191_058_636_308_479_999_999)
+    /// );
+    /// ```
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// This is computed from the current state, without advancing the
+    /// iterator, so there's no need to clone it and call `next` to find out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut permutations = [1, 2].permutations::<2>();
+    /// assert!(!permutations.is_empty());
+    /// permutations.by_ref().for_each(drop);
+    /// assert!(permutations.is_empty());
+    /// assert!([1].permutations::<2>().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+
+    /// Create an iterator over only the permutations of `items` whose position
+    /// in the iteration order falls in `ranks`.
+    ///
+    /// Whole blocks of permutations are skipped to get to the start of the
+    /// range, see [`SliceCombinations::from_rank_range`] for more.
+    ///
+    /// [`SliceCombinations::from_rank_range`]: crate::SliceCombinations::from_rank_range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SlicePermutations;
+    ///
+    /// let mut chunk = SlicePermutations::<_, 2>::from_rank_range(&[1, 2, 3], 3..10);
+    /// assert_eq!(chunk.next(), Some([&3, &1]));
+    /// assert_eq!(chunk.next(), Some([&2, &3]));
+    /// assert_eq!(chunk.next(), Some([&3, &2]));
+    /// assert_eq!(chunk.next(), None);
+    /// ```
+    pub fn from_rank_range(items: &'a [T], ranks: Range<usize>) -> Take<Self> {
+        let mut iter = Self::new(items);
+        let _ = iter.advance_by(ranks.start);
+        iter.take(ranks.len())
+    }
+
+    /// Return an iterator adaptor which also yields the parity of each
+    /// permutation.
+    ///
+    /// The parity is that of the arrangement of the elements relative to their
+    /// order in the input. Heap's algorithm performs a single swap per step, so
+    /// this costs next to nothing to track.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// // The determinant of a 3x3 matrix as a sum over signed permutations
+    /// let m = [[2, 0, 1], [1, 3, 2], [1, 1, 2]];
+    /// let det: i32 = [0, 1, 2]
+    ///     .permutations::<3>()
+    ///     .with_parity()
+    ///     .map(|([&a, &b, &c], parity)| i32::from(parity.sign()) * m[0][a] * m[1][b] * m[2][c])
+    ///     .sum();
+    /// assert_eq!(det, 6);
+    /// ```
+    pub fn with_parity(self) -> WithParity<Self> {
+        WithParity::new(self)
+    }
+
+    /// Return an iterator adaptor which yields every `step`-th permutation,
+    /// starting with the first one.
+    ///
+    /// Every step skips whole blocks of permutations sharing a combination at
+    /// once, and only steps through the permutations within the block it lands
+    /// in. See [`SliceCombinations::step_by_rank`] for more.
+    ///
+    /// [`SliceCombinations::step_by_rank`]: crate::SliceCombinations::step_by_rank
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let mut samples = [1, 2, 3].permutations::<2>().step_by_rank(2);
+    /// assert_eq!(samples.next(), Some([&1, &2]));
+    /// assert_eq!(samples.next(), Some([&1, &3]));
+    /// assert_eq!(samples.next(), Some([&2, &3]));
+    /// assert_eq!(samples.next(), None);
+    /// ```
+    pub fn step_by_rank(self, step: usize) -> StepBy<Self> {
+        self.step_by(step)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return an iterator adaptor which yields every permutation in a `Box`.
+    ///
+    /// Like [`SliceCombinations::boxed`], each array is written straight into its
+    /// heap allocation, which keeps the stack usage down when `K` is large.
+    ///
+    /// [`SliceCombinations::boxed`]: crate::SliceCombinations::boxed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items: Vec<u32> = (0..200).collect();
+    /// let mut permutations = items.permutations::<200>().boxed();
+    /// assert_eq!(*permutations.next().unwrap()[0], 0);
+    /// assert_eq!(*permutations.next().unwrap()[0], 1);
+    /// ```
+    pub fn boxed(self) -> Boxed<Self> {
+        Boxed::new(self)
+    }
+
+    /// Return an iterator adaptor which also yields the positions in the slice
+    /// of the elements of each permutation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = ["a", "b", "c"];
+    /// let mut permutations = items.permutations::<2>().with_indices();
+    /// assert_eq!(permutations.next(), Some(([0, 1], [&"a", &"b"])));
+    /// assert_eq!(permutations.next(), Some(([1, 0], [&"b", &"a"])));
+    /// ```
+    pub fn with_indices(self) -> WithIndices<Self, K> {
+        WithIndices::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, const K: usize> BoxedSource for SlicePermutations<'a, T, K> {
+    fn next_boxed(&mut self) -> Option<Box<[&'a T; K]>> {
+        self.state.get_boxed_and_step(self.items, |t| t)
+    }
+}
+
+impl<'a, T, const K: usize> IndexedSource<K> for SlicePermutations<'a, T, K> {
+    fn next_with_indices(&mut self) -> Option<([usize; K], [&'a T; K])> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the generator wasn't done, so every index is in bounds
+        Some((indices, indices.map(|i| unsafe { get_item(self.items, i) })))
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SlicePermutations<'_, T, K> {}
+
+/// A lending iterator that returns k-length permutations of mutable references
+/// into `slice`.
+///
+/// The same element shows up in many permutations, so the references returned
+/// by [`next`] only live until the next call. That's why this doesn't implement
+/// [`Iterator`], and is driven with `while let` instead.
+///
+/// This `struct` is created by the [`permutations_mut`] method on [`SliceExt`].
+/// See its documentation for more.
+///
+/// [`next`]: SlicePermutationsMut::next
+/// [`permutations_mut`]: super::SliceExt::permutations_mut
+/// [`SliceExt`]: super::SliceExt
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SlicePermutationsMut<'a, T, const K: usize> {
+    items: &'a mut [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SlicePermutationsMut<'a, T, K> {
+    pub(crate) fn new(items: &'a mut [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Advance to the next permutation and return mutable references to its
+    /// elements, or `None` once every permutation has been returned.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<[&mut T; K]> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the indices are a reordering of a combination's indices, so
+        // they're in bounds and no element is borrowed twice
+        Some(unsafe { self.items.get_disjoint_unchecked_mut(indices) })
+    }
+
+    /// Return the number of permutations left.
+    ///
+    /// Returns `None` if the number doesn't fit in a `usize`.
+    pub fn remaining(&self) -> Option<usize> {
+        self.state.remaining(self.items.len())
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// See [`SlicePermutations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// See [`SlicePermutations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+/// An iterator that returns k-length permutations of copies of values from
+/// `slice`.
+///
+/// This `struct` is created by the [`permutations_copied`] method on [`SliceExt`].
+/// See its documentation for more.
+///
+/// [`permutations_copied`]: super::SliceExt::permutations_copied
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceCopiedPermutations<'a, T, const K: usize> {
+    items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> SliceCopiedPermutations<'a, T, K>
+where
+    T: Copy,
+{
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::new(),
+        }
+    }
+
+    /// Save the position of the iterator, so that it can be returned to with
+    /// `restore`.
+    ///
+    /// See [`Permutations::save`] for more.
+    pub fn save(&self) -> PermutationsSnapshot<K> {
+        PermutationsSnapshot {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Return to a position saved with `save`.
+    ///
+    /// See [`Permutations::restore`] for more.
+    pub fn restore(&mut self, snapshot: PermutationsSnapshot<K>) {
+        self.state = snapshot.state;
+    }
+
+    /// Return the next permutation without advancing the iterator.
+    ///
+    /// This is computed from the current state, so unlike wrapping the
+    /// iterator in a [`Peekable`] no permutation is stored.
+    ///
+    /// [`Peekable`]: core::iter::Peekable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = [1, 2, 3];
+    /// let mut permutations = items.permutations_copied::<2>();
+    /// assert_eq!(permutations.peek(), Some([1, 2]));
+    /// assert_eq!(permutations.next(), Some([1, 2]));
+    /// ```
+    pub fn peek(&self) -> Option<[T; K]> {
+        self.state.peek(self.items, |t| *t)
+    }
+
+    /// Return the exact number of permutations left as a `u128`.
+    ///
+    /// See [`SlicePermutations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no permutations left.
+    ///
+    /// See [`SlicePermutations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+
+    /// Return an iterator adaptor which also yields the parity of each
+    /// permutation.
+    ///
+    /// The parity is that of the arrangement of the elements relative to their
+    /// order in the input. Heap's algorithm performs a single swap per step, so
+    /// this costs next to nothing to track.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::{Parity, SliceExt};
+    ///
+    /// let mut permutations = ['a', 'b'].permutations_copied::<2>().with_parity();
+    /// assert_eq!(permutations.next(), Some((['a', 'b'], Parity::Even)));
+    /// assert_eq!(permutations.next(), Some((['b', 'a'], Parity::Odd)));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    pub fn with_parity(self) -> WithParity<Self> {
+        WithParity::new(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Return an iterator adaptor which copies every permutation into a `Box`.
+    ///
+    /// See [`SlicePermutations::boxed`] for more.
+    pub fn boxed(self) -> Boxed<Self> {
+        Boxed::new(self)
+    }
+
+    /// Return an iterator adaptor which also yields the positions in the slice
+    /// of the elements of each permutation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    ///
+    /// let items = ["a", "b", "c"];
+    /// let mut permutations = items.permutations_copied::<2>().with_indices();
+    /// assert_eq!(permutations.next(), Some(([0, 1], ["a", "b"])));
+    /// assert_eq!(permutations.next(), Some(([1, 0], ["b", "a"])));
+    /// ```
+    pub fn with_indices(self) -> WithIndices<Self, K> {
+        WithIndices::new(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> BoxedSource for SliceCopiedPermutations<'_, T, K>
+where
+    T: Copy,
+{
+    fn next_boxed(&mut self) -> Option<Box<[T; K]>> {
+        self.state.get_boxed_and_step(self.items, |&t| t)
+    }
+}
+
+impl<T, const K: usize> IndexedSource<K> for SliceCopiedPermutations<'_, T, K>
+where
+    T: Copy,
+{
+    fn next_with_indices(&mut self) -> Option<([usize; K], [T; K])> {
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        // SAFETY: the generator wasn't done, so every index is in bounds
+        Some((
+            indices,
+            indices.map(|i| unsafe { *get_item(self.items, i) }),
+        ))
+    }
+}
+
+impl<T, const K: usize> Iterator for SliceCopiedPermutations<'_, T, K>
+where
+    T: Copy,
+{
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        self.state.get_copied_and_step(self.items)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, [T; K]) -> B,
+    {
+        let mut acc = init;
+        while let Some(res) = self.state.get_copied_and_step(self.items) {
+            acc = g(acc, res);
+        }
+        acc
+    }
+
+    fn try_fold<B, G, R>(&mut self, init: B, mut g: G) -> R
+    where
+        G: FnMut(B, [T; K]) -> R,
+        R: Try<Output = B>,
+    {
+        let mut acc = init;
+        while let Some(res) = self.state.get_copied_and_step(self.items) {
+            acc = g(acc, res)?;
+        }
+        R::from_output(acc)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceCopiedPermutations<'_, T, K> where T: Copy {}
+
+#[cfg(feature = "alloc")]
+/// An iterator that returns k-length circular permutations of values from `iter`.
+///
+/// This `struct` is created by the [`circular_permutations`] method on [`IterExt`].
+/// See its documentation for more.
+///
+/// [`circular_permutations`]: super::IterExt::circular_permutations
+/// [`IterExt`]: super::IterExt
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CircularPermutations<I, const K: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    items: Vec<I::Item>,
+    state: State<K>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize> CircularPermutations<I, K>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            items: Vec::new(),
+            state: State::circular(),
+        }
+    }
+
+    /// Return the exact number of circular permutations left as a `u128`.
+    ///
+    /// This needs the input iterator to know its exact length. See
+    /// [`SlicePermutations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128>
+    where
+        I: ExactSizeIterator,
+    {
+        let item_count = self.items.len().checked_add(self.iter.len())?;
+        self.state.remaining_u128(item_count)
+    }
+
+    /// Return `true` if there are no circular permutations left.
+    ///
+    /// See [`Combinations::is_exhausted`] for more.
+    ///
+    /// [`Combinations::is_exhausted`]: crate::Combinations::is_exhausted
+    pub fn is_exhausted(&mut self) -> bool {
+        if let Some(max_index) = self.state.max_index() {
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            self.items.extend(self.iter.by_ref().take(missing_count));
+        }
+        self.state.is_done(self.items.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize> Iterator for CircularPermutations<I, K>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        if K > 0 {
+            let max_index = self.state.max_index().unwrap();
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            if missing_count > 0 {
+                // Try to fill the buffer
+                self.items.extend(self.iter.by_ref().take(missing_count));
+            }
+        }
+        self.state.get_and_step(&self.items, |t| t.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = self.items.len();
+        let remaining = |len: usize| self.state.remaining(buffered.checked_add(len)?);
+        let (lower, _) = size_hint(remaining(lower));
+        (lower, upper.and_then(remaining))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize> FusedIterator for CircularPermutations<I, K>
+where
+    I: FusedIterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator that returns k-length circular permutations of values from `slice`.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceCircularPermutations<'a, T, const K: usize> {
+    items: &'a [T],
+    state: State<K>,
+}
+
+impl<'a, T, const K: usize> Iterator for SliceCircularPermutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.state.get_and_step(self.items, |t| t)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.state.remaining(self.items.len()))
+    }
+
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        self.state.advance_by(self.items.len(), n)
+    }
+}
+
+impl<'a, T, const K: usize> SliceCircularPermutations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            state: State::circular(),
+        }
+    }
+
+    /// Return the exact number of circular permutations left as a `u128`.
+    ///
+    /// See [`SlicePermutations::remaining_len`] for more.
+    pub fn remaining_len(&self) -> Option<u128> {
+        self.state.remaining_u128(self.items.len())
+    }
+
+    /// Return `true` if there are no circular permutations left.
+    ///
+    /// See [`SlicePermutations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        self.state.is_done(self.items.len())
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceCircularPermutations<'_, T, K> {}
+
+/// An iterator that returns the k-length permutations of values from `slice`
+/// which have a given parity.
+///
+/// This `struct` is created by the [`even_permutations`] and [`odd_permutations`]
+/// methods on [`SliceExt`]. See their documentation for more.
+///
+/// [`even_permutations`]: super::SliceExt::even_permutations
+/// [`odd_permutations`]: super::SliceExt::odd_permutations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceParityPermutations<'a, T, const K: usize> {
+    items: &'a [T],
+    state: State<K>,
+    parity: Parity,
+}
+
+impl<'a, T, const K: usize> SliceParityPermutations<'a, T, K> {
+    pub(crate) fn new(items: &'a [T], parity: Parity) -> Self {
+        Self {
+            items,
             state: State::new(),
+            parity,
+        }
+    }
+
+    /// Return `true` if there are no permutations of the chosen parity left.
+    ///
+    /// See [`SlicePermutations::is_empty`] for more.
+    pub fn is_empty(&self) -> bool {
+        // A count which doesn't fit in a `usize` isn't zero
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceParityPermutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        // Parities alternate with every step, so at most one permutation has to
+        // be passed over, except when there is only one per combination
+        while self.state.parity() != self.parity {
+            if self.state.comb_gen.is_done(self.items.len()) {
+                return None;
+            }
+            self.state.step();
         }
+        self.state.get_and_step(self.items, |t| t)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.state.remaining(self.items.len()).map(|n| {
+            if K < 2 {
+                // Every permutation of fewer than two elements is even
+                if self.parity == Parity::Even {
+                    n
+                } else {
+                    0
+                }
+            } else if self.state.parity() == self.parity {
+                n.div_ceil(2)
+            } else {
+                n / 2
+            }
+        });
+        size_hint(remaining)
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceParityPermutations<'_, T, K> {}
+
+mod sealed {
+    use crate::perm::Parity;
+
+    /// Permutation iterators which can report the parity of their next item.
+    pub trait ParitySource: Iterator {
+        fn next_parity(&self) -> Parity;
     }
 }
 
-impl<I, const K: usize> Iterator for Permutations<I, K>
+use sealed::ParitySource;
+
+#[cfg(feature = "alloc")]
+impl<I, const K: usize, A> ParitySource for Permutations<I, K, A>
 where
+    A: Allocator,
     I: Iterator,
     I::Item: Clone,
 {
-    type Item = [I::Item; K];
+    fn next_parity(&self) -> Parity {
+        self.state.parity()
+    }
+}
 
-    fn next(&mut self) -> Option<[I::Item; K]> {
-        if K > 0 {
-            let max_index = self.state.max_index().unwrap();
-            let missing_count = (max_index + 1).saturating_sub(self.items.len());
-            if missing_count > 0 {
-                // Try to fill the buffer
-                self.items.extend(self.iter.by_ref().take(missing_count));
-            }
-        }
-        self.state.get_and_step(&self.items, |t| t.clone())
+impl<'a, T, const K: usize> ParitySource for SlicePermutations<'a, T, K> {
+    fn next_parity(&self) -> Parity {
+        self.state.parity()
     }
 }
 
-impl<I, const K: usize> FusedIterator for Permutations<I, K>
+impl<T, const K: usize> ParitySource for SliceCopiedPermutations<'_, T, K>
 where
-    // This should be `I: Iterator, Combinations<I, K>: FusedIterator`,
-    // but it exposes the implementation and makes for lousy docs.
-    // There is a test which will stop compiling if the bounds for
-    // `FusedIterator for Combinations` impl change.
-    I: FusedIterator,
-    I::Item: Clone,
+    T: Copy,
 {
+    fn next_parity(&self) -> Parity {
+        self.state.parity()
+    }
 }
 
-/// An iterator that returns k-length permutations of values from `slice`.
+/// An iterator that returns permutations along with their parity.
+///
+/// This `struct` is created by the `with_parity` method on the permutation
+/// iterators, like [`SlicePermutations::with_parity`]. See its documentation for
+/// more.
 #[derive(Clone)]
-#[must_use = "iterators do nothing unless consumed"]
-pub struct SlicePermutations<'a, T, const K: usize> {
-    items: &'a [T],
-    state: State<K>,
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct WithParity<P> {
+    iter: P,
 }
 
-impl<'a, T, const K: usize> Iterator for SlicePermutations<'a, T, K> {
-    type Item = [&'a T; K];
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.state.get_and_step(self.items, |t| t)
+impl<P> WithParity<P> {
+    pub(crate) fn new(iter: P) -> Self {
+        Self { iter }
     }
 }
 
-impl<'a, T, const K: usize> SlicePermutations<'a, T, K> {
-    pub(crate) fn new(items: &'a [T]) -> Self {
-        Self {
-            items,
-            state: State::new(),
-        }
+impl<P> Iterator for WithParity<P>
+where
+    P: ParitySource,
+{
+    type Item = (P::Item, Parity);
+
+    fn next(&mut self) -> Option<(P::Item, Parity)> {
+        let parity = self.iter.next_parity();
+        let item = self.iter.next()?;
+        Some((item, parity))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
-impl<T, const K: usize> FusedIterator for SlicePermutations<'_, T, K> {}
+impl<P> ExactSizeIterator for WithParity<P> where P: ParitySource + ExactSizeIterator {}
+
+impl<P> FusedIterator for WithParity<P> where P: ParitySource + FusedIterator {}
 
 #[cfg(test)]
 mod test {
@@ -182,6 +1707,105 @@ mod test {
     use crate::IterExt;
     use core::sync::atomic::{AtomicUsize, Ordering};
 
+    #[test]
+    fn remaining_len() {
+        use crate::SliceExt;
+
+        let items = [0u8; 6];
+        let mut permutations = items.permutations::<4>();
+        let mut copied = items.permutations_copied::<4>();
+        let mut mutable = [0u8; 6];
+        let mut mutable = mutable.permutations_mut::<4>();
+        loop {
+            let len = permutations.clone().count() as u128;
+            assert_eq!(permutations.remaining_len(), Some(len));
+            assert_eq!(copied.remaining_len(), Some(len));
+            assert_eq!(mutable.remaining_len(), Some(len));
+            mutable.next();
+            copied.next();
+            if permutations.next().is_none() {
+                break;
+            }
+        }
+        assert_eq!(permutations.remaining_len(), Some(0));
+        let items = [0u8; 40];
+        let mut permutations = items.permutations::<22>();
+        // 40! / 18! overflows a `usize` but not a `u128`
+        let total = (19..=40).map(|i| i as u128).product::<u128>();
+        assert_eq!(permutations.remaining_len(), Some(total));
+        permutations.nth(1000);
+        assert_eq!(permutations.remaining_len(), Some(total - 1001));
+        assert_eq!(items.permutations::<35>().remaining_len(), None);
+    }
+
+    #[test]
+    fn snapshots() {
+        use crate::SliceExt;
+        use alloc::vec::Vec;
+
+        let all: Vec<_> = (0..6).permutations::<3>().collect();
+        let mut permutations = (0..6).permutations::<3>();
+        permutations.nth(4);
+        let snapshot = permutations.save();
+        assert!(permutations.by_ref().eq(all[5..].iter().copied()));
+        permutations.restore(snapshot.clone());
+        assert_eq!(permutations.size_hint().1, Some(all.len() - 5));
+        assert_eq!(permutations.next(), Some(all[5]));
+
+        // Snapshots carry over to any iterator over the same elements
+        let items = [0, 1, 2, 3, 4, 5];
+        let mut slice = items.permutations::<3>();
+        slice.restore(snapshot.clone());
+        assert_eq!(slice.next(), Some(all[5].map(|i| &items[i])));
+        let mut copied = items.permutations_copied::<3>();
+        copied.restore(snapshot.clone());
+        assert_eq!(copied.save().state.remaining(6), Some(all.len() - 5));
+        let mut fresh = (0..6).permutations::<3>();
+        fresh.restore(snapshot);
+        assert!(fresh.items().is_empty());
+        assert!(fresh.eq(all[5..].iter().copied()));
+
+        let mut forked = (0..6).permutations::<3>().fork();
+        let start = forked.save();
+        assert_eq!(forked.by_ref().count(), all.len());
+        forked.restore(start);
+        assert_eq!(forked.next(), Some(all[0]));
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut permutations = (0..5).permutations::<3>();
+        for len in (0..=60).rev() {
+            assert_eq!(permutations.len(), len);
+            permutations.next();
+        }
+        let mut permutations = (0..5).circular_permutations::<3>();
+        for len in (0..=20).rev() {
+            assert_eq!(permutations.size_hint(), (len, Some(len)));
+            permutations.next();
+        }
+        assert_eq!((0..2).permutations::<3>().len(), 0);
+        assert_eq!((0..).permutations::<2>().size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn fold() {
+        let sum = (1..5)
+            .permutations::<2>()
+            .fold(0, |acc, [a, b]| acc + a * 10 + b);
+        assert_eq!(sum, 330);
+        assert_eq!((0..).permutations::<0>().fold(0, |acc, _| acc + 1), 1);
+        // Only the elements the permutations so far needed are read
+        let mut permutations = (0..).permutations::<2>();
+        assert_eq!(permutations.find(|&[a, b]| a > b + 1), Some([2, 0]));
+        assert_eq!(permutations.items(), &[0, 1, 2]);
+        assert_eq!(permutations.next(), Some([1, 2]));
+        let mut permutations = (0..4).permutations::<3>();
+        permutations.nth(4);
+        let rest: Vec<_> = permutations.collect();
+        assert!(rest.into_iter().eq((0..4).permutations::<3>().skip(5)));
+    }
+
     #[test]
     fn order() {
         let mut permutations = (1..4).permutations();
@@ -221,6 +1845,31 @@ mod test {
         assert!(gen.is_done());
     }
 
+    fn check_seek<const N: usize>(mut stepped: LazyPermutationGenerator<N>) {
+        let mut sought = stepped.clone();
+        for rank in 0..stepped.len().unwrap() {
+            sought.seek(rank);
+            assert_eq!(sought.indices(), stepped.indices());
+            assert_eq!(sought.rank(), rank);
+            stepped.step();
+        }
+    }
+
+    #[test]
+    fn gen_seek() {
+        check_seek(LazyPermutationGenerator::<0>::new());
+        check_seek(LazyPermutationGenerator::<1>::new());
+        check_seek(LazyPermutationGenerator::<2>::new());
+        check_seek(LazyPermutationGenerator::<3>::new());
+        check_seek(LazyPermutationGenerator::<4>::new());
+        check_seek(LazyPermutationGenerator::<5>::new());
+        check_seek(LazyPermutationGenerator::<6>::new());
+        check_seek(LazyPermutationGenerator::<7>::new());
+        check_seek(LazyPermutationGenerator::<8>::new());
+        check_seek(LazyPermutationGenerator::<5>::fixing_first());
+        check_seek(LazyPermutationGenerator::<7>::fixing_first());
+    }
+
     #[test]
     fn none_on_size_too_big() {
         let mut permutations = (1..2).permutations::<2>();
@@ -236,6 +1885,51 @@ mod test {
         assert_eq!(permutations.next(), None);
     }
 
+    #[test]
+    fn presized() {
+        assert!((0..10).permutations::<3>().items.capacity() >= 10);
+        assert_eq!((0..10).permutations::<0>().items.capacity(), 0);
+        assert_eq!((0..).permutations::<2>().items.capacity(), 0);
+        assert_eq!((0..usize::MAX).permutations::<2>().items.capacity(), 0);
+        let filtered = (0..10).filter(|n| n % 3 > 0);
+        assert!(filtered.permutations_with_capacity::<2>(7).items.capacity() >= 7);
+    }
+
+    #[test]
+    fn fork() {
+        let mut permutations = (0..6).permutations::<3>();
+        permutations.nth(4);
+        let forked = permutations.clone().fork();
+        assert_eq!(forked.size_hint(), permutations.size_hint());
+        assert_eq!(forked.remaining_len(), Some(115));
+        let other = forked.clone();
+        assert!(Arc::ptr_eq(&forked.items, &other.items));
+        assert!(forked.eq(permutations.clone()));
+        assert!(other.eq(permutations));
+        assert_eq!((0..).permutations::<0>().fork().count(), 1);
+    }
+
+    #[test]
+    fn heap_swaps() {
+        use crate::SliceExt;
+
+        let items = [1, 2, 3, 4];
+        let mut word = items;
+        let mut permutations = items.permutations_copied::<4>();
+        assert_eq!(permutations.next(), Some(word));
+        let mut swaps = super::heap_swaps::<4>();
+        assert_eq!(swaps.size_hint(), (23, Some(23)));
+        while let Some((i, j)) = swaps.next() {
+            word.swap(i, j);
+            assert_eq!(permutations.next(), Some(word));
+            assert_eq!(swaps.size_hint(), permutations.size_hint());
+        }
+        assert_eq!(permutations.next(), None);
+        assert_eq!(super::heap_swaps::<0>().next(), None);
+        assert_eq!(super::heap_swaps::<1>().next(), None);
+        assert_eq!(super::heap_swaps::<30>().size_hint(), (usize::MAX, None));
+    }
+
     #[test]
     fn fused_propagation() {
         let fused = [1, 2, 3].iter().fuse();
@@ -303,6 +1997,176 @@ mod test {
 mod slice_test {
     use crate::SliceExt;
 
+    #[test]
+    fn peek() {
+        let items = [1, 2, 3, 4, 5];
+        let mut permutations = items.permutations::<3>();
+        let mut copied = items.permutations_copied::<3>();
+        loop {
+            let peeked = permutations.peek();
+            assert_eq!(peeked, permutations.next());
+            assert_eq!(copied.peek(), copied.next());
+            if peeked.is_none() {
+                break;
+            }
+        }
+        assert_eq!(items.permutations::<6>().peek(), None);
+        assert_eq!(items.permutations::<0>().peek(), Some([]));
+    }
+
+    #[test]
+    fn step_by_rank() {
+        let items = [1, 2, 3, 4, 5];
+        for step in 1..70 {
+            let stepped: alloc::vec::Vec<_> = items.permutations::<3>().step_by(step).collect();
+            let mut samples = items.permutations::<3>().step_by_rank(step);
+            assert_eq!(samples.size_hint(), (stepped.len(), Some(stepped.len())));
+            assert!(samples.by_ref().eq(stepped));
+        }
+        let items: alloc::vec::Vec<_> = (0..40).collect();
+        let samples = items.permutations::<2>().step_by_rank(100);
+        assert_eq!(samples.count(), 16);
+    }
+
+    #[test]
+    fn from_rank_range() {
+        use crate::SlicePermutations;
+
+        let items = [1, 2, 3, 4];
+        let all: alloc::vec::Vec<_> = items.permutations::<3>().collect();
+        for start in 0..=26 {
+            for end in start..=26 {
+                let chunk = SlicePermutations::<_, 3>::from_rank_range(&items, start..end);
+                let expected = &all[start.min(24)..end.min(24)];
+                assert_eq!(chunk.size_hint(), (expected.len(), Some(expected.len())));
+                assert!(chunk.eq(expected.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn parity_classes() {
+        use crate::{Parity, Perm};
+
+        let indices = [0, 1, 2, 3];
+        let mut even = indices.even_permutations::<4>();
+        let mut odd = indices.odd_permutations::<4>();
+        assert_eq!(
+            (even.size_hint(), odd.size_hint()),
+            ((12, Some(12)), (12, Some(12)))
+        );
+        for (mut iter, parity) in [(even.clone(), Parity::Even), (odd.clone(), Parity::Odd)] {
+            assert!(iter.all(|p| Perm::new(p.map(|&i| i)).unwrap().parity() == parity));
+        }
+        let mut all: alloc::vec::Vec<_> = even.by_ref().chain(odd.by_ref()).collect();
+        all.sort();
+        let mut expected: alloc::vec::Vec<_> = indices.permutations::<4>().collect();
+        expected.sort();
+        assert_eq!(all, expected);
+
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(items.even_permutations::<3>().count(), 30);
+        assert_eq!(items.odd_permutations::<3>().size_hint(), (30, Some(30)));
+        assert_eq!(items.even_permutations::<1>().size_hint(), (5, Some(5)));
+        assert_eq!(items.odd_permutations::<1>().next(), None);
+        assert!(items.odd_permutations::<1>().is_empty());
+        assert!(!items.even_permutations::<1>().is_empty());
+        assert_eq!(items.odd_permutations::<0>().size_hint(), (0, Some(0)));
+        assert_eq!(items.odd_permutations::<6>().next(), None);
+
+        // The rank of these permutations doesn't fit in a `usize`
+        let items: alloc::vec::Vec<u8> = (0..22).collect();
+        let mut odd = items.odd_permutations::<22>();
+        assert_eq!(odd.next().map(|p| p[..2] == [&1, &0]), Some(true));
+        let mut even = items.even_permutations::<22>();
+        assert_eq!(even.next().map(|p| p[0] == &0), Some(true));
+        assert!(even
+            .take(100)
+            .all(|p| Perm::new(p.map(|&i| usize::from(i))).unwrap().parity() == Parity::Even));
+    }
+
+    #[test]
+    fn with_parity() {
+        use crate::Perm;
+
+        let indices = [0, 1, 2, 3, 4];
+        let mut permutations = indices.permutations_copied::<5>().with_parity();
+        assert_eq!(permutations.size_hint(), (120, Some(120)));
+        for (p, parity) in permutations.by_ref().take(70) {
+            assert_eq!(Perm::new(p).unwrap().parity(), parity);
+        }
+        assert_eq!(permutations.size_hint(), (50, Some(50)));
+        // Parity restarts at the sorted arrangement of every combination
+        let mut permutations = indices.permutations::<2>().with_parity();
+        assert!(permutations.all(|([a, b], parity)| (a < b) == (parity.sign() == 1)));
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut permutations = [1, 2, 3, 4].permutations::<4>();
+        for len in (0..=24).rev() {
+            assert_eq!(permutations.size_hint(), (len, Some(len)));
+            permutations.next();
+        }
+    }
+
+    #[test]
+    fn advance_by() {
+        let items = [1, 2, 3, 4, 5];
+        for start in 0..62 {
+            for n in 0..66 {
+                let mut jumped = items.permutations::<3>();
+                let mut stepped = jumped.clone();
+                jumped.advance_by(start).ok();
+                stepped.by_ref().take(start).for_each(drop);
+                let res = jumped.advance_by(n);
+                let taken = stepped.by_ref().take(n).count();
+                assert_eq!(
+                    res.map_err(|k| k.get()),
+                    if taken == n { Ok(()) } else { Err(n - taken) }
+                );
+                assert!(jumped.eq(stepped));
+            }
+        }
+        let items: alloc::vec::Vec<usize> = (0..10).collect();
+        // Landing in the middle of a combination seeks to the permutation
+        let mut permutations = items.permutations::<8>();
+        permutations.nth(40_000);
+        let mut expected = items.permutations::<8>();
+        expected.by_ref().take(40_001).for_each(drop);
+        assert!(permutations.take(100).eq(expected.take(100)));
+        #[cfg(target_pointer_width = "64")]
+        {
+            let items: alloc::vec::Vec<usize> = (0..500).collect();
+            let mut permutations = items.permutations::<4>();
+            let last = [0, 1, 2, 3].permutations::<4>().last().unwrap();
+            let expected = last.map(|&i| &items[496 + i]);
+            assert_eq!(permutations.nth(24 * 2_573_031_125 - 1), Some(expected));
+            assert_eq!(permutations.next(), None);
+        }
+    }
+
+    #[test]
+    fn for_each() {
+        let items = [1, 2, 3, 4, 5];
+        let mut all = alloc::vec::Vec::new();
+        items.permutations::<3>().for_each(|p| all.push(p));
+        assert!(all.into_iter().eq(items.permutations::<3>()));
+    }
+
+    #[test]
+    fn try_fold() {
+        let mut permutations = [1, 2, 3].permutations();
+        assert!(permutations.any(|[&a, &b]| a > b));
+        assert_eq!(permutations.next(), Some([&1, &3]));
+        assert_eq!(permutations.try_fold(0, |acc, _| Some(acc + 1)), Some(3));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(
+            [1, 2, 3, 4].permutations::<3>().fold(0, |acc, _| acc + 1),
+            24
+        );
+    }
+
     #[test]
     fn order() {
         let mut permutations = [1, 2, 3].permutations();
@@ -331,3 +2195,187 @@ mod slice_test {
         assert_eq!(permutations.next(), None);
     }
 }
+
+#[cfg(test)]
+mod mut_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut items = [1, 2, 3];
+        let mut permutations = items.permutations_mut();
+        assert_eq!(permutations.remaining(), Some(6));
+        assert_eq!(permutations.next(), Some([&mut 1, &mut 2]));
+        assert_eq!(permutations.next(), Some([&mut 2, &mut 1]));
+        assert_eq!(permutations.next(), Some([&mut 1, &mut 3]));
+        assert_eq!(permutations.next(), Some([&mut 3, &mut 1]));
+        assert_eq!(permutations.next(), Some([&mut 2, &mut 3]));
+        assert_eq!(permutations.next(), Some([&mut 3, &mut 2]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.remaining(), Some(0));
+    }
+
+    #[test]
+    fn matches_references() {
+        let items = [3, 1, 4, 1, 5];
+        let mut copy = items;
+        let mut permutations = copy.permutations_mut::<3>();
+        let mut expected = items.permutations::<3>();
+        while let Some(p) = permutations.next() {
+            assert_eq!(Some(p.map(|t| *t)), expected.next().map(|p| p.map(|&t| t)));
+        }
+        assert_eq!(expected.next(), None);
+    }
+
+    #[test]
+    fn mutates_in_place() {
+        let mut items = [0; 4];
+        let mut permutations = items.permutations_mut::<2>();
+        while let Some([first, _]) = permutations.next() {
+            *first += 1;
+        }
+        assert_eq!(items, [3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut items = [1];
+        let mut permutations = items.permutations_mut::<2>();
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut items = [1, 2];
+        let mut permutations = items.permutations_mut::<0>();
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod copied_test {
+    use crate::SliceExt;
+
+    #[test]
+    fn order() {
+        let mut permutations = [1, 2, 3].permutations_copied();
+        assert_eq!(permutations.next(), Some([1, 2]));
+        assert_eq!(permutations.next(), Some([2, 1]));
+        assert_eq!(permutations.next(), Some([1, 3]));
+        assert_eq!(permutations.next(), Some([3, 1]));
+        assert_eq!(permutations.next(), Some([2, 3]));
+        assert_eq!(permutations.next(), Some([3, 2]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn matches_references() {
+        let items = [3u8, 1, 4, 1, 5, 9];
+        assert!(items
+            .permutations_copied::<4>()
+            .eq(items.permutations::<4>().map(|p| p.map(|&t| t))));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut permutations = [1].permutations_copied::<2>();
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod circular_test {
+    use super::*;
+    use crate::{IterExt, SliceExt};
+
+    #[test]
+    fn advance_by() {
+        let items = [1, 2, 3, 4, 5];
+        for start in 0..32 {
+            for n in 0..34 {
+                let mut jumped = items.circular_permutations::<4>();
+                let mut stepped = jumped.clone();
+                jumped.advance_by(start).ok();
+                stepped.by_ref().take(start).for_each(drop);
+                let res = jumped.advance_by(n);
+                let taken = stepped.by_ref().take(n).count();
+                assert_eq!(
+                    res.map_err(|k| k.get()),
+                    if taken == n { Ok(()) } else { Err(n - taken) }
+                );
+                assert!(jumped.eq(stepped));
+            }
+        }
+    }
+
+    #[test]
+    fn order() {
+        let mut permutations = (1..5).circular_permutations();
+        assert_eq!(permutations.next(), Some([1, 2, 3]));
+        assert_eq!(permutations.next(), Some([1, 3, 2]));
+        assert_eq!(permutations.next(), Some([1, 2, 4]));
+        assert_eq!(permutations.next(), Some([1, 4, 2]));
+        assert_eq!(permutations.next(), Some([1, 3, 4]));
+        assert_eq!(permutations.next(), Some([1, 4, 3]));
+        assert_eq!(permutations.next(), Some([2, 3, 4]));
+        assert_eq!(permutations.next(), Some([2, 4, 3]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn gen_order() {
+        let mut gen = LazyPermutationGenerator::fixing_first();
+        assert_eq!(gen.indices(), &[0, 1, 2, 3]);
+        gen.step();
+        assert_eq!(gen.indices(), &[0, 2, 1, 3]);
+        gen.step();
+        assert_eq!(gen.indices(), &[0, 3, 1, 2]);
+        gen.step();
+        gen.step();
+        gen.step();
+        assert!(!gen.is_done());
+        gen.step();
+        assert!(gen.is_done());
+    }
+
+    #[test]
+    fn no_rotations() {
+        let items = [1, 2, 3, 4, 5];
+        let all: Vec<_> = items.circular_permutations::<4>().collect();
+        // C(5, 4) * 3!
+        assert_eq!(all.len(), 30);
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                let rotated = (0..4).any(|r| (0..4).all(|j| a[j] == b[(j + r) % 4]));
+                assert!(!rotated);
+            }
+        }
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut permutations = [1].circular_permutations::<2>();
+        assert!(permutations.is_empty());
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+        let mut permutations = (1..2).circular_permutations::<2>();
+        assert!(permutations.is_exhausted());
+        assert_eq!(permutations.next(), None);
+        assert!(!(1..4).circular_permutations::<2>().is_exhausted());
+        assert!(![1, 2].circular_permutations::<2>().is_empty());
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = (1..5).circular_permutations();
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+}
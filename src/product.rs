@@ -0,0 +1,405 @@
+use crate::make_array;
+use core::convert::TryFrom;
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyOdometerGenerator<const N: usize> {
+    digits: [usize; N],
+    done: bool,
+}
+
+impl<const N: usize> LazyOdometerGenerator<N> {
+    pub fn new() -> Self {
+        Self {
+            digits: [0; N],
+            done: false,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn digits(&self) -> &[usize; N] {
+        &self.digits
+    }
+
+    pub fn step(&mut self, bases: &[usize; N]) {
+        // Increment the last digit, carrying into the previous ones
+        let mut i = N;
+        while i > 0 {
+            i -= 1;
+            self.digits[i] += 1;
+            if self.digits[i] < bases[i] {
+                return;
+            }
+            self.digits[i] = 0;
+        }
+        self.done = true;
+    }
+}
+
+/// Return an iterator over every array of `D` digits where each digit is less
+/// than its base in `bases`.
+///
+/// The last digit varies fastest, so the digit arrays are produced in
+/// lexicographic order, which is also the order of their [`rank`]. If any of the
+/// bases is zero the resulting iterator will yield no items.
+///
+/// [`rank`]: MixedRadix::rank
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::mixed_radix;
+///
+/// let mut digits = mixed_radix([2, 3]);
+/// assert_eq!(digits.next(), Some([0, 0]));
+/// assert_eq!(digits.next(), Some([0, 1]));
+/// assert_eq!(digits.next(), Some([0, 2]));
+/// assert_eq!(digits.next(), Some([1, 0]));
+/// assert_eq!(digits.next(), Some([1, 1]));
+/// assert_eq!(digits.next(), Some([1, 2]));
+/// assert_eq!(digits.next(), None);
+/// ```
+pub fn mixed_radix<const D: usize>(bases: [usize; D]) -> MixedRadix<D> {
+    MixedRadix::new(bases)
+}
+
+/// An iterator that returns the digits of a mixed-radix counter.
+///
+/// This `struct` is created by the [`mixed_radix`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct MixedRadix<const D: usize> {
+    bases: [usize; D],
+    gen: LazyOdometerGenerator<D>,
+}
+
+impl<const D: usize> MixedRadix<D> {
+    pub(crate) fn new(bases: [usize; D]) -> Self {
+        let mut gen = LazyOdometerGenerator::new();
+        if bases.contains(&0) {
+            gen.done = true;
+        }
+        Self { bases, gen }
+    }
+
+    /// Return the bases of the digits.
+    pub fn bases(&self) -> &[usize; D] {
+        &self.bases
+    }
+
+    /// Return the position of `digits` in the sequence of all digit arrays.
+    ///
+    /// Returns `None` if a digit is not less than its base, or if the rank does
+    /// not fit in a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::mixed_radix;
+    ///
+    /// let digits = mixed_radix([2, 3]);
+    /// assert_eq!(digits.rank(&[1, 1]), Some(4));
+    /// assert_eq!(digits.rank(&[2, 0]), None);
+    /// ```
+    pub fn rank(&self, digits: &[usize; D]) -> Option<usize> {
+        digits
+            .iter()
+            .zip(&self.bases)
+            .try_fold(0usize, |rank, (&digit, &base)| {
+                if digit < base {
+                    rank.checked_mul(base)?.checked_add(digit)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Return the digit array at position `rank` in the sequence of all digit
+    /// arrays.
+    ///
+    /// Returns `None` if there are no more than `rank` digit arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::mixed_radix;
+    ///
+    /// let digits = mixed_radix([2, 3]);
+    /// assert_eq!(digits.unrank(4), Some([1, 1]));
+    /// assert_eq!(digits.unrank(6), None);
+    /// ```
+    pub fn unrank(&self, mut rank: usize) -> Option<[usize; D]> {
+        let mut digits = [0; D];
+        for i in (0..D).rev() {
+            let base = self.bases[i];
+            if base == 0 {
+                return None;
+            }
+            digits[i] = rank % base;
+            rank /= base;
+        }
+        if rank == 0 {
+            Some(digits)
+        } else {
+            None
+        }
+    }
+}
+
+impl<const D: usize> Iterator for MixedRadix<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.digits();
+            self.gen.step(&self.bases);
+            Some(res)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.gen.is_done() {
+            return (0, Some(0));
+        }
+        // The digits left to go for each position form the rank of the last
+        // digit array counted from the current one
+        let remaining =
+            self.bases
+                .iter()
+                .zip(self.gen.digits())
+                .try_fold(1u128, |n, (&base, &digit)| {
+                    (n - 1)
+                        .checked_mul(base as u128)?
+                        .checked_add((base - digit) as u128)
+                });
+        match remaining {
+            Some(n) => (
+                usize::try_from(n).unwrap_or(usize::MAX),
+                usize::try_from(n).ok(),
+            ),
+            None => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<[usize; D]> {
+        if self.gen.is_done() {
+            return None;
+        }
+        // Jump straight to the requested digits by adding `n` to them, carrying
+        // into the previous ones
+        let mut carry = n as u128;
+        for (digit, &base) in self.gen.digits.iter_mut().zip(&self.bases).rev() {
+            let sum = *digit as u128 + carry;
+            *digit = (sum % base as u128) as usize;
+            carry = sum / base as u128;
+        }
+        if carry > 0 {
+            self.gen.done = true;
+            return None;
+        }
+        self.next()
+    }
+}
+
+impl<const D: usize> FusedIterator for MixedRadix<D> {}
+
+/// Return an iterator over every way of taking one element from each of the
+/// `N` slices.
+///
+/// The iterator produces a new array per iteration, and returns references to the
+/// elements of the slices. The last slice varies fastest. If any of the slices is
+/// empty the resulting iterator will yield no items.
+///
+/// Each slice acts as the alphabet of one position, so this also enumerates a
+/// keyspace with a separate character class per position. Skipping ahead with
+/// [`Iterator::nth`] takes constant time per digit, which allows a keyspace to be
+/// split up between workers.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::multi_cartesian_product;
+///
+/// let mut product = multi_cartesian_product([&[1, 2][..], &[3], &[4, 5]]);
+/// assert_eq!(product.next(), Some([&1, &3, &4]));
+/// assert_eq!(product.next(), Some([&1, &3, &5]));
+/// assert_eq!(product.next(), Some([&2, &3, &4]));
+/// assert_eq!(product.next(), Some([&2, &3, &5]));
+/// assert_eq!(product.next(), None);
+/// ```
+///
+/// Enumerate every PIN made up of a letter followed by two digits:
+///
+/// ```
+/// use const_combinations::multi_cartesian_product;
+///
+/// let letters = ['a', 'b', 'c'];
+/// let digits = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+/// let mut keyspace = multi_cartesian_product([&letters[..], &digits, &digits]);
+/// assert_eq!(keyspace.size_hint(), (300, Some(300)));
+/// assert_eq!(keyspace.nth(123), Some([&'b', &'2', &'3']));
+/// assert_eq!(keyspace.next(), Some([&'b', &'2', &'4']));
+/// assert_eq!(keyspace.size_hint(), (175, Some(175)));
+/// ```
+#[doc(alias = "keyspace")]
+pub fn multi_cartesian_product<T, const N: usize>(
+    slices: [&[T]; N],
+) -> MultiCartesianProduct<'_, T, N> {
+    MultiCartesianProduct::new(slices)
+}
+
+/// An iterator that returns one value from each of `N` slices.
+///
+/// This `struct` is created by the [`multi_cartesian_product`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct MultiCartesianProduct<'a, T, const N: usize> {
+    slices: [&'a [T]; N],
+    digits: MixedRadix<N>,
+}
+
+impl<'a, T, const N: usize> MultiCartesianProduct<'a, T, N> {
+    pub(crate) fn new(slices: [&'a [T]; N]) -> Self {
+        Self {
+            slices,
+            digits: MixedRadix::new(make_array(|i| slices[i].len())),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for MultiCartesianProduct<'a, T, N> {
+    type Item = [&'a T; N];
+
+    fn next(&mut self) -> Option<[&'a T; N]> {
+        let digits = self.digits.next()?;
+        Some(make_array(|i| &self.slices[i][digits[i]]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.digits.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<[&'a T; N]> {
+        let digits = self.digits.nth(n)?;
+        Some(make_array(|i| &self.slices[i][digits[i]]))
+    }
+}
+
+impl<T, const N: usize> FusedIterator for MultiCartesianProduct<'_, T, N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut product = multi_cartesian_product([&[1, 2][..], &[3, 4]]);
+        assert_eq!(product.next(), Some([&1, &3]));
+        assert_eq!(product.next(), Some([&1, &4]));
+        assert_eq!(product.next(), Some([&2, &3]));
+        assert_eq!(product.next(), Some([&2, &4]));
+        assert_eq!(product.next(), None);
+        assert_eq!(product.next(), None);
+    }
+
+    #[test]
+    fn gen_order() {
+        let mut gen = LazyOdometerGenerator::new();
+        let bases = [2, 1, 3];
+        assert_eq!(gen.digits(), &[0, 0, 0]);
+        gen.step(&bases);
+        assert_eq!(gen.digits(), &[0, 0, 1]);
+        gen.step(&bases);
+        assert_eq!(gen.digits(), &[0, 0, 2]);
+        gen.step(&bases);
+        assert_eq!(gen.digits(), &[1, 0, 0]);
+        gen.step(&bases);
+        gen.step(&bases);
+        assert!(!gen.is_done());
+        gen.step(&bases);
+        assert!(gen.is_done());
+    }
+
+    #[test]
+    fn rank_round_trip() {
+        let digits = mixed_radix([3, 1, 4]);
+        for (rank, d) in digits.clone().enumerate() {
+            assert_eq!(digits.rank(&d), Some(rank));
+            assert_eq!(digits.unrank(rank), Some(d));
+        }
+        assert_eq!(digits.clone().count(), 12);
+        assert_eq!(digits.unrank(12), None);
+        assert_eq!(digits.rank(&[0, 1, 0]), None);
+        assert_eq!(
+            mixed_radix([usize::MAX, 2]).rank(&[usize::MAX - 1, 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn nth_matches_stepping() {
+        let bases = [2, 3, 2];
+        for n in 0..14 {
+            let mut digits = mixed_radix(bases);
+            digits.next();
+            let mut stepped = digits.clone();
+            for _ in 0..n {
+                stepped.next();
+            }
+            assert_eq!(digits.nth(n), stepped.next());
+            assert_eq!(
+                digits.size_hint(),
+                (10usize.saturating_sub(n), Some(10usize.saturating_sub(n)))
+            );
+            assert!(digits.eq(stepped));
+        }
+        let mut digits = mixed_radix([usize::MAX, usize::MAX]);
+        assert_eq!(digits.size_hint(), (usize::MAX, None));
+        assert_eq!(digits.nth(usize::MAX), Some([1, 0]));
+        let mut digits = mixed_radix([usize::MAX, 2]);
+        assert_eq!(digits.size_hint(), (usize::MAX, None));
+        assert_eq!(digits.nth(usize::MAX), Some([usize::MAX / 2, 1]));
+        assert_eq!(digits.size_hint(), (usize::MAX - 1, Some(usize::MAX - 1)));
+        assert_eq!(digits.nth(usize::MAX - 4), Some([usize::MAX - 2, 1]));
+        assert_eq!(digits.size_hint(), (2, Some(2)));
+        assert_eq!(digits.nth(1), Some([usize::MAX - 1, 1]));
+        assert_eq!(digits.nth(1), None);
+        assert_eq!(digits.next(), None);
+        let digits = mixed_radix([usize::MAX; 3]);
+        assert_eq!(digits.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn none_on_zero_base() {
+        let mut digits = mixed_radix([2, 0]);
+        assert_eq!(digits.next(), None);
+        assert_eq!(digits.unrank(0), None);
+        let mut digits = mixed_radix([]);
+        assert_eq!(digits.next(), Some([]));
+        assert_eq!(digits.next(), None);
+        assert_eq!(digits.unrank(0), Some([]));
+        assert_eq!(digits.unrank(1), None);
+    }
+
+    #[test]
+    fn none_on_empty_slice() {
+        let mut product = multi_cartesian_product([&[1, 2][..], &[]]);
+        assert_eq!(product.next(), None);
+        assert_eq!(product.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut product = multi_cartesian_product::<u8, 0>([]);
+        assert_eq!(product.next(), Some([]));
+        assert_eq!(product.next(), None);
+        assert_eq!(product.next(), None);
+    }
+}
@@ -0,0 +1,604 @@
+use crate::make_array;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::{empty, FusedIterator, Iterator, Sum};
+use core::ops::{Add, Range};
+
+/// Addition which reports overflow instead of panicking or wrapping.
+///
+/// The searches for combinations with a given sum use this to compare sums
+/// which don't fit in the element type with the target. It's implemented for
+/// all the primitive integers.
+pub trait CheckedAdd: Sized {
+    /// Return `self + rhs`, or `None` if it overflows.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add {
+    ($($t:ty),*) => {$(
+        impl CheckedAdd for $t {
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_checked_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Return the positions of `items` in the order of their values.
+///
+/// The sort is stable, so an already sorted slice keeps its positions in order.
+fn sorted_order<T: Ord>(items: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&i| &items[i]);
+    order
+}
+
+/// Compare the sum of `sorted`, whose elements are in ascending order, with
+/// `target`, even when the sum doesn't fit in a `T`.
+fn cmp_sum<T, I>(mut sorted: I, target: T) -> Ordering
+where
+    T: Copy + Ord + CheckedAdd + Sum,
+    I: DoubleEndedIterator<Item = T>,
+{
+    let zero: T = empty().sum();
+    let mut total = zero;
+    loop {
+        // Take from the end which pulls the running total back towards zero,
+        // so it can only overflow once the elements left all have one sign
+        let next = if total >= zero {
+            sorted.next()
+        } else {
+            sorted.next_back()
+        };
+        match next {
+            None => return total.cmp(&target),
+            Some(t) => match total.checked_add(t) {
+                Some(sum) => total = sum,
+                // The rest of the elements only take the sum further out
+                None if t > zero => return Ordering::Greater,
+                None => return Ordering::Less,
+            },
+        }
+    }
+}
+
+/// An iterator that returns k-length combinations of values from `slice` whose
+/// total weight is within a budget.
+///
+/// This `struct` is created by the [`weight_bounded_combinations`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`weight_bounded_combinations`]: super::SliceExt::weight_bounded_combinations
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceWeightBoundedCombinations<'a, T, W, const K: usize> {
+    items: &'a [T],
+    weights: Vec<W>,
+    budget: W,
+    indices: [usize; K],
+    // `sums[i]` is the total weight of the elements at `indices[..=i]`
+    sums: Vec<W>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T, W, const K: usize> SliceWeightBoundedCombinations<'a, T, W, K>
+where
+    W: Copy + PartialOrd + Add<Output = W>,
+{
+    pub(crate) fn new<F>(items: &'a [T], budget: W, weight: F) -> Self
+    where
+        F: FnMut(&'a T) -> W,
+    {
+        Self {
+            items,
+            weights: items.iter().map(weight).collect(),
+            budget,
+            indices: [0; K],
+            sums: Vec::with_capacity(K),
+            started: false,
+            done: K > items.len(),
+        }
+    }
+
+    /// Return whether choosing `candidate` at `pos` leaves room for at least one
+    /// combination within the budget.
+    ///
+    /// The lightest way to fill the positions from `pos` onwards is with the
+    /// elements right from `candidate`, since the weights are sorted.
+    fn fits(&self, pos: usize, candidate: usize) -> bool {
+        let end = candidate + (K - pos);
+        if end > self.weights.len() {
+            return false;
+        }
+        let mut total = match pos {
+            0 => self.weights[candidate],
+            _ => self.sums[pos - 1] + self.weights[candidate],
+        };
+        for &w in &self.weights[candidate + 1..end] {
+            if total > self.budget {
+                return false;
+            }
+            total = total + w;
+        }
+        total <= self.budget
+    }
+
+    fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let (mut pos, mut start) = if !self.started {
+            self.started = true;
+            if K == 0 {
+                return true;
+            }
+            (0, 0)
+        } else if K == 0 {
+            self.done = true;
+            return false;
+        } else {
+            (K - 1, self.indices[K - 1] + 1)
+        };
+        loop {
+            // When the lightest candidate doesn't fit, the heavier ones after it
+            // don't either, so there is no need to look at them
+            if self.fits(pos, start) {
+                self.indices[pos] = start;
+                self.sums.truncate(pos);
+                let sum = match pos {
+                    0 => self.weights[start],
+                    _ => self.sums[pos - 1] + self.weights[start],
+                };
+                self.sums.push(sum);
+                if pos + 1 == K {
+                    return true;
+                }
+                pos += 1;
+                start += 1;
+            } else if pos == 0 {
+                self.done = true;
+                return false;
+            } else {
+                pos -= 1;
+                start = self.indices[pos] + 1;
+            }
+        }
+    }
+}
+
+impl<'a, T, W, const K: usize> Iterator for SliceWeightBoundedCombinations<'a, T, W, K>
+where
+    W: Copy + PartialOrd + Add<Output = W>,
+{
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        if self.step() {
+            let indices = &self.indices;
+            Some(make_array(|i| &self.items[indices[i]]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, W, const K: usize> FusedIterator for SliceWeightBoundedCombinations<'_, T, W, K> where
+    W: Copy + PartialOrd + Add<Output = W>
+{
+}
+
+/// An iterator that returns k-length combinations of values from `slice` which
+/// add up to a target.
+///
+/// This `struct` is created by the [`combinations_with_sum`] method on
+/// [`SliceExt`]. See its documentation for more.
+///
+/// [`combinations_with_sum`]: super::SliceExt::combinations_with_sum
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceTargetSumCombinations<'a, T, const K: usize> {
+    items: &'a [T],
+    // The positions of the items from the smallest up, which the search runs
+    // through
+    order: Vec<usize>,
+    target: T,
+    indices: [usize; K],
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T, const K: usize> SliceTargetSumCombinations<'a, T, K>
+where
+    T: Copy + Ord + CheckedAdd + Sum,
+{
+    pub(crate) fn new(items: &'a [T], target: T) -> Self {
+        let zero = empty().sum();
+        Self {
+            items,
+            order: sorted_order(items),
+            target,
+            indices: [0; K],
+            started: false,
+            done: K > items.len() || (K == 0 && target != zero),
+        }
+    }
+
+    /// Move to the next combination with the target sum, returning `false` once
+    /// there are none left.
+    fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let (mut pos, mut start) = if !self.started {
+            self.started = true;
+            if K == 0 {
+                return true;
+            }
+            (0, 0)
+        } else if K == 0 {
+            self.done = true;
+            return false;
+        } else {
+            (K - 1, self.indices[K - 1] + 1)
+        };
+        let (items, order) = (self.items, &self.order);
+        let value = move |p: usize| items[order[p]];
+        let n = items.len();
+        loop {
+            let left = K - pos;
+            // The elements chosen so far followed by the ones at `next` and
+            // `rest`, which keeps them in ascending order
+            let with_prefix = |next: Range<usize>, rest: Range<usize>| {
+                self.indices[..pos]
+                    .iter()
+                    .copied()
+                    .chain(next)
+                    .chain(rest)
+                    .map(value)
+            };
+            let mut found = None;
+            for candidate in start..=n - left {
+                // The smallest sum is made with the elements right after the
+                // candidate, and only grows for the candidates after it
+                let smallest = with_prefix(candidate..candidate + left, 0..0);
+                if cmp_sum(smallest, self.target) == Ordering::Greater {
+                    break;
+                }
+                // The largest sum is made with the largest elements
+                let largest = with_prefix(candidate..candidate + 1, n - left + 1..n);
+                if cmp_sum(largest, self.target) != Ordering::Less {
+                    found = Some(candidate);
+                    break;
+                }
+            }
+            match found {
+                Some(c) => {
+                    self.indices[pos] = c;
+                    if pos + 1 == K {
+                        // The smallest and largest sums are the same here, so
+                        // this is the target
+                        return true;
+                    }
+                    pos += 1;
+                    start = c + 1;
+                }
+                None if pos == 0 => {
+                    self.done = true;
+                    return false;
+                }
+                None => {
+                    pos -= 1;
+                    start = self.indices[pos] + 1;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for SliceTargetSumCombinations<'a, T, K>
+where
+    T: Copy + Ord + CheckedAdd + Sum,
+{
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<[&'a T; K]> {
+        if self.step() {
+            let mut indices = self.indices.map(|p| self.order[p]);
+            indices.sort_unstable();
+            Some(indices.map(|i| &self.items[i]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const K: usize> FusedIterator for SliceTargetSumCombinations<'_, T, K> where
+    T: Copy + Ord + CheckedAdd + Sum
+{
+}
+
+/// An iterator that returns the subsets of the values from `slice` which add up
+/// to a target.
+///
+/// This `struct` is created by the [`subsets_with_sum`] method on [`SliceExt`].
+/// See its documentation for more.
+///
+/// [`subsets_with_sum`]: super::SliceExt::subsets_with_sum
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SliceSubsetSums<'a, T> {
+    items: &'a [T],
+    // The positions of the items from the smallest up, which the search runs
+    // through
+    order: Vec<usize>,
+    target: T,
+    indices: Vec<usize>,
+    // `sums[i]` is the sum of the elements at `indices[..i]`
+    sums: Vec<T>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a, T> SliceSubsetSums<'a, T>
+where
+    T: Copy + Ord + CheckedAdd + Sum,
+{
+    pub(crate) fn new(items: &'a [T], target: T) -> Self {
+        Self {
+            items,
+            order: sorted_order(items),
+            target,
+            indices: Vec::new(),
+            sums: vec![empty().sum()],
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Return the sum of the current subset with `candidate` added, if it
+    /// doesn't exceed the target.
+    fn fits(&self, candidate: usize) -> Option<T> {
+        let sum = self
+            .sums
+            .last()
+            .unwrap()
+            .checked_add(self.items[*self.order.get(candidate)?])?;
+        if sum <= self.target {
+            Some(sum)
+        } else {
+            None
+        }
+    }
+
+    /// Move to the next subset in lexicographic order whose sum doesn't exceed
+    /// the target, returning `false` once there are none left.
+    fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        } else if !self.started {
+            self.started = true;
+            return true;
+        }
+        // Extend the current subset with the next element, or else replace its
+        // last element with the next one. When an element doesn't fit, the
+        // larger ones after it don't either.
+        let mut candidate = self.indices.last().map_or(0, |&i| i + 1);
+        loop {
+            if let Some(sum) = self.fits(candidate) {
+                self.indices.push(candidate);
+                self.sums.push(sum);
+                return true;
+            }
+            match self.indices.pop() {
+                Some(i) => {
+                    self.sums.pop();
+                    candidate = i + 1;
+                }
+                None => {
+                    self.done = true;
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for SliceSubsetSums<'a, T>
+where
+    T: Copy + Ord + CheckedAdd + Sum,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        while self.step() {
+            if *self.sums.last().unwrap() == self.target {
+                let mut indices: Vec<usize> = self.indices.iter().map(|&p| self.order[p]).collect();
+                indices.sort_unstable();
+                return Some(indices.into_iter().map(|i| &self.items[i]).collect());
+            }
+        }
+        None
+    }
+}
+
+impl<T> FusedIterator for SliceSubsetSums<'_, T> where T: Copy + Ord + CheckedAdd + Sum {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let mut combinations = [1, 2, 3, 4].weight_bounded_combinations::<2, _, _>(5, |&w| w);
+        assert_eq!(combinations.next(), Some([&1, &2]));
+        assert_eq!(combinations.next(), Some([&1, &3]));
+        assert_eq!(combinations.next(), Some([&1, &4]));
+        assert_eq!(combinations.next(), Some([&2, &3]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let mut items: Vec<u32> = (0..12).map(|i| i * i % 17).collect();
+        items.sort();
+        for budget in [0, 5, 20, 40, 200] {
+            let mut filtered: Vec<_> = items
+                .combinations::<4>()
+                .filter(|c| c.iter().copied().sum::<u32>() <= budget)
+                .collect();
+            filtered.sort();
+            let mut bounded: Vec<_> = items
+                .weight_bounded_combinations::<4, _, _>(budget, |&w| w)
+                .collect();
+            bounded.sort();
+            assert_eq!(bounded, filtered);
+        }
+    }
+
+    #[test]
+    fn prunes() {
+        let mut calls = 0;
+        let items = [1.5, 2.0, 2.5, 100.0, 200.0, 300.0];
+        let count = items
+            .weight_bounded_combinations::<2, _, _>(10.0, |&w| {
+                calls += 1;
+                w
+            })
+            .count();
+        assert_eq!((count, calls), (3, 6));
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = [1, 2].weight_bounded_combinations::<3, _, _>(100, |&w| w);
+        assert_eq!(combinations.next(), None);
+        let mut combinations = [1, 2].weight_bounded_combinations::<2, _, _>(2, |&w| w);
+        assert_eq!(combinations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = [1, 2].weight_bounded_combinations::<0, _, _>(0, |&w| w);
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod sum_test {
+    use crate::SliceExt;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn order() {
+        let mut combinations = [1, 2, 3, 4, 5].combinations_with_sum::<2>(6);
+        assert_eq!(combinations.next(), Some([&1, &5]));
+        assert_eq!(combinations.next(), Some([&2, &4]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(combinations.next(), None);
+        let mut subsets = [1, 2, 3, 4, 5].subsets_with_sum(6);
+        assert_eq!(subsets.next(), Some(vec![&1, &2, &3]));
+        assert_eq!(subsets.next(), Some(vec![&1, &5]));
+        assert_eq!(subsets.next(), Some(vec![&2, &4]));
+        assert_eq!(subsets.next(), None);
+        assert_eq!(subsets.next(), None);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let mut items: Vec<i32> = (0..14).map(|i| i * i % 19 - 6).collect();
+        items.sort();
+        for target in [-20, -3, 0, 7, 30, 100] {
+            let mut filtered: Vec<_> = items
+                .combinations::<4>()
+                .filter(|c| c.iter().copied().sum::<i32>() == target)
+                .collect();
+            filtered.sort();
+            let mut found: Vec<_> = items.combinations_with_sum::<4>(target).collect();
+            found.sort();
+            assert_eq!(found, filtered);
+        }
+    }
+
+    #[test]
+    fn subsets_match_filtered() {
+        let items = [0u32, 1, 1, 2, 3, 5, 8, 13];
+        for target in [0, 1, 7, 13, 33, 34] {
+            let filtered = items
+                .subsets_by_size()
+                .filter(|s| s.iter().copied().sum::<u32>() == target)
+                .count();
+            assert_eq!(items.subsets_with_sum(target).count(), filtered);
+        }
+    }
+
+    #[test]
+    fn empty_sum() {
+        assert_eq!([1, 2].combinations_with_sum::<0>(0).next(), Some([]));
+        assert_eq!([1, 2].combinations_with_sum::<0>(1).next(), None);
+        let mut subsets = [1, 2].subsets_with_sum(0);
+        assert_eq!(subsets.next(), Some(vec![]));
+        assert_eq!(subsets.next(), None);
+    }
+
+    #[test]
+    fn unsorted() {
+        let items = [5, -2, 4, 0, 3, 1];
+        let mut combinations = items.combinations_with_sum::<3>(2);
+        assert_eq!(combinations.next(), Some([&-2, &4, &0]));
+        assert_eq!(combinations.next(), Some([&-2, &3, &1]));
+        assert_eq!(combinations.next(), None);
+        for target in [-2, 0, 5, 9, 12] {
+            let filtered = items
+                .combinations::<3>()
+                .filter(|c| c.iter().copied().sum::<i32>() == target)
+                .count();
+            assert_eq!(items.combinations_with_sum::<3>(target).count(), filtered);
+        }
+        let items = [8u32, 1, 13, 0, 5, 2, 1, 3];
+        let mut subsets = items.subsets_with_sum(4);
+        assert_eq!(subsets.next(), Some(vec![&1, &0, &2, &1]));
+        for target in [0, 1, 7, 13, 33] {
+            let filtered = items
+                .subsets_by_size()
+                .filter(|s| s.iter().copied().sum::<u32>() == target)
+                .count();
+            assert_eq!(items.subsets_with_sum(target).count(), filtered);
+        }
+    }
+
+    #[test]
+    fn sums_out_of_range() {
+        let items = [1u8, 2, 3, 200, 200, 200];
+        let mut combinations = items.combinations_with_sum::<3>(6);
+        assert_eq!(combinations.next(), Some([&1, &2, &3]));
+        assert_eq!(combinations.next(), None);
+        assert_eq!(items.combinations_with_sum::<2>(203).count(), 3);
+        assert_eq!(items.subsets_with_sum(206).count(), 3);
+
+        let items: [i8; 9] = [-128, -100, -90, -5, 0, 3, 90, 100, 127];
+        for target in [-128, -100, -2, 0, 7, 100, 127] {
+            let filtered = items
+                .combinations::<3>()
+                .filter(|c| c.iter().map(|&&t| i32::from(t)).sum::<i32>() == i32::from(target))
+                .count();
+            assert_eq!(items.combinations_with_sum::<3>(target).count(), filtered);
+        }
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        assert_eq!([1, 2].combinations_with_sum::<3>(3).next(), None);
+        assert_eq!([1, 2].subsets_with_sum(4).next(), None);
+    }
+}
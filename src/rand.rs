@@ -0,0 +1,223 @@
+//! Random sampling with a [`rand`] generator.
+//!
+//! [`rand`]: ::rand
+
+use crate::counting::binomial;
+use crate::{make_array, try_make_array, SliceCombinations};
+use ::rand::Rng;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::iter::Iterator;
+
+/// Choose `K` elements of `iter` uniformly at random, with reservoir sampling.
+pub(crate) fn choose_array<I, R, const K: usize>(mut iter: I, rng: &mut R) -> Option<[I::Item; K]>
+where
+    I: Iterator,
+    R: Rng + ?Sized,
+{
+    let mut sample: [I::Item; K] = try_make_array(|_| iter.next()).ok()?;
+    if K == 0 {
+        return Some(sample);
+    }
+    // Every element replaces one in the sample with probability `K / seen`
+    for (seen, item) in (K + 1..).zip(iter) {
+        let j = rng.gen_range(0..seen);
+        if j < K {
+            sample[j] = item;
+        }
+    }
+    Some(sample)
+}
+
+/// Return a derangement of `N` positions chosen uniformly at random.
+///
+/// A derangement is a permutation which moves every position, as enumerated by
+/// [`fixed_point_permutations`] with `m` set to zero. Random permutations are
+/// shuffled one position at a time from the back, and a shuffle is restarted as
+/// soon as it leaves a position in place. Every completed shuffle is a uniform
+/// random permutation without fixed points, so the result is uniform too, and
+/// since about one in `e` permutations is a derangement only a few shuffles are
+/// started on average. Returns `None` if `N` is one, since a single position
+/// can't be moved.
+///
+/// [`fixed_point_permutations`]: crate::fixed_point_permutations
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::random_derangement;
+/// use rand::rngs::SmallRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// let secret_santa: [usize; 6] = random_derangement(&mut rng).unwrap();
+/// assert!(secret_santa.iter().enumerate().all(|(giver, &taker)| giver != taker));
+/// assert_eq!(random_derangement::<1, _>(&mut rng), None);
+/// ```
+#[doc(alias = "secret_santa")]
+pub fn random_derangement<const N: usize, R>(rng: &mut R) -> Option<[usize; N]>
+where
+    R: Rng + ?Sized,
+{
+    if N == 1 {
+        return None;
+    }
+    'shuffle: loop {
+        let mut indices: [usize; N] = make_array(|i| i);
+        for i in (0..N).rev() {
+            // Position `i` is final after this swap
+            indices.swap(i, rng.gen_range(0..=i));
+            if indices[i] == i {
+                continue 'shuffle;
+            }
+        }
+        return Some(indices);
+    }
+}
+
+impl<'a, T, const K: usize> SliceCombinations<'a, T, K> {
+    /// Choose `m` distinct combinations uniformly at random.
+    ///
+    /// Every combination has a rank, its position in the iteration order. This
+    /// draws `m` distinct ranks with Floyd's algorithm, which takes exactly `m`
+    /// random numbers, and then jumps straight to the combination at each of
+    /// them. Only the chosen ranks are stored, so this is cheap no matter how
+    /// many combinations there are to choose from. The combinations are
+    /// returned in iteration order, and if there are fewer than `m` of them all
+    /// of them are returned.
+    ///
+    /// The combinations are counted from the first one, regardless of how far
+    /// the iterator has advanced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of combinations doesn't fit in a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::SliceExt;
+    /// use rand::rngs::SmallRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(1);
+    /// let items: Vec<u32> = (0..60).collect();
+    /// // There are over 10^17 combinations to choose from
+    /// let samples = items.combinations::<30>().sample_distinct(5, &mut rng);
+    /// assert_eq!(samples.len(), 5);
+    /// assert!(samples.windows(2).all(|pair| pair[0] != pair[1]));
+    /// ```
+    pub fn sample_distinct<R>(&self, m: usize, rng: &mut R) -> Vec<[&'a T; K]>
+    where
+        R: Rng + ?Sized,
+    {
+        let count = binomial(self.items.len(), K).expect("too many combinations to rank");
+        let mut ranks = BTreeSet::new();
+        for j in count - m.min(count)..count {
+            // Either `t` is new, or `j` is since it is larger than all before it
+            let t = rng.gen_range(0..=j);
+            if !ranks.insert(t) {
+                ranks.insert(j);
+            }
+        }
+        ranks
+            .into_iter()
+            .filter_map(|rank| self.get(rank))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IterExt;
+    use ::rand::rngs::SmallRng;
+    use ::rand::SeedableRng;
+
+    #[test]
+    fn uniform() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut counts = [0usize; 10];
+        for _ in 0..10_000 {
+            let mut sample = (0..5).choose_array::<_, 2>(&mut rng).unwrap();
+            sample.sort();
+            let rank = (0..5).combinations::<2>().position(|c| c == sample);
+            counts[rank.unwrap()] += 1;
+        }
+        // Each of the ten pairs is expected 1000 times
+        assert!(
+            counts.iter().all(|&c| (900..1100).contains(&c)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn derangements_uniform() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let all: Vec<_> = crate::fixed_point_permutations::<4>(0)
+            .map(|p| *p.as_array())
+            .collect();
+        let mut counts = [0usize; 9];
+        for _ in 0..9_000 {
+            let derangement = random_derangement::<4, _>(&mut rng).unwrap();
+            counts[all.iter().position(|&d| d == derangement).unwrap()] += 1;
+        }
+        // Each of the nine derangements is expected 1000 times
+        assert!(
+            counts.iter().all(|&c| (900..1100).contains(&c)),
+            "{:?}",
+            counts
+        );
+        assert_eq!(random_derangement::<0, _>(&mut rng), Some([]));
+        assert_eq!(random_derangement::<1, _>(&mut rng), None);
+        assert_eq!(random_derangement::<2, _>(&mut rng), Some([1, 0]));
+    }
+
+    #[test]
+    fn distinct_uniform() {
+        use crate::SliceExt;
+
+        let mut rng = SmallRng::seed_from_u64(7);
+        let items = [0, 1, 2, 3, 4];
+        let all: Vec<_> = items.combinations::<3>().collect();
+        let mut counts = [0usize; 10];
+        for _ in 0..2_500 {
+            let samples = items.combinations::<3>().sample_distinct(4, &mut rng);
+            assert_eq!(samples.len(), 4);
+            for pair in samples.windows(2) {
+                let rank = |c| all.iter().position(|&a| a == c);
+                assert!(rank(pair[0]) < rank(pair[1]));
+            }
+            for sample in samples {
+                counts[all.iter().position(|&a| a == sample).unwrap()] += 1;
+            }
+        }
+        // Each of the ten combinations is expected in 1000 of the samples
+        assert!(
+            counts.iter().all(|&c| (900..1100).contains(&c)),
+            "{:?}",
+            counts
+        );
+        let samples = items.combinations::<3>().sample_distinct(20, &mut rng);
+        assert_eq!(samples, all);
+        assert!(items
+            .combinations::<6>()
+            .sample_distinct(1, &mut rng)
+            .is_empty());
+    }
+
+    #[test]
+    fn too_short() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        assert_eq!((0..2).choose_array::<_, 3>(&mut rng), None);
+        assert_eq!(
+            (0..3).choose_array::<_, 3>(&mut rng).map(|mut s| {
+                s.sort();
+                s
+            }),
+            Some([0, 1, 2])
+        );
+        assert_eq!((0..0).choose_array::<_, 0>(&mut rng), Some([]));
+    }
+}
@@ -0,0 +1,298 @@
+//! Parallel iterators over the combinations and permutations of a slice.
+//!
+//! Every combination and permutation of a slice has a rank, which is its
+//! position in the iteration order, and the iterators can jump straight to any
+//! rank. This lets rayon split a parallel iterator anywhere while knowing the
+//! exact length of each half, so it is an [`IndexedParallelIterator`].
+
+use crate::{SliceCombinations, SlicePermutations};
+use ::rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use ::rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+
+mod sealed {
+    /// Slice iterators which can be restarted from their first item.
+    pub trait Ranked: Iterator {
+        fn restart(&self) -> Self;
+    }
+}
+
+use sealed::Ranked;
+
+/// Return the number of items left in `iter`, which the slice iterators know
+/// exactly whenever it fits in a `usize`.
+fn exact_len<I>(iter: &I) -> usize
+where
+    I: Ranked,
+{
+    match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => upper,
+        _ => panic!("the number of items doesn't fit in a `usize`"),
+    }
+}
+
+impl<T, const K: usize> Ranked for SliceCombinations<'_, T, K> {
+    fn restart(&self) -> Self {
+        Self::new(self.items)
+    }
+}
+
+impl<T, const K: usize> Ranked for SlicePermutations<'_, T, K> {
+    fn restart(&self) -> Self {
+        Self::new(self.items)
+    }
+}
+
+/// A parallel iterator over the remaining items of a slice iterator.
+///
+/// This `struct` is created by calling `into_par_iter` on [`SliceCombinations`]
+/// or [`SlicePermutations`], and picks up wherever the iterator it was created
+/// from left off.
+///
+/// # Panics
+///
+/// `into_par_iter` panics if the number of items the slice iterator produces
+/// from its start doesn't fit in a `usize`, since an indexed parallel iterator
+/// needs to know its exact length.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::SliceExt;
+/// use rayon::prelude::*;
+///
+/// let items: Vec<u64> = (1..=20).collect();
+/// let sums: Vec<u64> = items
+///     .combinations::<3>()
+///     .into_par_iter()
+///     .map(|[a, b, c]| a + b + c)
+///     .collect();
+/// assert_eq!(sums.len(), 1140);
+/// assert_eq!(sums[0], 6);
+/// ```
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct ParRanks<I> {
+    iter: I,
+    // The number of items from the start of `iter`
+    total: usize,
+}
+
+impl<I> ParRanks<I>
+where
+    I: Ranked,
+{
+    fn new(iter: I) -> Self {
+        // Check the length up front, so that a count which is too large panics
+        // here rather than somewhere inside rayon
+        let total = exact_len(&iter.restart());
+        Self { iter, total }
+    }
+}
+
+impl<'a, T, const K: usize> IntoParallelIterator for SliceCombinations<'a, T, K>
+where
+    T: Sync,
+{
+    type Iter = ParRanks<Self>;
+    type Item = [&'a T; K];
+
+    fn into_par_iter(self) -> ParRanks<Self> {
+        ParRanks::new(self)
+    }
+}
+
+impl<'a, T, const K: usize> IntoParallelIterator for SlicePermutations<'a, T, K>
+where
+    T: Sync,
+{
+    type Iter = ParRanks<Self>;
+    type Item = [&'a T; K];
+
+    fn into_par_iter(self) -> ParRanks<Self> {
+        ParRanks::new(self)
+    }
+}
+
+impl<I> ParRanks<I>
+where
+    I: Ranked + Send,
+    I::Item: Send,
+{
+    fn into_producer(self) -> RankProducer<I> {
+        let front = self.total - exact_len(&self.iter);
+        RankProducer {
+            iter: self.iter,
+            front,
+            back: self.total,
+        }
+    }
+}
+
+impl<I> ParallelIterator for ParRanks<I>
+where
+    I: Ranked + Send,
+    I::Item: Send,
+{
+    type Item = I::Item;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<I::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(exact_len(&self.iter))
+    }
+}
+
+impl<I> IndexedParallelIterator for ParRanks<I>
+where
+    I: Ranked + Send,
+    I::Item: Send,
+{
+    fn len(&self) -> usize {
+        exact_len(&self.iter)
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<I::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<I::Item>,
+    {
+        callback.callback(self.into_producer())
+    }
+}
+
+/// The items with ranks in `front..back`, where `iter` is at rank `front`.
+struct RankProducer<I> {
+    iter: I,
+    front: usize,
+    back: usize,
+}
+
+impl<I> Producer for RankProducer<I>
+where
+    I: Ranked + Send,
+    I::Item: Send,
+{
+    type Item = I::Item;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.front + index;
+        let mut right = self.iter.restart();
+        let _ = right.advance_by(mid);
+        (
+            Self {
+                iter: self.iter,
+                front: self.front,
+                back: mid,
+            },
+            Self {
+                iter: right,
+                front: mid,
+                back: self.back,
+            },
+        )
+    }
+}
+
+impl<I> Iterator for RankProducer<I>
+where
+    I: Ranked,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.front == self.back {
+            None
+        } else {
+            self.front += 1;
+            self.iter.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<I> DoubleEndedIterator for RankProducer<I>
+where
+    I: Ranked,
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        if self.front == self.back {
+            None
+        } else {
+            self.back -= 1;
+            self.iter.restart().nth(self.back)
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for RankProducer<I> where I: Ranked {}
+
+impl<I> FusedIterator for RankProducer<I> where I: Ranked {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use ::rayon::prelude::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn matches_sequential() {
+        let items: Vec<u32> = (0..12).collect();
+        let par: Vec<_> = items.combinations::<4>().into_par_iter().collect();
+        assert!(par.into_iter().eq(items.combinations::<4>()));
+        let par: Vec<_> = items.permutations::<3>().into_par_iter().collect();
+        assert!(par.into_iter().eq(items.permutations::<3>()));
+    }
+
+    #[test]
+    fn indexed() {
+        let items: Vec<u32> = (0..10).collect();
+        let mut combinations = items.combinations::<3>();
+        combinations.nth(9);
+        let par = combinations.clone().into_par_iter();
+        assert_eq!(par.len(), 110);
+        let enumerated: Vec<_> = par.with_min_len(7).enumerate().collect();
+        assert!(enumerated.into_iter().eq(combinations.clone().enumerate()));
+        let zipped: Vec<_> = items
+            .permutations::<2>()
+            .into_par_iter()
+            .zip(combinations.clone().into_par_iter())
+            .collect();
+        assert!(zipped
+            .into_iter()
+            .eq(items.permutations::<2>().zip(combinations.clone())));
+        let reversed: Vec<_> = combinations.clone().into_par_iter().rev().collect();
+        assert!(reversed
+            .into_iter()
+            .eq(combinations.collect::<Vec<_>>().into_iter().rev()));
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of items doesn't fit in a `usize`")]
+    fn too_many_items() {
+        let items: Vec<u32> = (0..200).collect();
+        let mut combinations = items.combinations::<100>();
+        combinations.nth(10);
+        let _ = combinations.into_par_iter();
+    }
+}
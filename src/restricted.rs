@@ -0,0 +1,573 @@
+use crate::backtrack::LazyBacktrackGenerator;
+use crate::perm::Perm;
+use core::iter::{FusedIterator, Iterator};
+
+/// Return an iterator over the permutations of `N` positions which have exactly
+/// `m` fixed points.
+///
+/// A fixed point is a position `i` which the permutation leaves in place, so
+/// with `m` set to zero this yields the derangements. Permutations are built up
+/// one position at a time, and prefixes which can no longer end up with exactly
+/// `m` fixed points are abandoned without being completed. Permutations are
+/// produced in lexicographic order of their indices, and there are as many of
+/// them as the rencontres number `D(N, m)`. If `m` is greater than `N` the
+/// resulting iterator will yield no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::fixed_point_permutations;
+///
+/// let mut derangements = fixed_point_permutations::<3>(0);
+/// assert_eq!(derangements.next().unwrap().as_array(), &[1, 2, 0]);
+/// assert_eq!(derangements.next().unwrap().as_array(), &[2, 0, 1]);
+/// assert!(derangements.next().is_none());
+///
+/// assert_eq!(fixed_point_permutations::<4>(1).count(), 8);
+/// ```
+#[doc(alias = "derangements")]
+#[doc(alias = "rencontres")]
+pub fn fixed_point_permutations<const N: usize>(m: usize) -> FixedPointPermutations<N> {
+    FixedPointPermutations::new(m)
+}
+
+/// An iterator that returns the permutations with exactly m fixed points.
+///
+/// This `struct` is created by the [`fixed_point_permutations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct FixedPointPermutations<const N: usize> {
+    gen: LazyBacktrackGenerator<N>,
+    m: usize,
+}
+
+impl<const N: usize> FixedPointPermutations<N> {
+    pub(crate) fn new(m: usize) -> Self {
+        Self {
+            gen: LazyBacktrackGenerator::new(N),
+            m,
+        }
+    }
+}
+
+impl<const N: usize> Iterator for FixedPointPermutations<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        let m = self.m;
+        if m > N {
+            return None;
+        }
+        let found = self.gen.step(|pos, prefix, candidate| {
+            let fixed = prefix.iter().enumerate().filter(|&(i, &j)| i == j).count()
+                + usize::from(candidate == pos);
+            // Later positions can only stay in place if their index is unused
+            let taken = prefix.iter().filter(|&&j| j > pos).count() + usize::from(candidate > pos);
+            let possible = N - pos - 1 - taken;
+            fixed <= m && fixed + possible >= m
+        });
+        if found {
+            Some(Perm::new_unchecked(*self.gen.indices()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for FixedPointPermutations<N> {}
+
+/// Return an iterator over the involutions of `N` positions.
+///
+/// An involution is a permutation which is its own inverse, so it only swaps
+/// disjoint pairs of positions and leaves the rest in place. They're built
+/// directly by either fixing the first free position or pairing it with a later
+/// one, so no other permutations are ever generated. Involutions are produced in
+/// lexicographic order of their indices.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::involutions;
+///
+/// let mut involutions = involutions::<3>();
+/// assert_eq!(involutions.next().unwrap().as_array(), &[0, 1, 2]);
+/// assert_eq!(involutions.next().unwrap().as_array(), &[0, 2, 1]);
+/// assert_eq!(involutions.next().unwrap().as_array(), &[1, 0, 2]);
+/// assert_eq!(involutions.next().unwrap().as_array(), &[2, 1, 0]);
+/// assert!(involutions.next().is_none());
+/// ```
+pub fn involutions<const N: usize>() -> Involutions<N> {
+    Involutions::new()
+}
+
+/// An iterator that returns the permutations which are their own inverse.
+///
+/// This `struct` is created by the [`involutions`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct Involutions<const N: usize> {
+    gen: LazyBacktrackGenerator<N>,
+}
+
+impl<const N: usize> Involutions<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyBacktrackGenerator::new(N),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for Involutions<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        let found = self.gen.step(|pos, prefix, candidate| {
+            // A position paired up by an earlier one has to point back to it,
+            // any other position is either fixed or paired with a later one
+            match prefix.iter().position(|&j| j == pos) {
+                Some(partner) => candidate == partner,
+                None => candidate >= pos,
+            }
+        });
+        if found {
+            Some(Perm::new_unchecked(*self.gen.indices()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for Involutions<N> {}
+
+/// Return an iterator over the permutations of `N` positions which consist of a
+/// single cycle.
+///
+/// Following such a permutation from any position visits every other position
+/// before coming back, so each one describes a closed tour, like the ones a
+/// traveling salesman takes. Prefixes which would close a cycle early are never
+/// extended, which leaves `(N - 1)!` permutations. Permutations are produced in
+/// lexicographic order of their indices. If `N` is zero the resulting iterator
+/// will yield no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::cyclic_permutations;
+///
+/// let mut cycles = cyclic_permutations::<3>();
+/// assert_eq!(cycles.next().unwrap().as_array(), &[1, 2, 0]);
+/// assert_eq!(cycles.next().unwrap().as_array(), &[2, 0, 1]);
+/// assert!(cycles.next().is_none());
+///
+/// assert_eq!(cyclic_permutations::<5>().count(), 24);
+/// ```
+#[doc(alias = "tours")]
+pub fn cyclic_permutations<const N: usize>() -> CyclicPermutations<N> {
+    CyclicPermutations::new()
+}
+
+/// An iterator that returns the permutations made up of a single cycle.
+///
+/// This `struct` is created by the [`cyclic_permutations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct CyclicPermutations<const N: usize> {
+    gen: LazyBacktrackGenerator<N>,
+}
+
+impl<const N: usize> CyclicPermutations<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyBacktrackGenerator::new(N),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for CyclicPermutations<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        if N == 0 {
+            return None;
+        }
+        let found = self.gen.step(|pos, prefix, candidate| {
+            // Follow the path starting at the candidate through the positions
+            // already filled in, only the last position may close the cycle
+            let mut end = candidate;
+            while end < pos {
+                end = prefix[end];
+            }
+            end != pos || pos + 1 == N
+        });
+        if found {
+            Some(Perm::new_unchecked(*self.gen.indices()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for CyclicPermutations<N> {}
+
+/// Return an iterator over the alternating permutations of `N` positions.
+///
+/// The indices of an alternating permutation go up and down in turn, so
+/// `p[0] < p[1] > p[2] < p[3]` and so on. Candidates which break the pattern, or
+/// which leave nothing to go up or down to for the next position, are skipped
+/// without being extended. Permutations are produced in lexicographic order of
+/// their indices, and there are as many of them as the Euler zigzag number `E(N)`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::alternating_permutations;
+///
+/// let mut zigzags = alternating_permutations::<4>();
+/// assert_eq!(zigzags.next().unwrap().as_array(), &[0, 2, 1, 3]);
+/// assert_eq!(zigzags.next().unwrap().as_array(), &[0, 3, 1, 2]);
+/// assert_eq!(zigzags.next().unwrap().as_array(), &[1, 2, 0, 3]);
+/// assert_eq!(zigzags.next().unwrap().as_array(), &[1, 3, 0, 2]);
+/// assert_eq!(zigzags.next().unwrap().as_array(), &[2, 3, 0, 1]);
+/// assert!(zigzags.next().is_none());
+/// ```
+#[doc(alias = "zigzag")]
+#[doc(alias = "up_down")]
+pub fn alternating_permutations<const N: usize>() -> AlternatingPermutations<N> {
+    AlternatingPermutations::new()
+}
+
+/// An iterator that returns the permutations whose indices go up and down in
+/// turn.
+///
+/// This `struct` is created by the [`alternating_permutations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct AlternatingPermutations<const N: usize> {
+    gen: LazyBacktrackGenerator<N>,
+}
+
+impl<const N: usize> AlternatingPermutations<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazyBacktrackGenerator::new(N),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for AlternatingPermutations<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        let found = self.gen.step(|pos, prefix, candidate| {
+            // Odd positions go up from the previous index, even ones go down
+            let fits = match prefix.last() {
+                Some(&prev) if pos % 2 == 1 => candidate > prev,
+                Some(&prev) => candidate < prev,
+                None => true,
+            };
+            if !fits || pos + 1 == N {
+                return fits;
+            }
+            let above = prefix.iter().filter(|&&j| j > candidate).count();
+            if pos % 2 == 0 {
+                N - 1 - candidate > above
+            } else {
+                candidate > prefix.len() - above
+            }
+        });
+        if found {
+            Some(Perm::new_unchecked(*self.gen.indices()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for AlternatingPermutations<N> {}
+
+/// Return an iterator over the permutations of `N` positions which have exactly
+/// `d` descents.
+///
+/// A descent is a position `i` where the indices go down, so `p[i] > p[i + 1]`.
+/// Permutations are built up one position at a time, and prefixes which can no
+/// longer end up with exactly `d` descents are abandoned without being
+/// completed. Permutations are produced in lexicographic order of their indices,
+/// and there are as many of them as the Eulerian number `A(N, d)`. If `d` is at
+/// least `N` the resulting iterator will yield no items, apart from the empty
+/// permutation when both are zero.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::descent_permutations;
+///
+/// let mut permutations = descent_permutations::<3>(1);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[0, 2, 1]);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[1, 0, 2]);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[1, 2, 0]);
+/// assert_eq!(permutations.next().unwrap().as_array(), &[2, 0, 1]);
+/// assert!(permutations.next().is_none());
+///
+/// assert_eq!(descent_permutations::<5>(2).count(), 66);
+/// ```
+#[doc(alias = "eulerian")]
+pub fn descent_permutations<const N: usize>(d: usize) -> DescentPermutations<N> {
+    DescentPermutations::new(d)
+}
+
+/// An iterator that returns the permutations with exactly d descents.
+///
+/// This `struct` is created by the [`descent_permutations`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct DescentPermutations<const N: usize> {
+    gen: LazyBacktrackGenerator<N>,
+    d: usize,
+}
+
+impl<const N: usize> DescentPermutations<N> {
+    pub(crate) fn new(d: usize) -> Self {
+        Self {
+            gen: LazyBacktrackGenerator::new(N),
+            d,
+        }
+    }
+}
+
+impl<const N: usize> Iterator for DescentPermutations<N> {
+    type Item = Perm<N>;
+
+    fn next(&mut self) -> Option<Perm<N>> {
+        let d = self.d;
+        if d > N.saturating_sub(1) {
+            return None;
+        }
+        let found = self.gen.step(|pos, prefix, candidate| {
+            let descents = prefix.windows(2).filter(|w| w[0] > w[1]).count()
+                + usize::from(prefix.last().is_some_and(|&prev| prev > candidate));
+            let rest = N - pos - 1;
+            let smaller = candidate - prefix.iter().filter(|&&j| j < candidate).count();
+            // The rest can only avoid descents by going up from the candidate,
+            // and can only all be descents by going down from it
+            let (least, most) = match rest {
+                0 => (0, 0),
+                _ => (usize::from(smaller > 0), rest - usize::from(smaller < rest)),
+            };
+            descents + least <= d && d <= descents + most
+        });
+        if found {
+            Some(Perm::new_unchecked(*self.gen.indices()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for DescentPermutations<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::make_array;
+    use crate::SliceExt;
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A008290
+        assert_eq!(fixed_point_permutations::<5>(0).count(), 44);
+        assert_eq!(fixed_point_permutations::<5>(1).count(), 45);
+        assert_eq!(fixed_point_permutations::<5>(2).count(), 20);
+        assert_eq!(fixed_point_permutations::<5>(3).count(), 10);
+        assert_eq!(fixed_point_permutations::<5>(4).count(), 0);
+        assert_eq!(fixed_point_permutations::<5>(5).count(), 1);
+        assert_eq!(fixed_point_permutations::<7>(0).count(), 1854);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let indices: [usize; 5] = make_array(|i| i);
+        for m in 0..=5 {
+            let mut all: alloc::vec::Vec<_> = indices
+                .permutations::<5>()
+                .map(|p| make_array(|i| *p[i]))
+                .filter(|p: &[usize; 5]| {
+                    p.iter().enumerate().filter(|&(i, &j)| i == j).count() == m
+                })
+                .collect();
+            all.sort_unstable();
+            assert!(fixed_point_permutations::<5>(m)
+                .map(|p| *p.as_array())
+                .eq(all));
+        }
+    }
+
+    #[test]
+    fn none_on_too_many_fixed_points() {
+        let mut permutations = fixed_point_permutations::<2>(3);
+        assert!(permutations.next().is_none());
+        assert!(permutations.next().is_none());
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = fixed_point_permutations::<0>(0);
+        assert_eq!(permutations.next().map(|p| *p.as_array()), Some([]));
+        assert!(permutations.next().is_none());
+        assert!(fixed_point_permutations::<0>(1).next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod involution_test {
+    use super::*;
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000085
+        assert_eq!(involutions::<1>().count(), 1);
+        assert_eq!(involutions::<4>().count(), 10);
+        assert_eq!(involutions::<6>().count(), 76);
+        assert_eq!(involutions::<8>().count(), 764);
+    }
+
+    #[test]
+    fn self_inverse() {
+        let mut last = None;
+        for p in involutions::<6>() {
+            assert_eq!(p.inverse(), p);
+            assert!(last < Some(p));
+            last = Some(p);
+        }
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut involutions = involutions::<0>();
+        assert_eq!(involutions.next().map(|p| *p.as_array()), Some([]));
+        assert!(involutions.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod cyclic_test {
+    use super::*;
+    use crate::counting::factorial;
+
+    #[test]
+    fn count() {
+        assert_eq!(cyclic_permutations::<1>().count(), 1);
+        assert_eq!(cyclic_permutations::<2>().count(), 1);
+        assert_eq!(cyclic_permutations::<6>().count(), factorial(5).unwrap());
+        assert_eq!(cyclic_permutations::<8>().count(), factorial(7).unwrap());
+    }
+
+    #[test]
+    fn single_cycle() {
+        let mut last = None;
+        for p in cyclic_permutations::<6>() {
+            assert_eq!(p.cycle_type(), [6]);
+            assert!(last < Some(p));
+            last = Some(p);
+        }
+    }
+
+    #[test]
+    fn none_on_n_zero() {
+        let mut cycles = cyclic_permutations::<0>();
+        assert!(cycles.next().is_none());
+        assert!(cycles.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod alternating_test {
+    use super::*;
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000111
+        assert_eq!(alternating_permutations::<1>().count(), 1);
+        assert_eq!(alternating_permutations::<2>().count(), 1);
+        assert_eq!(alternating_permutations::<5>().count(), 16);
+        assert_eq!(alternating_permutations::<7>().count(), 272);
+        assert_eq!(alternating_permutations::<9>().count(), 7936);
+    }
+
+    #[test]
+    fn zigzag() {
+        let mut last = None;
+        for p in alternating_permutations::<7>() {
+            for (i, w) in p.as_array().windows(2).enumerate() {
+                assert_eq!(w[0] < w[1], i % 2 == 0);
+            }
+            assert!(last < Some(p));
+            last = Some(p);
+        }
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut zigzags = alternating_permutations::<0>();
+        assert_eq!(zigzags.next().map(|p| *p.as_array()), Some([]));
+        assert!(zigzags.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod descent_test {
+    use super::*;
+    use crate::counting::factorial;
+    use crate::make_array;
+    use crate::SliceExt;
+    use alloc::vec::Vec;
+
+    fn descents(p: &[usize]) -> usize {
+        p.windows(2).filter(|w| w[0] > w[1]).count()
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A008292
+        let counts: Vec<_> = (0..6)
+            .map(|d| descent_permutations::<6>(d).count())
+            .collect();
+        assert_eq!(counts, [1, 57, 302, 302, 57, 1]);
+        let total: usize = (0..8).map(|d| descent_permutations::<8>(d).count()).sum();
+        assert_eq!(total, factorial(8).unwrap());
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let indices: [usize; 5] = make_array(|i| i);
+        for d in 0..5 {
+            let mut all: Vec<_> = indices
+                .permutations::<5>()
+                .map(|p| make_array(|i| *p[i]))
+                .filter(|p: &[usize; 5]| descents(p) == d)
+                .collect();
+            all.sort_unstable();
+            assert!(descent_permutations::<5>(d).map(|p| *p.as_array()).eq(all));
+        }
+    }
+
+    #[test]
+    fn none_on_too_many_descents() {
+        let mut permutations = descent_permutations::<3>(3);
+        assert!(permutations.next().is_none());
+        assert!(permutations.next().is_none());
+        assert!(descent_permutations::<1>(1).next().is_none());
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut permutations = descent_permutations::<0>(0);
+        assert_eq!(permutations.next().map(|p| *p.as_array()), Some([]));
+        assert!(permutations.next().is_none());
+        assert!(descent_permutations::<0>(1).next().is_none());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    combinations::LazyCombinationGenerator, make_array, permutations::LazyPermutationGenerator,
+    try_make_array,
+};
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+
+/// Take the items at the ascending `indices` from a fresh copy of `iter`, in a
+/// single pass.
+fn gather<I, const K: usize>(iter: &I, indices: &[usize; K]) -> Option<[I::Item; K]>
+where
+    I: Iterator + Clone,
+{
+    let mut iter = iter.clone();
+    let mut pos = 0;
+    try_make_array(|i| {
+        let item = iter.nth(indices[i] - pos);
+        pos = indices[i] + 1;
+        item
+    })
+    .ok()
+}
+
+/// An iterator which can be traversed again by cloning it.
+///
+/// This `struct` is created by the [`rewindable`] method on [`IterExt`]. See its
+/// documentation for more.
+///
+/// [`rewindable`]: super::IterExt::rewindable
+/// [`IterExt`]: super::IterExt
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Rewindable<I> {
+    iter: I,
+}
+
+impl<I> Rewindable<I>
+where
+    I: Iterator + Clone + ExactSizeIterator,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length combinations of
+    /// the elements from the iterator, without buffering them.
+    ///
+    /// This produces the same items in the same order as
+    /// [`IterExt::combinations`](super::IterExt::combinations).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut combinations = (1..5).rewindable().combinations();
+    /// assert_eq!(combinations.next(), Some([1, 2, 3]));
+    /// assert_eq!(combinations.next(), Some([1, 2, 4]));
+    /// assert_eq!(combinations.next(), Some([1, 3, 4]));
+    /// assert_eq!(combinations.next(), Some([2, 3, 4]));
+    /// assert_eq!(combinations.next(), None);
+    /// ```
+    pub fn combinations<const K: usize>(self) -> RewindableCombinations<I, K> {
+        RewindableCombinations::new(self.iter)
+    }
+
+    /// Return an iterator adaptor that iterates over the k-length permutations of
+    /// the elements from the iterator, without buffering them.
+    ///
+    /// This produces the same items in the same order as
+    /// [`IterExt::permutations`](super::IterExt::permutations). Only the `K`
+    /// elements of the current combination are kept around while it is being
+    /// permuted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use const_combinations::IterExt;
+    ///
+    /// let mut permutations = (0..3).rewindable().permutations();
+    /// assert_eq!(permutations.next(), Some([0, 1]));
+    /// assert_eq!(permutations.next(), Some([1, 0]));
+    /// assert_eq!(permutations.next(), Some([0, 2]));
+    /// assert_eq!(permutations.next(), Some([2, 0]));
+    /// assert_eq!(permutations.next(), Some([1, 2]));
+    /// assert_eq!(permutations.next(), Some([2, 1]));
+    /// assert_eq!(permutations.next(), None);
+    /// ```
+    pub fn permutations<const K: usize>(self) -> RewindablePermutations<I, K>
+    where
+        I::Item: Clone,
+    {
+        RewindablePermutations::new(self.iter)
+    }
+}
+
+impl<I> Iterator for Rewindable<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Rewindable<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for Rewindable<I> where I: FusedIterator {}
+
+/// An iterator that returns k-length combinations of values from a rewindable
+/// iterator.
+///
+/// This `struct` is created by the [`combinations`] method on [`Rewindable`]. See
+/// its documentation for more.
+///
+/// [`combinations`]: Rewindable::combinations
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct RewindableCombinations<I, const K: usize> {
+    iter: I,
+    len: usize,
+    gen: LazyCombinationGenerator<K>,
+}
+
+impl<I, const K: usize> RewindableCombinations<I, K> {
+    pub(crate) fn new(iter: I) -> Self
+    where
+        I: ExactSizeIterator,
+    {
+        Self::with_len(iter.len(), iter)
+    }
+
+    pub(crate) fn with_len(len: usize, iter: I) -> Self {
+        Self {
+            len,
+            iter,
+            gen: LazyCombinationGenerator::new(),
+        }
+    }
+}
+
+impl<I, const K: usize> Iterator for RewindableCombinations<I, K>
+where
+    I: Iterator + Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        if self.gen.is_done(self.len) {
+            return None;
+        }
+        let res = gather(&self.iter, self.gen.indices());
+        self.gen.step();
+        res
+    }
+}
+
+impl<I, const K: usize> FusedIterator for RewindableCombinations<I, K> where I: Iterator + Clone {}
+
+/// An iterator that returns k-length permutations of values from a rewindable
+/// iterator.
+///
+/// This `struct` is created by the [`permutations`] method on [`Rewindable`]. See
+/// its documentation for more.
+///
+/// [`permutations`]: Rewindable::permutations
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct RewindablePermutations<I, const K: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    len: usize,
+    comb_gen: LazyCombinationGenerator<K>,
+    perm_gen: LazyPermutationGenerator<K>,
+    // The elements of the current combination
+    items: Option<[I::Item; K]>,
+}
+
+impl<I, const K: usize> RewindablePermutations<I, K>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I) -> Self
+    where
+        I: ExactSizeIterator,
+    {
+        Self::with_len(iter.len(), iter)
+    }
+
+    pub(crate) fn with_len(len: usize, iter: I) -> Self {
+        Self {
+            len,
+            iter,
+            comb_gen: LazyCombinationGenerator::new(),
+            perm_gen: LazyPermutationGenerator::new(),
+            items: None,
+        }
+    }
+}
+
+impl<I, const K: usize> Iterator for RewindablePermutations<I, K>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    type Item = [I::Item; K];
+
+    fn next(&mut self) -> Option<[I::Item; K]> {
+        if self.comb_gen.is_done(self.len) {
+            return None;
+        }
+        if self.items.is_none() {
+            self.items = gather(&self.iter, self.comb_gen.indices());
+        }
+        let items = self.items.as_ref()?;
+        let perm_indices = self.perm_gen.indices();
+        let res = make_array(|i| items[perm_indices[i]].clone());
+        self.perm_gen.step();
+        if self.perm_gen.is_done() {
+            // Reset the permutation generator and move to the next combination
+            self.perm_gen.reset();
+            self.comb_gen.step();
+            self.items = None;
+        }
+        Some(res)
+    }
+}
+
+impl<I, const K: usize> FusedIterator for RewindablePermutations<I, K>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IterExt;
+
+    #[test]
+    fn matches_buffered() {
+        assert!((0..7)
+            .rewindable()
+            .combinations::<3>()
+            .eq((0..7).combinations::<3>()));
+        assert!((0..6)
+            .rewindable()
+            .permutations::<3>()
+            .eq((0..6).permutations::<3>()));
+        assert_eq!((0..6).rewindable().permutations::<3>().count(), 120);
+    }
+
+    #[test]
+    fn large_source() {
+        let mut combinations = (0..usize::MAX).rewindable().combinations();
+        assert_eq!(combinations.next(), Some([0, 1, 2]));
+        assert_eq!(combinations.next(), Some([0, 1, 3]));
+        assert_eq!(combinations.next(), Some([0, 2, 3]));
+        assert_eq!(combinations.next(), Some([1, 2, 3]));
+        assert_eq!(combinations.next(), Some([0, 1, 4]));
+    }
+
+    #[test]
+    fn chars() {
+        use crate::StrExt;
+
+        let word = "héllo";
+        let chars: alloc::vec::Vec<char> = word.chars().collect();
+        assert!(word
+            .char_combinations::<3>()
+            .eq(chars.iter().copied().combinations::<3>()));
+        assert!(word
+            .char_permutations::<2>()
+            .eq(chars.iter().copied().permutations::<2>()));
+        assert_eq!("".char_combinations::<0>().next(), Some([]));
+        assert_eq!("ab".char_permutations::<3>().next(), None);
+    }
+
+    #[test]
+    fn btree() {
+        use crate::{BTreeMapExt, BTreeSetExt, SliceExt};
+        use alloc::collections::{BTreeMap, BTreeSet};
+
+        let items = [7, 2, 9, 4, 1];
+        let set: BTreeSet<_> = items.iter().copied().collect();
+        let map: BTreeMap<_, _> = items.iter().map(|&i| (i, ())).collect();
+        let sorted = [1, 2, 4, 7, 9];
+        assert!(set.combinations::<3>().eq(sorted.combinations::<3>()));
+        assert!(map.key_combinations::<2>().eq(sorted.combinations::<2>()));
+        assert_eq!(set.combinations::<3>().count(), 10);
+        assert_eq!(BTreeSet::<u8>::new().combinations::<1>().next(), None);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut combinations = (0..2).rewindable().combinations::<3>();
+        assert_eq!(combinations.next(), None);
+        let mut permutations = (0..2).rewindable().permutations::<3>();
+        assert_eq!(permutations.next(), None);
+        assert_eq!(permutations.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut combinations = (0..2).rewindable().combinations();
+        assert_eq!(combinations.next(), Some([]));
+        assert_eq!(combinations.next(), None);
+        let mut permutations = (0..2).rewindable().permutations();
+        assert_eq!(permutations.next(), Some([]));
+        assert_eq!(permutations.next(), None);
+    }
+}
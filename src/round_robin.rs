@@ -0,0 +1,159 @@
+use core::iter::{ExactSizeIterator, FusedIterator, Iterator};
+
+/// Return an iterator over the rounds of a round-robin tournament between `N`
+/// participants.
+///
+/// Every round pairs the participants `0..N` up into `P` disjoint matches, and
+/// over all the rounds every participant meets every other one exactly once.
+/// `P` has to be `N / 2`, which is checked at compile time. The rounds are
+/// built with the circle method: one seat stays in place while the others
+/// rotate around it. With an even number of participants there are `N - 1`
+/// rounds, and participant `N - 1` sits in the fixed seat. With an odd number
+/// there are `N` rounds, the fixed seat is an empty bye seat, and every
+/// participant rotates. In each round the one participant paired with the bye
+/// seat isn't in any of the matches.
+///
+/// Each match is yielded as a pair with the lower participant first, and the
+/// matches of a round are sorted.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::round_robin;
+///
+/// let mut rounds = round_robin::<4, 2>();
+/// assert_eq!(rounds.next(), Some([(0, 3), (1, 2)]));
+/// assert_eq!(rounds.next(), Some([(0, 2), (1, 3)]));
+/// assert_eq!(rounds.next(), Some([(0, 1), (2, 3)]));
+/// assert_eq!(rounds.next(), None);
+///
+/// // Participant 0 has a bye in the first round
+/// let mut rounds = round_robin::<3, 1>();
+/// assert_eq!(rounds.next(), Some([(1, 2)]));
+/// assert_eq!(rounds.count(), 2);
+/// ```
+#[doc(alias = "circle_method")]
+#[doc(alias = "tournament")]
+pub fn round_robin<const N: usize, const P: usize>() -> RoundRobin<N, P> {
+    RoundRobin::new()
+}
+
+/// An iterator that returns the rounds of a round-robin tournament.
+///
+/// This `struct` is created by the [`round_robin`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct RoundRobin<const N: usize, const P: usize> {
+    round: usize,
+}
+
+impl<const N: usize, const P: usize> RoundRobin<N, P> {
+    // With an odd number of participants, a made up participant `N` stands in
+    // for the bye
+    const SEATS: usize = N + N % 2;
+    const ROUNDS: usize = if N < 2 { 0 } else { Self::SEATS - 1 };
+
+    pub(crate) fn new() -> Self {
+        const { assert!(P == N / 2, "`P` has to be half of `N`, rounded down") };
+        Self { round: 0 }
+    }
+}
+
+impl<const N: usize, const P: usize> Iterator for RoundRobin<N, P> {
+    type Item = [(usize, usize); P];
+
+    fn next(&mut self) -> Option<[(usize, usize); P]> {
+        if self.round == Self::ROUNDS {
+            return None;
+        }
+        let rotating = Self::SEATS - 1;
+        let round = self.round;
+        self.round += 1;
+        let mut matches = [(0, 0); P];
+        let mut len = 0;
+        // The fixed seat plays whoever is across from it, unless it's the bye
+        if Self::SEATS == N {
+            matches[0] = (round, Self::SEATS - 1);
+            len = 1;
+        }
+        for i in 1..Self::SEATS / 2 {
+            let a = (round + i) % rotating;
+            let b = (round + rotating - i) % rotating;
+            matches[len] = (a.min(b), a.max(b));
+            len += 1;
+        }
+        matches.sort_unstable();
+        Some(matches)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = Self::ROUNDS - self.round;
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize, const P: usize> ExactSizeIterator for RoundRobin<N, P> {}
+
+impl<const N: usize, const P: usize> FusedIterator for RoundRobin<N, P> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn check<const N: usize, const P: usize>() {
+        let rounds: Vec<_> = round_robin::<N, P>().collect();
+        assert_eq!(rounds.len(), if N < 2 { 0 } else { N - 1 + N % 2 });
+        let mut met = vec![vec![0; N]; N];
+        for round in &rounds {
+            let mut seen = vec![false; N];
+            for &(a, b) in round {
+                assert!(a < b && b < N);
+                assert!(!seen[a] && !seen[b]);
+                seen[a] = true;
+                seen[b] = true;
+                met[a][b] += 1;
+            }
+        }
+        assert!(met
+            .iter()
+            .enumerate()
+            .all(|(a, row)| row[a + 1..].iter().all(|&count| count == 1)));
+    }
+
+    #[test]
+    fn everyone_meets_once() {
+        check::<0, 0>();
+        check::<1, 0>();
+        check::<2, 1>();
+        check::<5, 2>();
+        check::<6, 3>();
+        check::<9, 4>();
+        check::<16, 8>();
+    }
+
+    #[test]
+    fn byes() {
+        // Every participant sits out exactly one round
+        let mut byes = [0; 5];
+        for round in round_robin::<5, 2>() {
+            for (p, byes) in byes.iter_mut().enumerate() {
+                if round.iter().all(|&(a, b)| a != p && b != p) {
+                    *byes += 1;
+                }
+            }
+        }
+        assert_eq!(byes, [1; 5]);
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut rounds = round_robin::<8, 4>();
+        assert_eq!(rounds.len(), 7);
+        rounds.next();
+        assert_eq!(rounds.len(), 6);
+        assert_eq!(round_robin::<1, 0>().next(), None);
+    }
+}
@@ -0,0 +1,292 @@
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazySetPartitionGenerator<const N: usize> {
+    // Restricted growth string: `blocks[i]` is the block containing element `i`
+    blocks: [usize; N],
+    // `maxima[i]` is the largest block index used in `blocks[..=i]`
+    maxima: [usize; N],
+    // If set, only partitions with exactly this many blocks are generated
+    exact: Option<usize>,
+    done: bool,
+}
+
+impl<const N: usize> LazySetPartitionGenerator<N> {
+    pub fn new() -> Self {
+        Self {
+            blocks: [0; N],
+            maxima: [0; N],
+            exact: None,
+            done: false,
+        }
+    }
+
+    pub fn with_blocks(k: usize) -> Self {
+        let mut gen = Self {
+            exact: Some(k),
+            ..Self::new()
+        };
+        if N == 0 || k == 0 || k > N {
+            gen.done = !(N == 0 && k == 0);
+        } else {
+            gen.fill(1, 0);
+        }
+        gen
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn blocks(&self) -> &[usize; N] {
+        &self.blocks
+    }
+
+    fn missing_blocks(&self, max: usize) -> usize {
+        self.exact.map_or(0, |k| k - 1 - max)
+    }
+
+    fn fill(&mut self, start: usize, mut max: usize) {
+        // Put everything from `start` onwards into the first block, except for
+        // the tail which is needed to open any blocks which are still missing
+        let missing = self.missing_blocks(max);
+        for j in start..N {
+            if N - j <= missing {
+                max += 1;
+                self.blocks[j] = max;
+            } else {
+                self.blocks[j] = 0;
+            }
+            self.maxima[j] = max;
+        }
+    }
+
+    pub fn step(&mut self) {
+        let limit = self.exact.unwrap_or(usize::MAX);
+        // Find the last element which can move to a later block, either an
+        // existing one or a brand new one
+        let mut i = N;
+        while i > 1 {
+            i -= 1;
+            let block = self.blocks[i] + 1;
+            if block > self.maxima[i - 1] + 1 || block >= limit {
+                continue;
+            }
+            let max = self.maxima[i - 1].max(block);
+            if self.missing_blocks(max) > N - 1 - i {
+                continue;
+            }
+            self.blocks[i] = block;
+            self.maxima[i] = max;
+            self.fill(i + 1, max);
+            return;
+        }
+        self.done = true;
+    }
+}
+
+/// Return an iterator over the partitions of an `N`-element set.
+///
+/// Each partition is yielded as a block-assignment array (a restricted growth
+/// string): element `i` belongs to block `blocks[i]`, and blocks are numbered in
+/// the order of their first element. Partitions are produced in lexicographic
+/// order of these arrays; there are as many of them as the `N`th Bell number.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::set_partitions;
+///
+/// let mut partitions = set_partitions::<3>();
+/// assert_eq!(partitions.next(), Some([0, 0, 0]));
+/// assert_eq!(partitions.next(), Some([0, 0, 1]));
+/// assert_eq!(partitions.next(), Some([0, 1, 0]));
+/// assert_eq!(partitions.next(), Some([0, 1, 1]));
+/// assert_eq!(partitions.next(), Some([0, 1, 2]));
+/// assert_eq!(partitions.next(), None);
+/// ```
+pub fn set_partitions<const N: usize>() -> SetPartitions<N> {
+    SetPartitions::new()
+}
+
+/// An iterator that returns the partitions of a set as block-assignment arrays.
+///
+/// This `struct` is created by the [`set_partitions`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SetPartitions<const N: usize> {
+    gen: LazySetPartitionGenerator<N>,
+}
+
+impl<const N: usize> SetPartitions<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazySetPartitionGenerator::new(),
+        }
+    }
+}
+
+impl<const N: usize> Iterator for SetPartitions<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.blocks();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize> FusedIterator for SetPartitions<N> {}
+
+/// Return an iterator over the partitions of an `N`-element set into exactly `K`
+/// non-empty blocks.
+///
+/// Partitions are yielded as block-assignment arrays in the same form and order as
+/// [`set_partitions`], skipping every partition with a different number of blocks
+/// without generating it. There are as many of them as the Stirling number of the
+/// second kind `S(N, K)`. If `K` is greater than `N` the resulting iterator will
+/// yield no items.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::set_partitions_into;
+///
+/// let mut partitions = set_partitions_into::<4, 3>();
+/// assert_eq!(partitions.next(), Some([0, 0, 1, 2]));
+/// assert_eq!(partitions.next(), Some([0, 1, 0, 2]));
+/// assert_eq!(partitions.next(), Some([0, 1, 1, 2]));
+/// assert_eq!(partitions.next(), Some([0, 1, 2, 0]));
+/// assert_eq!(partitions.next(), Some([0, 1, 2, 1]));
+/// assert_eq!(partitions.next(), Some([0, 1, 2, 2]));
+/// assert_eq!(partitions.next(), None);
+/// ```
+pub fn set_partitions_into<const N: usize, const K: usize>() -> SetPartitionsInto<N, K> {
+    SetPartitionsInto::new()
+}
+
+/// An iterator that returns the partitions of a set into exactly k blocks.
+///
+/// This `struct` is created by the [`set_partitions_into`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SetPartitionsInto<const N: usize, const K: usize> {
+    gen: LazySetPartitionGenerator<N>,
+}
+
+impl<const N: usize, const K: usize> SetPartitionsInto<N, K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            gen: LazySetPartitionGenerator::with_blocks(K),
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> Iterator for SetPartitionsInto<N, K> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        if self.gen.is_done() {
+            None
+        } else {
+            let res = *self.gen.blocks();
+            self.gen.step();
+            Some(res)
+        }
+    }
+}
+
+impl<const N: usize, const K: usize> FusedIterator for SetPartitionsInto<N, K> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut partitions = set_partitions::<4>();
+        assert_eq!(partitions.next(), Some([0, 0, 0, 0]));
+        assert_eq!(partitions.next(), Some([0, 0, 0, 1]));
+        assert_eq!(partitions.next(), Some([0, 0, 1, 0]));
+        assert_eq!(partitions.next(), Some([0, 0, 1, 1]));
+        assert_eq!(partitions.next(), Some([0, 0, 1, 2]));
+        assert_eq!(partitions.next(), Some([0, 1, 0, 0]));
+        assert_eq!(partitions.nth(8), Some([0, 1, 2, 3]));
+        assert_eq!(partitions.next(), None);
+        assert_eq!(partitions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A000110
+        assert_eq!(set_partitions::<1>().count(), 1);
+        assert_eq!(set_partitions::<5>().count(), 52);
+        assert_eq!(set_partitions::<7>().count(), 877);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut partitions = set_partitions::<0>();
+        assert_eq!(partitions.next(), Some([]));
+        assert_eq!(partitions.next(), None);
+    }
+}
+
+#[cfg(test)]
+mod into_test {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let mut partitions = set_partitions_into::<4, 2>();
+        assert_eq!(partitions.next(), Some([0, 0, 0, 1]));
+        assert_eq!(partitions.next(), Some([0, 0, 1, 0]));
+        assert_eq!(partitions.next(), Some([0, 0, 1, 1]));
+        assert_eq!(partitions.next(), Some([0, 1, 0, 0]));
+        assert_eq!(partitions.next(), Some([0, 1, 0, 1]));
+        assert_eq!(partitions.next(), Some([0, 1, 1, 0]));
+        assert_eq!(partitions.next(), Some([0, 1, 1, 1]));
+        assert_eq!(partitions.next(), None);
+        assert_eq!(partitions.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        // https://oeis.org/A008277
+        assert_eq!(set_partitions_into::<5, 1>().count(), 1);
+        assert_eq!(set_partitions_into::<5, 2>().count(), 15);
+        assert_eq!(set_partitions_into::<5, 3>().count(), 25);
+        assert_eq!(set_partitions_into::<5, 4>().count(), 10);
+        assert_eq!(set_partitions_into::<5, 5>().count(), 1);
+        assert_eq!(set_partitions_into::<7, 3>().count(), 301);
+    }
+
+    #[test]
+    fn matches_filtered() {
+        let filtered = set_partitions::<6>()
+            .filter(|blocks| blocks.iter().max() == Some(&2))
+            .eq(set_partitions_into::<6, 3>());
+        assert!(filtered);
+    }
+
+    #[test]
+    fn none_on_size_too_big() {
+        let mut partitions = set_partitions_into::<2, 3>();
+        assert_eq!(partitions.next(), None);
+        let mut partitions = set_partitions_into::<2, 0>();
+        assert_eq!(partitions.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut partitions = set_partitions_into::<0, 0>();
+        assert_eq!(partitions.next(), Some([]));
+        assert_eq!(partitions.next(), None);
+    }
+}
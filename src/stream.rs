@@ -0,0 +1,121 @@
+//! A `Stream` adapter which enumerates an iterator in budgeted batches.
+
+use crate::{SliceCombinations, SlicePermutations};
+use core::iter::FusedIterator;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
+
+/// A stream that returns the items of an iterator, yielding to the executor after
+/// every `budget` items.
+///
+/// This `struct` is created by the [`into_stream`] method on [`IterExt`]. See its
+/// documentation for more.
+///
+/// [`into_stream`]: super::IterExt::into_stream
+/// [`IterExt`]: super::IterExt
+#[derive(Clone)]
+#[must_use = "streams do nothing unless polled"]
+pub struct BudgetedStream<I> {
+    iter: I,
+    budget: usize,
+    left: usize,
+}
+
+/// A stream over the k-length combinations of the elements from a slice.
+pub type CombinationsStream<'a, T, const K: usize> = BudgetedStream<SliceCombinations<'a, T, K>>;
+
+/// A stream over the k-length permutations of the elements from a slice.
+pub type PermutationsStream<'a, T, const K: usize> = BudgetedStream<SlicePermutations<'a, T, K>>;
+
+impl<I> BudgetedStream<I> {
+    pub(crate) fn new(iter: I, budget: usize) -> Self {
+        assert!(budget > 0, "budget must be positive");
+        Self {
+            iter,
+            budget,
+            left: budget,
+        }
+    }
+}
+
+impl<I> Stream for BudgetedStream<I>
+where
+    I: Iterator + Unpin,
+{
+    type Item = I::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        let this = self.get_mut();
+        if this.left == 0 {
+            // Give other tasks a turn, and ask to be polled again right away
+            this.left = this.budget;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        this.left -= 1;
+        Poll::Ready(this.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> FusedStream for BudgetedStream<I>
+where
+    I: FusedIterator + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.iter.size_hint().1 == Some(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IterExt, SliceExt};
+    use alloc::vec::Vec;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+    use futures_core::stream::{FusedStream, Stream};
+
+    fn poll_all<S: Stream + Unpin>(mut stream: S) -> (Vec<S::Item>, usize) {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = Vec::new();
+        let mut pending = 0;
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return (items, pending),
+                Poll::Pending => pending += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn yields_between_batches() {
+        let items = [1, 2, 3, 4, 5];
+        let (all, pending) = poll_all(items.combinations::<2>().into_stream(3));
+        assert!(all.into_iter().eq(items.combinations::<2>()));
+        // Ten combinations and the final `None` take eleven polls
+        assert_eq!(pending, 3);
+        let (all, pending) = poll_all(items.permutations::<5>().into_stream(1000));
+        assert_eq!((all.len(), pending), (120, 0));
+    }
+
+    #[test]
+    fn terminated() {
+        let mut stream = [1, 2].combinations::<2>().into_stream(1);
+        assert!(!stream.is_terminated());
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_ready());
+        assert!(stream.is_terminated());
+        assert_eq!(stream.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "budget must be positive")]
+    fn zero_budget() {
+        let _ = [1].combinations::<1>().into_stream(0);
+    }
+}
@@ -0,0 +1,252 @@
+//! Lending iterators which hand out views into a reused buffer.
+//!
+//! The [`combinations`] and [`permutations`] adaptors clone every element of
+//! every array they produce. Consumers which only read the arrays can instead
+//! convert them into a [`StreamingCombinations`] or [`StreamingPermutations`],
+//! which keep the current array in a buffer and only replace the elements that
+//! changed since the previous one.
+//!
+//! [`combinations`]: crate::IterExt::combinations
+//! [`permutations`]: crate::IterExt::permutations
+
+use crate::{combinations, make_array, permutations};
+use alloc::vec::Vec;
+use core::iter::Iterator;
+
+/// An iterator whose items borrow from the iterator itself.
+///
+/// Each item has to be dropped before the next one can be taken, which is what
+/// allows the iterator to reuse its storage between items.
+pub trait LendingIterator {
+    /// The type of the items being lent.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// Advance the iterator and return the next item.
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+
+    /// Call a closure on every remaining item.
+    fn for_each<F>(mut self, mut f: F)
+    where
+        Self: Sized,
+        F: FnMut(Self::Item<'_>),
+    {
+        while let Some(item) = self.next() {
+            f(item);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Buffer<T, const K: usize> {
+    current: Option<[T; K]>,
+    indices: [usize; K],
+}
+
+impl<T, const K: usize> Buffer<T, K>
+where
+    T: Clone,
+{
+    fn new() -> Self {
+        Self {
+            current: None,
+            indices: [0; K],
+        }
+    }
+
+    fn update(&mut self, items: &[T], indices: [usize; K]) -> &[T; K] {
+        let last = self.indices;
+        self.indices = indices;
+        if let Some(current) = &mut self.current {
+            let mut changed = (0..K).filter(|&i| last[i] != indices[i]);
+            match (changed.next(), changed.next(), changed.next()) {
+                // Two elements trading places only needs a swap
+                (Some(i), Some(j), None) if indices[i] == last[j] && indices[j] == last[i] => {
+                    current.swap(i, j)
+                }
+                _ => {
+                    for i in 0..K {
+                        if last[i] != indices[i] {
+                            current[i] = items[indices[i]].clone();
+                        }
+                    }
+                }
+            }
+        } else {
+            self.current = Some(make_array(|i| items[indices[i]].clone()));
+        }
+        self.current.as_ref().unwrap()
+    }
+}
+
+/// A lending iterator that returns k-length combinations of values from `iter`.
+///
+/// This `struct` is created by the [`streaming`] method on [`Combinations`].
+/// See its documentation for more.
+///
+/// [`streaming`]: crate::Combinations::streaming
+/// [`Combinations`]: crate::Combinations
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct StreamingCombinations<I, const K: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    items: Vec<I::Item>,
+    state: combinations::State<K>,
+    buffer: Buffer<I::Item, K>,
+}
+
+impl<I, const K: usize> StreamingCombinations<I, K>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    pub(crate) fn new(iter: I, items: Vec<I::Item>, state: combinations::State<K>) -> Self {
+        Self {
+            iter,
+            items,
+            state,
+            buffer: Buffer::new(),
+        }
+    }
+}
+
+impl<I, const K: usize> LendingIterator for StreamingCombinations<I, K>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item<'a>
+        = &'a [I::Item; K]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&[I::Item; K]> {
+        if let Some(max_index) = self.state.max_index() {
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            self.items.extend(self.iter.by_ref().take(missing_count));
+        }
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        Some(self.buffer.update(&self.items, indices))
+    }
+}
+
+/// A lending iterator that returns k-length permutations of values from `iter`.
+///
+/// This `struct` is created by the [`streaming`] method on [`Permutations`].
+/// See its documentation for more.
+///
+/// [`streaming`]: crate::Permutations::streaming
+/// [`Permutations`]: crate::Permutations
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct StreamingPermutations<I, const K: usize>
+where
+    I: Iterator,
+{
+    iter: I,
+    items: Vec<I::Item>,
+    state: permutations::State<K>,
+    buffer: Buffer<I::Item, K>,
+}
+
+impl<I, const K: usize> StreamingPermutations<I, K>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    pub(crate) fn new(iter: I, items: Vec<I::Item>, state: permutations::State<K>) -> Self {
+        Self {
+            iter,
+            items,
+            state,
+            buffer: Buffer::new(),
+        }
+    }
+}
+
+impl<I, const K: usize> LendingIterator for StreamingPermutations<I, K>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item<'a>
+        = &'a [I::Item; K]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&[I::Item; K]> {
+        if let Some(max_index) = self.state.max_index() {
+            let missing_count = (max_index + 1).saturating_sub(self.items.len());
+            self.items.extend(self.iter.by_ref().take(missing_count));
+        }
+        let indices = self.state.get_indices_and_step(self.items.len())?;
+        Some(self.buffer.update(&self.items, indices))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IterExt;
+    use core::cell::Cell;
+
+    struct Counted<'a>(u8, &'a Cell<usize>);
+
+    impl Clone for Counted<'_> {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Counted(self.0, self.1)
+        }
+    }
+
+    #[test]
+    fn matches_combinations() {
+        let mut all = Vec::new();
+        let mut combinations = (0..7).combinations::<4>().streaming();
+        while let Some(arr) = combinations.next() {
+            all.push(*arr);
+        }
+        assert!(all.into_iter().eq((0..7).combinations::<4>()));
+        assert!((0..2).combinations::<3>().streaming().next().is_none());
+    }
+
+    #[test]
+    fn matches_permutations() {
+        let mut all = Vec::new();
+        (0..5)
+            .permutations::<3>()
+            .streaming()
+            .for_each(|arr| all.push(*arr));
+        assert!(all.into_iter().eq((0..5).permutations::<3>()));
+        let mut empty = (0..2).permutations::<0>().streaming();
+        assert_eq!(empty.next(), Some(&[]));
+        assert_eq!(empty.next(), None);
+    }
+
+    #[test]
+    fn fewer_clones() {
+        let clones = Cell::new(0);
+        let items: Vec<_> = (0..6).map(|i| Counted(i, &clones)).collect();
+        let mut all = Vec::new();
+        let mut permutations = items.iter().cloned().permutations::<3>().streaming();
+        while let Some(arr) = permutations.next() {
+            all.push(arr.iter().map(|c| c.0).collect::<Vec<_>>());
+        }
+        let streamed = clones.replace(0);
+        let expected: Vec<Vec<u8>> = items
+            .iter()
+            .cloned()
+            .permutations::<3>()
+            .map(|arr| arr.iter().map(|c| c.0).collect())
+            .collect();
+        assert_eq!(all, expected);
+        // Cloning every element of all 120 arrays takes 360 clones, while the
+        // buffer only clones when moving on to another combination
+        assert!(clones.get() >= 360);
+        assert!(streamed < 6 + 20 * 3);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::combinations::LazyLexCombinationGenerator;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+/// An iterator that returns all subsets of values from `slice`, ordered by size.
+///
+/// This `struct` is created by the [`subsets_by_size`] method on [`SliceExt`]. See
+/// its documentation for more.
+///
+/// [`subsets_by_size`]: super::SliceExt::subsets_by_size
+/// [`SliceExt`]: super::SliceExt
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct SubsetsBySize<'a, T> {
+    items: &'a [T],
+    gen: LazyLexCombinationGenerator,
+    size: usize,
+}
+
+impl<'a, T> SubsetsBySize<'a, T> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            gen: LazyLexCombinationGenerator::new(0),
+            size: 0,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SubsetsBySize<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Vec<&'a T>> {
+        if self.gen.is_done(self.items.len()) {
+            if self.size >= self.items.len() {
+                return None;
+            }
+            // Move on to the subsets which are one element larger
+            self.size += 1;
+            self.gen = LazyLexCombinationGenerator::new(self.size);
+        }
+        let res = self.gen.indices().iter().map(|&i| &self.items[i]).collect();
+        self.gen.step(self.items.len());
+        Some(res)
+    }
+}
+
+impl<T> FusedIterator for SubsetsBySize<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use crate::SliceExt;
+    use alloc::vec;
+
+    #[test]
+    fn order() {
+        let mut subsets = [1, 2, 3].subsets_by_size();
+        assert_eq!(subsets.next(), Some(vec![]));
+        assert_eq!(subsets.next(), Some(vec![&1]));
+        assert_eq!(subsets.next(), Some(vec![&2]));
+        assert_eq!(subsets.next(), Some(vec![&3]));
+        assert_eq!(subsets.next(), Some(vec![&1, &2]));
+        assert_eq!(subsets.next(), Some(vec![&1, &3]));
+        assert_eq!(subsets.next(), Some(vec![&2, &3]));
+        assert_eq!(subsets.next(), Some(vec![&1, &2, &3]));
+        assert_eq!(subsets.next(), None);
+        assert_eq!(subsets.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        assert_eq!([0; 10].subsets_by_size().count(), 1 << 10);
+    }
+
+    #[test]
+    fn empty_slice() {
+        let mut subsets = <[u8]>::subsets_by_size(&[]);
+        assert_eq!(subsets.next(), Some(vec![]));
+        assert_eq!(subsets.next(), None);
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Iterator};
+
+#[derive(Clone)]
+pub struct LazyTableauGenerator<const N: usize> {
+    shape: Vec<usize>,
+    // Lattice word: `rows[v]` is the row the entry `v` is in
+    rows: [usize; N],
+    // `lengths[r]` is the number of entries in row `r`
+    lengths: Vec<usize>,
+    done: bool,
+}
+
+impl<const N: usize> LazyTableauGenerator<N> {
+    pub fn new(shape: Vec<usize>) -> Self {
+        let lengths = vec![0; shape.len()];
+        let mut gen = Self {
+            shape,
+            rows: [0; N],
+            lengths,
+            done: false,
+        };
+        gen.fill(0);
+        gen
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn rows(&self) -> &[usize; N] {
+        &self.rows
+    }
+
+    /// Return whether another entry fits at the end of `row`, keeping the
+    /// entries so far in the shape of a partition.
+    fn fits(&self, row: usize) -> bool {
+        self.lengths[row] < self.shape[row]
+            && (row == 0 || self.lengths[row - 1] > self.lengths[row])
+    }
+
+    fn fill(&mut self, start: usize) {
+        // Put every entry in the topmost row it fits in. The entries so far
+        // always form a partition inside the shape, so one of them has room.
+        for v in start..N {
+            let row = (0..self.shape.len()).find(|&r| self.fits(r)).unwrap();
+            self.rows[v] = row;
+            self.lengths[row] += 1;
+        }
+    }
+
+    pub fn step(&mut self) {
+        // Find the last entry which can move down to a later row
+        for v in (0..N).rev() {
+            let row = self.rows[v];
+            self.lengths[row] -= 1;
+            if let Some(next) = (row + 1..self.shape.len()).find(|&r| self.fits(r)) {
+                self.rows[v] = next;
+                self.lengths[next] += 1;
+                self.fill(v + 1);
+                return;
+            }
+        }
+        self.done = true;
+    }
+}
+
+/// Return an iterator over the standard Young tableaux of a shape.
+///
+/// The shape is a partition of `N`, listing the lengths of the rows from top to
+/// bottom. A standard Young tableau fills its cells with the numbers `0..N`
+/// such that they increase along every row and down every column. Each tableau
+/// is yielded as the flat array of its cells, row after row, which can be split
+/// back up into rows with the lengths from [`YoungTableaux::shape`].
+///
+/// Tableaux are produced in lexicographic order of the rows their entries are
+/// in, `0` first. Returns `None` if `shape` isn't in decreasing order, or if it
+/// doesn't add up to `N`.
+///
+/// # Examples
+///
+/// ```
+/// use const_combinations::young_tableaux;
+///
+/// // 0 1 2     0 1 3     0 1 4     0 2 3     0 2 4
+/// // 3 4       2 4       2 3       1 4       1 3
+/// let mut tableaux = young_tableaux::<5>(&[3, 2]).unwrap();
+/// assert_eq!(tableaux.shape(), &[3, 2]);
+/// assert_eq!(tableaux.next(), Some([0, 1, 2, 3, 4]));
+/// assert_eq!(tableaux.next(), Some([0, 1, 3, 2, 4]));
+/// assert_eq!(tableaux.next(), Some([0, 1, 4, 2, 3]));
+/// assert_eq!(tableaux.next(), Some([0, 2, 3, 1, 4]));
+/// assert_eq!(tableaux.next(), Some([0, 2, 4, 1, 3]));
+/// assert_eq!(tableaux.next(), None);
+///
+/// assert!(young_tableaux::<5>(&[2, 3]).is_none());
+/// ```
+#[doc(alias = "syt")]
+pub fn young_tableaux<const N: usize>(shape: &[usize]) -> Option<YoungTableaux<N>> {
+    let decreasing = shape.windows(2).all(|pair| pair[0] >= pair[1]);
+    let size = shape
+        .iter()
+        .try_fold(0usize, |acc, &len| acc.checked_add(len));
+    if decreasing && size == Some(N) {
+        Some(YoungTableaux::new(shape))
+    } else {
+        None
+    }
+}
+
+/// An iterator that returns the standard Young tableaux of a shape as flat
+/// arrays.
+///
+/// This `struct` is created by the [`young_tableaux`] function. See its
+/// documentation for more.
+#[derive(Clone)]
+#[must_use = "iterators do nothing unless consumed"]
+pub struct YoungTableaux<const N: usize> {
+    gen: LazyTableauGenerator<N>,
+}
+
+impl<const N: usize> YoungTableaux<N> {
+    pub(crate) fn new(shape: &[usize]) -> Self {
+        // Empty rows at the bottom make no difference
+        let rows = shape.iter().take_while(|&&len| len > 0).count();
+        Self {
+            gen: LazyTableauGenerator::new(shape[..rows].to_vec()),
+        }
+    }
+
+    /// Return the lengths of the rows of the tableaux, from top to bottom.
+    ///
+    /// Rows of length zero are left out.
+    pub fn shape(&self) -> &[usize] {
+        self.gen.shape()
+    }
+}
+
+impl<const N: usize> Iterator for YoungTableaux<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        if self.gen.is_done() {
+            return None;
+        }
+        // Start of every row in the flat array, which then moves along the row
+        let mut cursors: Vec<usize> = self
+            .gen
+            .shape()
+            .iter()
+            .scan(0, |start, &len| {
+                *start += len;
+                Some(*start - len)
+            })
+            .collect();
+        let mut res = [0; N];
+        for (v, &row) in self.gen.rows().iter().enumerate() {
+            res[cursors[row]] = v;
+            cursors[row] += 1;
+        }
+        self.gen.step();
+        Some(res)
+    }
+}
+
+impl<const N: usize> FusedIterator for YoungTableaux<N> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::counting::factorial;
+
+    /// Count the tableaux of a shape with the hook length formula.
+    fn hook_lengths(shape: &[usize]) -> usize {
+        let mut hooks = 1;
+        for (r, &len) in shape.iter().enumerate() {
+            for c in 0..len {
+                let below = shape[r + 1..].iter().filter(|&&l| l > c).count();
+                hooks *= len - c + below;
+            }
+        }
+        factorial(shape.iter().sum()).unwrap() / hooks
+    }
+
+    fn check<const N: usize>(shape: &[usize]) {
+        let mut count = 0;
+        for tableau in young_tableaux::<N>(shape).unwrap() {
+            count += 1;
+            let mut start = 0;
+            for (r, &len) in shape.iter().enumerate() {
+                let row = &tableau[start..start + len];
+                assert!(row.windows(2).all(|pair| pair[0] < pair[1]));
+                if r > 0 {
+                    let above = &tableau[start - shape[r - 1]..];
+                    assert!((0..len).all(|c| above[c] < row[c]));
+                }
+                start += len;
+            }
+        }
+        assert_eq!(count, hook_lengths(shape));
+    }
+
+    #[test]
+    fn hook_length_formula() {
+        check::<1>(&[1]);
+        check::<3>(&[2, 1]);
+        check::<6>(&[3, 3]);
+        check::<6>(&[1, 1, 1, 1, 1, 1]);
+        check::<8>(&[4, 3, 1]);
+        check::<9>(&[3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn invalid_shapes() {
+        assert!(young_tableaux::<3>(&[1, 2]).is_none());
+        assert!(young_tableaux::<3>(&[2, 2]).is_none());
+        assert!(young_tableaux::<3>(&[usize::MAX, 4]).is_none());
+    }
+
+    #[test]
+    fn empty_rows() {
+        let mut tableaux = young_tableaux::<2>(&[2, 0, 0]).unwrap();
+        assert_eq!(tableaux.shape(), &[2]);
+        assert_eq!(tableaux.next(), Some([0, 1]));
+        assert_eq!(tableaux.next(), None);
+    }
+
+    #[test]
+    fn empty_arr_on_n_zero() {
+        let mut tableaux = young_tableaux::<0>(&[]).unwrap();
+        assert_eq!(tableaux.next(), Some([]));
+        assert_eq!(tableaux.next(), None);
+        assert_eq!(tableaux.next(), None);
+    }
+}